@@ -47,6 +47,14 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for FarmTokenAttributes<M> {
             original_owner: self.original_owner,
         }
     }
+
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self {
+        let part = self.clone().into_part(payment_amount);
+        self.compounded_reward -= &part.compounded_reward;
+        self.current_farm_amount -= &part.current_farm_amount;
+
+        part
+    }
 }
 
 impl<M: ManagedTypeApi> Mergeable<M> for FarmTokenAttributes<M> {
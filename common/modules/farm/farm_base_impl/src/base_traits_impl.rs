@@ -184,7 +184,7 @@ pub trait FarmContract {
         _sc: &Self::FarmSc,
         _total_exit_amount: &mut BigUint<<Self::FarmSc as ContractBase>::Api>,
         _token_attributes: &Self::AttributesType,
-        _storage_cache: &StorageCache<Self::FarmSc>,
+        _storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
     }
 }
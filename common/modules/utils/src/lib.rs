@@ -116,6 +116,52 @@ pub trait UtilsModule {
         }
     }
 
+    /// Burns the given position and creates one new token for each of the split amounts,
+    /// each holding its proportional part of the original attributes.
+    /// The last token receives whatever is left, so no rounding dust is lost.
+    fn split_and_create_tokens<T: FixedSupplyToken<Self::Api> + Clone + TopEncode + TopDecode>(
+        &self,
+        payment: &EsdtTokenPayment,
+        split_amounts: MultiValueEncoded<BigUint>,
+        mapper: &NonFungibleTokenMapper,
+    ) -> PaymentsVec<Self::Api> {
+        mapper.require_same_token(&payment.token_identifier);
+        require!(
+            split_amounts.len() > 1,
+            "Must split into at least two tokens"
+        );
+
+        let mut remaining_attributes: T =
+            self.get_attributes_as_part_of_fixed_supply(payment, mapper);
+        let last_index = split_amounts.len() - 1;
+        let mut new_tokens = PaymentsVec::new();
+        for (i, amount) in split_amounts.into_iter().enumerate() {
+            require!(amount > 0, "Invalid split amount");
+
+            let remaining_amount = remaining_attributes.get_total_supply();
+            let part_attributes = if i < last_index {
+                require!(
+                    amount < remaining_amount,
+                    "Split amounts must add up to the payment amount"
+                );
+                remaining_attributes.split_part(&amount)
+            } else {
+                require!(
+                    amount == remaining_amount,
+                    "Split amounts must add up to the payment amount"
+                );
+                remaining_attributes.clone()
+            };
+
+            let new_token = mapper.nft_create(amount, &part_attributes);
+            new_tokens.push(new_token);
+        }
+
+        mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        new_tokens
+    }
+
     fn require_valid_token_id(&self, token_id: &TokenIdentifier) {
         require!(token_id.is_valid_esdt_identifier(), "Invalid token ID");
     }
@@ -7,6 +7,10 @@ pub trait FixedSupplyToken<M: ManagedTypeApi> {
 
     fn into_part(self, payment_amount: &BigUint<M>) -> Self;
 
+    /// Splits a part of `payment_amount` off, keeping the rest in `self`.
+    /// Unlike separate `into_part` calls, which round down, the parts always add up to the whole.
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self;
+
     /// full_value * current_supply / total_supply
     fn rule_of_three(&self, current_supply: &BigUint<M>, full_value: &BigUint<M>) -> BigUint<M> {
         let total_supply = self.get_total_supply();
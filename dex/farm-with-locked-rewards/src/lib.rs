@@ -11,7 +11,7 @@ use core::marker::PhantomData;
 use mergeable::Mergeable;
 
 use farm::{
-    base_functions::{BaseFunctionsModule, ClaimRewardsResultType, Wrapper},
+    base_functions::{BaseFunctionsModule, ClaimRewardsResultType, DoubleMultiPayment, Wrapper},
     exit_penalty::{
         DEFAULT_BURN_GAS_LIMIT, DEFAULT_MINUMUM_FARMING_EPOCHS, DEFAULT_PENALTY_PERCENT,
     },
@@ -34,8 +34,11 @@ pub trait Farm:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + farm::base_functions::BaseFunctionsModule
     + farm::exit_penalty::ExitPenaltyModule
+    + farm::fees_collector::FeesCollectorModule
+    + farm::funded_rewards::FundedRewardsModule
     + farm::progress_update::ProgressUpdateModule
     + farm::claim_boost_only::ClaimBoostOnlyModule
+    + farm::transfer_position::TransferPositionModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -217,6 +220,20 @@ pub trait Farm:
         )
     }
 
+    /// The amount of farming tokens that would be kept as penalty when exiting
+    /// `farm_token_amount` of a position with the given attributes at the current epoch.
+    ///
+    /// The penalty only depends on the position, not on its holder, so the view takes
+    /// the exited amount instead of the user, which also covers partial exits.
+    #[view(getExitPenalty)]
+    fn get_exit_penalty_view(
+        &self,
+        farm_token_amount: BigUint,
+        attributes: FarmTokenAttributes<Self::Api>,
+    ) -> BigUint {
+        NoMintWrapper::<Self>::get_exit_penalty(self, &farm_token_amount, &attributes)
+    }
+
     #[payable("*")]
     #[endpoint(mergeFarmTokens)]
     fn merge_farm_tokens_endpoint(
@@ -233,6 +250,40 @@ pub trait Farm:
         merged_farm_token
     }
 
+    #[payable("*")]
+    #[endpoint(splitFarmToken)]
+    fn split_farm_token_endpoint(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let new_farm_tokens = self.split_farm_token::<NoMintWrapper<Self>>(split_amounts);
+        self.send().direct_multi(&caller, &new_farm_tokens);
+
+        new_farm_tokens.into()
+    }
+
+    #[payable("*")]
+    #[endpoint(transferFarmPosition)]
+    fn transfer_farm_position_endpoint(
+        &self,
+        new_owner: ManagedAddress,
+    ) -> DoubleMultiPayment<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let transfer_result = self.transfer_farm_position(&caller, new_owner.clone());
+        self.send_payment_non_zero(&new_owner, &transfer_result.new_farm_token);
+
+        let boosted_rewards = transfer_result.boosted_rewards;
+        let locked_rewards_payment = self.send_to_lock_contract_non_zero(
+            boosted_rewards.token_identifier,
+            boosted_rewards.amount,
+            caller.clone(),
+            caller,
+        );
+
+        (transfer_result.new_farm_token, locked_rewards_payment).into()
+    }
+
     #[endpoint(startProduceRewards)]
     fn start_produce_rewards_endpoint(&self) {
         self.require_caller_has_admin_permissions();
@@ -245,6 +296,20 @@ pub trait Farm:
         self.end_produce_rewards::<NoMintWrapper<Self>>();
     }
 
+    /// Rewards are minted virtually when claimed, so the reward tokens converted
+    /// from a redistributed penalty are burned, which requires the local burn role.
+    #[endpoint(processPendingPenalty)]
+    fn process_pending_penalty_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+
+        let redistributed_rewards = self.process_pending_penalty::<NoMintWrapper<Self>>();
+        if redistributed_rewards > 0 {
+            let reward_token_id = self.reward_token_id().get();
+            self.send()
+                .esdt_local_burn(&reward_token_id, 0, &redistributed_rewards);
+        }
+    }
+
     #[endpoint(setPerBlockRewardAmount)]
     fn set_per_block_rewards_endpoint(&self, per_block_amount: BigUint) {
         self.require_caller_has_admin_permissions();
@@ -288,8 +353,23 @@ where
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
-        let total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let mut total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let funded_mode = sc.funded_rewards_mode().get();
+        if funded_mode {
+            let remaining_rewards = sc.get_remaining_funded_rewards();
+            total_reward = core::cmp::min(total_reward, remaining_rewards);
+        }
+
         if total_reward > 0u64 {
+            // rewards are minted virtually when claimed,
+            // so the deposited tokens are burned as they are allocated
+            if funded_mode {
+                sc.accumulated_rewards()
+                    .update(|accumulated| *accumulated += &total_reward);
+                sc.send()
+                    .esdt_local_burn(&storage_cache.reward_token_id, 0, &total_reward);
+            }
+
             storage_cache.reward_reserve += &total_reward;
             let split_rewards = sc.take_reward_slice(total_reward);
 
@@ -329,7 +409,7 @@ where
         sc: &Self::FarmSc,
         total_exit_amount: &mut BigUint<<Self::FarmSc as ContractBase>::Api>,
         token_attributes: &Self::AttributesType,
-        storage_cache: &StorageCache<Self::FarmSc>,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
         Wrapper::<T>::apply_penalty(sc, total_exit_amount, token_attributes, storage_cache)
     }
@@ -1,9 +1,13 @@
 use common_structs::FarmTokenAttributes;
+use multiversx_sc::types::EsdtLocalRole;
 use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint, DebugApi};
 use simple_lock::locked_token::LockedTokenAttributes;
 
+use farm::funded_rewards::FundedRewardsModule;
+use farm_with_locked_rewards::Farm;
+
 use crate::farm_with_locked_rewards_setup::{
-    FarmSetup, BOOSTED_YIELDS_PERCENTAGE, FARM_TOKEN_ID, LOCKED_REWARD_TOKEN_ID,
+    FarmSetup, BOOSTED_YIELDS_PERCENTAGE, FARM_TOKEN_ID, LOCKED_REWARD_TOKEN_ID, REWARD_TOKEN_ID,
 };
 
 mod farm_with_locked_rewards_setup;
@@ -226,3 +230,72 @@ fn farm_with_boosted_yields_no_proxy_test() {
             None,
         );
 }
+
+#[test]
+fn farm_with_locked_rewards_funded_mode_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = FarmSetup::new(
+        farm_with_locked_rewards::contract_obj,
+        energy_factory::contract_obj,
+    );
+
+    let top_up_amount = 3_000;
+    let owner = farm_setup.owner.clone();
+    farm_setup
+        .b_mock
+        .set_esdt_balance(&owner, REWARD_TOKEN_ID, &rust_biguint!(top_up_amount));
+    farm_setup.b_mock.set_esdt_local_roles(
+        farm_setup.farm_wrapper.address_ref(),
+        REWARD_TOKEN_ID,
+        &[EsdtLocalRole::Burn],
+    );
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.end_produce_rewards_endpoint();
+            sc.set_funded_rewards_mode(true);
+        })
+        .assert_ok();
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &owner,
+            &farm_setup.farm_wrapper,
+            REWARD_TOKEN_ID,
+            0,
+            &rust_biguint!(top_up_amount),
+            |sc| {
+                sc.top_up_rewards();
+                sc.start_produce_rewards_endpoint();
+            },
+        )
+        .assert_ok();
+
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    // 10 blocks would produce 10_000 rewards, but only the deposited amount is distributed
+    farm_setup.b_mock.set_block_nonce(10);
+
+    let received_reward_amt = farm_setup.claim_rewards(&first_user, 1, farm_token_amount);
+    assert_eq!(received_reward_amt, top_up_amount);
+
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.accumulated_rewards().get(),
+                managed_biguint!(top_up_amount)
+            );
+            assert_eq!(sc.get_remaining_funded_rewards(), managed_biguint!(0));
+        })
+        .assert_ok();
+
+    // the deposited tokens were burned, as the locked rewards are minted virtually
+    farm_setup.b_mock.check_esdt_balance(
+        farm_setup.farm_wrapper.address_ref(),
+        REWARD_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+}
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           62
+// Endpoints:                           84
 // Async Callback:                       1
-// Total number of exported functions:  64
+// Total number of exported functions:  86
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -22,9 +22,13 @@ multiversx_sc_wasm_adapter::endpoints! {
         claimRewards
         exitFarm
         calculateRewardsForGivenPosition
+        getExitPenalty
         mergeFarmTokens
+        splitFarmToken
+        transferFarmPosition
         startProduceRewards
         endProduceRewards
+        processPendingPenalty
         setPerBlockRewardAmount
         getRewardPerShare
         getRewardReserve
@@ -55,10 +59,27 @@ multiversx_sc_wasm_adapter::endpoints! {
         set_penalty_percent
         set_minimum_farming_epochs
         set_burn_gas_limit
+        setPenaltySchedule
+        setPenaltySteps
+        setPenaltyDestination
+        setFeesCollectorAddress
+        setPenaltyMaxSlippage
+        getPenaltyMaxSlippage
         getPenaltyPercent
         getMinimumFarmingEpoch
         getBurnGasLimit
         getPairContractManagedAddress
+        getPenaltySchedule
+        getPenaltySteps
+        getPenaltyDestination
+        getFeesCollectorAddress
+        getPendingPenalty
+        setFundedRewardsMode
+        topUpRewards
+        withdrawRewards
+        isFundedRewardsMode
+        getAccumulatedRewards
+        getRewardCapacity
         setBoostedYieldsRewardsPercentage
         collectUndistributedBoostedRewards
         getBoostedYieldsRewardsPercentage
@@ -77,6 +98,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTotalEnergyForWeek
         getTotalLockedTokensForWeek
         updateEnergyForUser
+        updateEnergyAfterDelegation
         getCurrentClaimProgress
         setEnergyFactoryAddress
         getEnergyFactoryAddress
@@ -7,13 +7,13 @@ multiversx_sc::derive_imports!();
 use core::marker::PhantomData;
 
 use common_errors::ERROR_ZERO_AMOUNT;
-use common_structs::FarmTokenAttributes;
+use common_structs::{FarmTokenAttributes, PaymentsVec};
 use contexts::storage_cache::StorageCache;
 
 use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
 use fixed_supply_token::FixedSupplyToken;
 
-use crate::exit_penalty;
+use crate::exit_penalty::{self, PenaltyDestination};
 
 pub type DoubleMultiPayment<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
 pub type ClaimRewardsResultType<M> = DoubleMultiPayment<M>;
@@ -52,6 +52,7 @@ pub trait BaseFunctionsModule:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + exit_penalty::ExitPenaltyModule
+    + crate::funded_rewards::FundedRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -151,7 +152,7 @@ pub trait BaseFunctionsModule:
         caller: ManagedAddress,
         payment: EsdtTokenPayment,
     ) -> ExitFarmResultWrapper<Self::Api> {
-        let base_exit_farm_result = self.exit_farm_base::<FC>(caller.clone(), payment);
+        let mut base_exit_farm_result = self.exit_farm_base::<FC>(caller.clone(), payment);
 
         let mut farming_token_payment = base_exit_farm_result.farming_token_payment;
         let reward_payment = base_exit_farm_result.reward_payment;
@@ -164,7 +165,7 @@ pub trait BaseFunctionsModule:
             self,
             &mut farming_token_payment.amount,
             &base_exit_farm_result.context.farm_token.attributes,
-            &base_exit_farm_result.storage_cache,
+            &mut base_exit_farm_result.storage_cache,
         );
 
         self.emit_exit_farm_event(
@@ -192,6 +193,15 @@ pub trait BaseFunctionsModule:
         token_mapper.nft_create(new_token_amount, &output_attributes)
     }
 
+    fn split_farm_token<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> PaymentsVec<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        let token_mapper = self.farm_token();
+        self.split_and_create_tokens::<FC::AttributesType>(&payment, split_amounts, &token_mapper)
+    }
+
     fn end_produce_rewards<FC: FarmContract<FarmSc = Self>>(&self) {
         let mut storage = StorageCache::new(self);
         FC::generate_aggregated_rewards(self, &mut storage);
@@ -208,6 +218,83 @@ pub trait BaseFunctionsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    /// Sends the penalty kept from exits to the current penalty destination.
+    /// Done separately from exiting, so a failing destination never blocks users from leaving.
+    ///
+    /// Returns the amount of reward tokens added to the reward reserve, if redistributed.
+    fn process_pending_penalty<FC: FarmContract<FarmSc = Self>>(&self) -> BigUint {
+        let penalty_amount = self.pending_penalty().take();
+        require!(penalty_amount > 0, "No pending penalty");
+
+        let farming_token_id = self.farming_token_id().get();
+        match self.penalty_destination().get() {
+            PenaltyDestination::Burn => {
+                let reward_token_id = self.reward_token_id().get();
+                self.burn_farming_tokens(&penalty_amount, &farming_token_id, &reward_token_id);
+
+                BigUint::zero()
+            }
+            PenaltyDestination::FeesCollector => {
+                self.send_to_fees_collector(&farming_token_id, &penalty_amount);
+
+                BigUint::zero()
+            }
+            PenaltyDestination::Redistribute => {
+                let mut storage_cache = StorageCache::new(self);
+                FC::generate_aggregated_rewards(self, &mut storage_cache);
+
+                self.redistribute_penalty(&penalty_amount, &mut storage_cache)
+            }
+        }
+    }
+
+    /// Converts the penalty into reward tokens and adds them to the reward reserve,
+    /// increasing the reward per share for the remaining farmers.
+    /// If there are no farmers left, the penalty is burned instead.
+    fn redistribute_penalty(
+        &self,
+        penalty_amount: &BigUint,
+        storage_cache: &mut StorageCache<Self>,
+    ) -> BigUint {
+        if storage_cache.farm_token_supply == 0u64 {
+            self.burn_farming_tokens(
+                penalty_amount,
+                &storage_cache.farming_token_id,
+                &storage_cache.reward_token_id,
+            );
+
+            return BigUint::zero();
+        }
+
+        let reward_amount = self.convert_farming_tokens_to_reward_tokens(
+            penalty_amount,
+            &storage_cache.farming_token_id,
+            &storage_cache.reward_token_id,
+        );
+        if reward_amount > 0u64 {
+            storage_cache.reward_reserve += &reward_amount;
+
+            let increase = (&reward_amount * &storage_cache.division_safety_constant)
+                / &storage_cache.farm_token_supply;
+            storage_cache.reward_per_share += &increase;
+        }
+
+        self.emit_redistribute_penalty_event(
+            EsdtTokenPayment::new(
+                storage_cache.farming_token_id.clone(),
+                0,
+                penalty_amount.clone(),
+            ),
+            EsdtTokenPayment::new(
+                storage_cache.reward_token_id.clone(),
+                0,
+                reward_amount.clone(),
+            ),
+        );
+
+        reward_amount
+    }
+
     fn require_queried(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.blockchain().get_sc_address();
@@ -257,12 +344,33 @@ where
     type FarmSc = T;
     type AttributesType = FarmTokenAttributes<<Self::FarmSc as ContractBase>::Api>;
 
+    fn mint_rewards(
+        sc: &Self::FarmSc,
+        token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+        amount: &BigUint<<Self::FarmSc as ContractBase>::Api>,
+    ) {
+        if !sc.funded_rewards_mode().get() {
+            sc.send().esdt_local_mint(token_id, 0, amount);
+        }
+    }
+
     fn generate_aggregated_rewards(
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
-        let total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let mut total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let funded_mode = sc.funded_rewards_mode().get();
+        if funded_mode {
+            let remaining_rewards = sc.get_remaining_funded_rewards();
+            total_reward = core::cmp::min(total_reward, remaining_rewards);
+        }
+
         if total_reward > 0u64 {
+            if funded_mode {
+                sc.accumulated_rewards()
+                    .update(|accumulated| *accumulated += &total_reward);
+            }
+
             storage_cache.reward_reserve += &total_reward;
             let split_rewards = sc.take_reward_slice(total_reward);
 
@@ -305,11 +413,11 @@ where
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         let current_epoch = sc.blockchain().get_block_epoch();
         let user_farming_epochs = current_epoch - token_attributes.entering_epoch;
-        let min_farming_epochs = sc.minimum_farming_epochs().get();
-        if user_farming_epochs >= min_farming_epochs {
+        let penalty_percent = sc.get_penalty_percent_for_farming_epochs(user_farming_epochs);
+        if penalty_percent == 0 {
             BigUint::zero()
         } else {
-            total_exit_amount * sc.penalty_percent().get() / exit_penalty::MAX_PERCENT
+            total_exit_amount * penalty_percent / exit_penalty::MAX_PERCENT
         }
     }
 
@@ -317,17 +425,27 @@ where
         sc: &Self::FarmSc,
         total_exit_amount: &mut BigUint<<Self::FarmSc as ContractBase>::Api>,
         token_attributes: &Self::AttributesType,
-        storage_cache: &StorageCache<Self::FarmSc>,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
         let penalty_amount = Self::get_exit_penalty(sc, total_exit_amount, token_attributes);
-        if penalty_amount > 0 {
-            *total_exit_amount -= &penalty_amount;
+        if penalty_amount == 0 {
+            return;
+        }
 
-            sc.burn_farming_tokens(
-                &penalty_amount,
-                &storage_cache.farming_token_id,
-                &storage_cache.reward_token_id,
-            );
+        *total_exit_amount -= &penalty_amount;
+
+        match sc.penalty_destination().get() {
+            PenaltyDestination::Burn => {
+                sc.burn_farming_tokens(
+                    &penalty_amount,
+                    &storage_cache.farming_token_id,
+                    &storage_cache.reward_token_id,
+                );
+            }
+            PenaltyDestination::FeesCollector | PenaltyDestination::Redistribute => {
+                sc.pending_penalty()
+                    .update(|pending_penalty| *pending_penalty += &penalty_amount);
+            }
         }
     }
 }
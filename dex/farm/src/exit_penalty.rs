@@ -1,7 +1,12 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
 use common_errors::ERROR_PARAMETERS;
 use common_structs::Epoch;
+use multiversx_sc::storage::mappers::SingleValue;
+use pair::config::ProxyTrait as _;
+use pair::safe_price::ProxyTrait as _;
+use pair::RemoveLiquidityResultType;
 
 pub const MAX_PERCENT: u64 = 10_000;
 pub const DEFAULT_PENALTY_PERCENT: u64 = 100;
@@ -9,9 +14,44 @@ pub const DEFAULT_MINUMUM_FARMING_EPOCHS: u64 = 3;
 pub const DEFAULT_BURN_GAS_LIMIT: u64 = 50_000_000;
 pub const DEFAULT_NFT_DEPOSIT_MAX_LEN: usize = 10;
 pub const MAX_MINIMUM_FARMING_EPOCHS: u64 = 30;
+pub const MAX_PENALTY_STEPS: usize = 10;
+pub const DEFAULT_PENALTY_MAX_SLIPPAGE: u64 = 100;
+
+/// Flat: `penalty_percent` is applied for the whole `minimum_farming_epochs` period.
+///
+/// Linear: the penalty starts at `penalty_percent` and decreases linearly,
+/// reaching 0 once `minimum_farming_epochs` have passed.
+///
+/// Step: the penalty is given by the first step whose `max_farming_epochs`
+/// is bigger than the number of epochs the position has been farming.
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PenaltySchedule {
+    Flat,
+    Linear,
+    Step,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PenaltyDestination {
+    Burn,
+    FeesCollector,
+    Redistribute,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, Default)]
+pub struct PenaltyStep {
+    pub max_farming_epochs: Epoch,
+    pub penalty_percent: u64,
+}
+
+pub type AllPenaltySteps = ArrayVec<PenaltyStep, MAX_PENALTY_STEPS>;
 
 #[multiversx_sc::module]
-pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
+pub trait ExitPenaltyModule:
+    config::ConfigModule
+    + permissions_module::PermissionsModule
+    + crate::fees_collector::FeesCollectorModule
+{
     #[only_owner]
     #[endpoint]
     fn set_penalty_percent(&self, percent: u64) {
@@ -33,6 +73,157 @@ pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
         self.burn_gas_limit().set(gas_limit);
     }
 
+    #[only_owner]
+    #[endpoint(setPenaltySchedule)]
+    fn set_penalty_schedule(&self, schedule: PenaltySchedule) {
+        if schedule == PenaltySchedule::Step {
+            require!(
+                !self.penalty_steps().get().is_empty(),
+                "No penalty steps set"
+            );
+        }
+
+        self.penalty_schedule().set(schedule);
+    }
+
+    /// Pairs of (max_farming_epochs, penalty_percent), replacing the existing steps.
+    ///
+    /// Epochs must be strictly increasing and at most MAX_MINIMUM_FARMING_EPOCHS,
+    /// while percentages must be strictly decreasing.
+    ///
+    /// For example, steps "10, 300" and "20, 100" mean a position exiting
+    /// after less than 10 epochs pays 3%, one exiting after 10 to 19 epochs pays 1%,
+    /// and positions farming for at least 20 epochs pay no penalty.
+    #[only_owner]
+    #[endpoint(setPenaltySteps)]
+    fn set_penalty_steps(&self, steps: MultiValueEncoded<MultiValue2<Epoch, u64>>) {
+        require!(
+            !steps.is_empty() && steps.len() <= MAX_PENALTY_STEPS,
+            ERROR_PARAMETERS
+        );
+
+        let mut all_steps = AllPenaltySteps::new();
+        let mut opt_prev_step: Option<PenaltyStep> = None;
+        for pair in steps {
+            let (max_farming_epochs, penalty_percent) = pair.into_tuple();
+            require!(
+                max_farming_epochs > 0
+                    && max_farming_epochs <= MAX_MINIMUM_FARMING_EPOCHS
+                    && penalty_percent < MAX_PERCENT,
+                ERROR_PARAMETERS
+            );
+
+            if let Some(prev_step) = opt_prev_step {
+                require!(
+                    max_farming_epochs > prev_step.max_farming_epochs
+                        && penalty_percent < prev_step.penalty_percent,
+                    "Invalid penalty steps"
+                );
+            }
+
+            let step = PenaltyStep {
+                max_farming_epochs,
+                penalty_percent,
+            };
+            unsafe {
+                all_steps.push_unchecked(step);
+            }
+            opt_prev_step = Some(step);
+        }
+
+        self.penalty_steps().set(&all_steps);
+    }
+
+    #[only_owner]
+    #[endpoint(setPenaltyDestination)]
+    fn set_penalty_destination(&self, destination: PenaltyDestination) {
+        match destination {
+            PenaltyDestination::Burn => {}
+            PenaltyDestination::FeesCollector => {
+                self.require_fees_collector_accepts(&self.farming_token_id().get());
+            }
+            PenaltyDestination::Redistribute => {
+                require!(
+                    !self.pair_contract_address().get().is_zero(),
+                    "Pair contract address not set"
+                );
+                self.require_pair_contains_reward_token();
+            }
+        }
+
+        self.penalty_destination().set(destination);
+    }
+
+    /// The maximum deviation from the pair's safe price accepted when converting
+    /// the penalty into reward tokens. Applies to both the liquidity removal and the swap.
+    #[only_owner]
+    #[endpoint(setPenaltyMaxSlippage)]
+    fn set_penalty_max_slippage(&self, max_slippage: u64) {
+        require!(max_slippage < MAX_PERCENT, ERROR_PARAMETERS);
+        self.penalty_max_slippage().set(max_slippage);
+    }
+
+    #[view(getPenaltyMaxSlippage)]
+    fn get_penalty_max_slippage(&self) -> u64 {
+        let mapper = self.penalty_max_slippage();
+        if mapper.is_empty() {
+            DEFAULT_PENALTY_MAX_SLIPPAGE
+        } else {
+            mapper.get()
+        }
+    }
+
+    fn require_pair_contains_reward_token(&self) {
+        let pair_contract_address = self.pair_contract_address().get();
+        let first_token_id: SingleValue<TokenIdentifier> = self
+            .pair_contract_proxy(pair_contract_address.clone())
+            .first_token_id()
+            .execute_on_dest_context();
+        let second_token_id: SingleValue<TokenIdentifier> = self
+            .pair_contract_proxy(pair_contract_address)
+            .second_token_id()
+            .execute_on_dest_context();
+
+        let reward_token_id = self.reward_token_id().get();
+        require!(
+            first_token_id.into() == reward_token_id || second_token_id.into() == reward_token_id,
+            "Pair does not contain the reward token"
+        );
+    }
+
+    fn get_penalty_percent_for_farming_epochs(&self, farming_epochs: Epoch) -> u64 {
+        match self.penalty_schedule().get() {
+            PenaltySchedule::Flat => {
+                let min_farming_epochs = self.minimum_farming_epochs().get();
+                if farming_epochs >= min_farming_epochs {
+                    0
+                } else {
+                    self.penalty_percent().get()
+                }
+            }
+            PenaltySchedule::Linear => {
+                let min_farming_epochs = self.minimum_farming_epochs().get();
+                if farming_epochs >= min_farming_epochs {
+                    return 0;
+                }
+
+                let penalty_percent = self.penalty_percent().get();
+                let remaining_epochs = min_farming_epochs - farming_epochs;
+                penalty_percent * remaining_epochs / min_farming_epochs
+            }
+            PenaltySchedule::Step => {
+                let steps = self.penalty_steps().get();
+                for step in steps.iter() {
+                    if farming_epochs < step.max_farming_epochs {
+                        return step.penalty_percent;
+                    }
+                }
+
+                0
+            }
+        }
+    }
+
     fn burn_farming_tokens(
         &self,
         farming_amount: &BigUint,
@@ -53,6 +244,86 @@ pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
         }
     }
 
+    /// Removes the penalized liquidity through the linked pair,
+    /// and swaps the non-reward token side into reward tokens.
+    /// The minimum amounts are derived from the pair's safe price.
+    fn convert_farming_tokens_to_reward_tokens(
+        &self,
+        farming_amount: &BigUint,
+        farming_token_id: &TokenIdentifier,
+        reward_token_id: &TokenIdentifier,
+    ) -> BigUint {
+        let pair_contract_address = self.pair_contract_address().get();
+        let max_slippage = self.get_penalty_max_slippage();
+
+        let safe_position: MultiValue2<EsdtTokenPayment, EsdtTokenPayment> = self
+            .pair_contract_proxy(pair_contract_address.clone())
+            .update_and_get_tokens_for_given_position_with_safe_price(farming_amount.clone())
+            .execute_on_dest_context();
+        let (first_safe_payment, second_safe_payment) = safe_position.into_tuple();
+
+        let remove_liq_result: RemoveLiquidityResultType<Self::Api> = self
+            .pair_contract_proxy(pair_contract_address.clone())
+            .remove_liquidity(
+                min_amount_with_slippage(&first_safe_payment.amount, max_slippage),
+                min_amount_with_slippage(&second_safe_payment.amount, max_slippage),
+            )
+            .with_esdt_transfer((farming_token_id.clone(), 0, farming_amount.clone()))
+            .execute_on_dest_context();
+        let (first_payment, second_payment) = remove_liq_result.into_tuple();
+
+        let mut reward_amount = BigUint::zero();
+        for payment in [first_payment, second_payment] {
+            if &payment.token_identifier == reward_token_id {
+                reward_amount += payment.amount;
+                continue;
+            }
+            if payment.amount == 0 {
+                continue;
+            }
+
+            let safe_amount_out: EsdtTokenPayment = self
+                .pair_contract_proxy(pair_contract_address.clone())
+                .update_and_get_safe_price(payment.clone())
+                .execute_on_dest_context();
+            let swap_result: EsdtTokenPayment = self
+                .pair_contract_proxy(pair_contract_address.clone())
+                .swap_tokens_fixed_input(
+                    reward_token_id.clone(),
+                    min_amount_with_slippage(&safe_amount_out.amount, max_slippage),
+                )
+                .with_esdt_transfer(payment)
+                .execute_on_dest_context();
+            reward_amount += swap_result.amount;
+        }
+
+        reward_amount
+    }
+
+    fn emit_redistribute_penalty_event(
+        &self,
+        penalty_farming_tokens: EsdtTokenPayment,
+        redistributed_rewards: EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let block = self.blockchain().get_block_nonce();
+        self.redistribute_penalty_event(
+            epoch,
+            block,
+            &penalty_farming_tokens,
+            &redistributed_rewards,
+        );
+    }
+
+    #[event("redistribute_penalty")]
+    fn redistribute_penalty_event(
+        &self,
+        #[indexed] epoch: u64,
+        #[indexed] block: u64,
+        #[indexed] penalty_farming_tokens: &EsdtTokenPayment,
+        redistributed_rewards: &EsdtTokenPayment,
+    );
+
     #[proxy]
     fn pair_contract_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
 
@@ -71,4 +342,38 @@ pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
     #[view(getPairContractManagedAddress)]
     #[storage_mapper("pair_contract_address")]
     fn pair_contract_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPenaltySchedule)]
+    #[storage_mapper("penalty_schedule")]
+    fn penalty_schedule(&self) -> SingleValueMapper<PenaltySchedule>;
+
+    #[view(getPenaltySteps)]
+    #[storage_mapper("penalty_steps")]
+    fn penalty_steps(&self) -> SingleValueMapper<AllPenaltySteps>;
+
+    #[view(getPenaltyDestination)]
+    #[storage_mapper("penalty_destination")]
+    fn penalty_destination(&self) -> SingleValueMapper<PenaltyDestination>;
+
+    #[storage_mapper("penalty_max_slippage")]
+    fn penalty_max_slippage(&self) -> SingleValueMapper<u64>;
+
+    /// Farming tokens kept as penalty on exit, waiting to be sent to their destination
+    /// through the `processPendingPenalty` endpoint
+    #[view(getPendingPenalty)]
+    #[storage_mapper("pending_penalty")]
+    fn pending_penalty(&self) -> SingleValueMapper<BigUint>;
+}
+
+/// The pair requires non-zero minimum amounts
+fn min_amount_with_slippage<M: ManagedTypeApi>(
+    expected_amount: &BigUint<M>,
+    max_slippage: u64,
+) -> BigUint<M> {
+    let min_amount = expected_amount * (MAX_PERCENT - max_slippage) / MAX_PERCENT;
+    if min_amount == 0 {
+        BigUint::from(1u32)
+    } else {
+        min_amount
+    }
 }
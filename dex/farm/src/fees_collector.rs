@@ -0,0 +1,80 @@
+multiversx_sc::imports!();
+
+pub mod fees_collector_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait FeesCollectorProxy {
+        #[payable("*")]
+        #[endpoint(depositSwapFees)]
+        fn deposit_swap_fees(&self);
+
+        #[view(getAllTokens)]
+        fn get_all_tokens(&self) -> MultiValueEncoded<TokenIdentifier>;
+
+        #[view(getAllKnownContracts)]
+        fn get_all_known_contracts(&self) -> MultiValueEncoded<ManagedAddress>;
+    }
+}
+
+/// Penalties sent to the fees collector, shared by the farms and the staking farm
+#[multiversx_sc::module]
+pub trait FeesCollectorModule {
+    #[only_owner]
+    #[endpoint(setFeesCollectorAddress)]
+    fn set_fees_collector_address(&self, fees_collector_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&fees_collector_address),
+            "Invalid fees collector address"
+        );
+
+        self.fees_collector_address().set(&fees_collector_address);
+    }
+
+    /// The fees collector only accepts deposits of known tokens from known contracts,
+    /// so this contract and the penalty token must both be registered there.
+    fn require_fees_collector_accepts(&self, token_id: &TokenIdentifier) {
+        require!(
+            !self.fees_collector_address().is_empty(),
+            "Fees collector address not set"
+        );
+
+        let fees_collector_address = self.fees_collector_address().get();
+        let own_sc_address = self.blockchain().get_sc_address();
+        let known_contracts: MultiValueEncoded<ManagedAddress> = self
+            .fees_collector_proxy(fees_collector_address.clone())
+            .get_all_known_contracts()
+            .execute_on_dest_context();
+        require!(
+            known_contracts
+                .into_iter()
+                .any(|address| address == own_sc_address),
+            "Farm is not a known contract in fees collector"
+        );
+
+        let known_tokens: MultiValueEncoded<TokenIdentifier> = self
+            .fees_collector_proxy(fees_collector_address)
+            .get_all_tokens()
+            .execute_on_dest_context();
+        require!(
+            known_tokens.into_iter().any(|known| &known == token_id),
+            "Farming token is not a known token in fees collector"
+        );
+    }
+
+    fn send_to_fees_collector(&self, token_id: &TokenIdentifier, amount: &BigUint) {
+        let fees_collector_address = self.fees_collector_address().get();
+        let _: IgnoreValue = self
+            .fees_collector_proxy(fees_collector_address)
+            .deposit_swap_fees()
+            .with_esdt_transfer((token_id.clone(), 0, amount.clone()))
+            .execute_on_dest_context();
+    }
+
+    #[proxy]
+    fn fees_collector_proxy(&self, to: ManagedAddress) -> fees_collector_proxy::Proxy<Self::Api>;
+
+    #[view(getFeesCollectorAddress)]
+    #[storage_mapper("fees_collector_address")]
+    fn fees_collector_address(&self) -> SingleValueMapper<ManagedAddress>;
+}
@@ -0,0 +1,99 @@
+multiversx_sc::imports!();
+
+use common_errors::ERROR_ZERO_AMOUNT;
+
+pub static ERROR_NOT_FUNDED_MODE: &[u8] = b"Farm is not in funded rewards mode";
+pub static ERROR_REWARDS_STILL_PRODUCED: &[u8] = b"Rewards production must be stopped first";
+pub static ERROR_FUNDED_REWARDS_REMAINING: &[u8] =
+    b"The remaining funded rewards must be withdrawn first";
+
+/// In funded mode, rewards are no longer minted.
+/// Instead, they are drawn from the reward tokens deposited through `topUpRewards`,
+/// and emission stops once all the deposited tokens were allocated to farmers.
+#[multiversx_sc::module]
+pub trait FundedRewardsModule:
+    rewards::RewardsModule
+    + config::ConfigModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+{
+    /// Leaving funded mode requires the unallocated deposits to be withdrawn first,
+    /// as they could not be withdrawn afterwards.
+    #[only_owner]
+    #[endpoint(setFundedRewardsMode)]
+    fn set_funded_rewards_mode(&self, funded: bool) {
+        require!(
+            !self.produce_rewards_enabled().get(),
+            ERROR_REWARDS_STILL_PRODUCED
+        );
+        if !funded {
+            require!(
+                self.get_remaining_funded_rewards() == 0,
+                ERROR_FUNDED_REWARDS_REMAINING
+            );
+        }
+
+        self.funded_rewards_mode().set(funded);
+    }
+
+    #[payable("*")]
+    #[endpoint(topUpRewards)]
+    fn top_up_rewards(&self) {
+        self.require_caller_has_admin_permissions();
+        self.require_funded_rewards_mode();
+
+        let (payment_token, payment_amount) = self.call_value().single_fungible_esdt();
+        let reward_token_id = self.reward_token_id().get();
+        require!(payment_token == reward_token_id, "Invalid token");
+
+        self.reward_capacity().update(|r| *r += payment_amount);
+    }
+
+    /// Sends back the deposited rewards that were not yet allocated to farmers.
+    /// Rewards production must be stopped through `endProduceRewards` beforehand.
+    #[only_owner]
+    #[endpoint(withdrawRewards)]
+    fn withdraw_rewards(&self) -> EsdtTokenPayment {
+        self.require_funded_rewards_mode();
+        require!(
+            !self.produce_rewards_enabled().get(),
+            ERROR_REWARDS_STILL_PRODUCED
+        );
+
+        let remaining_rewards = self.get_remaining_funded_rewards();
+        require!(remaining_rewards > 0, ERROR_ZERO_AMOUNT);
+
+        let accumulated_rewards = self.accumulated_rewards().get();
+        self.reward_capacity().set(&accumulated_rewards);
+
+        let caller = self.blockchain().get_caller();
+        let reward_token_id = self.reward_token_id().get();
+        self.send()
+            .direct_esdt(&caller, &reward_token_id, 0, &remaining_rewards);
+
+        EsdtTokenPayment::new(reward_token_id, 0, remaining_rewards)
+    }
+
+    fn get_remaining_funded_rewards(&self) -> BigUint {
+        let reward_capacity = self.reward_capacity().get();
+        let accumulated_rewards = self.accumulated_rewards().get();
+
+        reward_capacity - accumulated_rewards
+    }
+
+    fn require_funded_rewards_mode(&self) {
+        require!(self.funded_rewards_mode().get(), ERROR_NOT_FUNDED_MODE);
+    }
+
+    #[view(isFundedRewardsMode)]
+    #[storage_mapper("fundedRewardsMode")]
+    fn funded_rewards_mode(&self) -> SingleValueMapper<bool>;
+
+    #[view(getAccumulatedRewards)]
+    #[storage_mapper("accumulatedRewards")]
+    fn accumulated_rewards(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getRewardCapacity)]
+    #[storage_mapper("reward_capacity")]
+    fn reward_capacity(&self) -> SingleValueMapper<BigUint>;
+}
@@ -8,7 +8,10 @@ multiversx_sc::derive_imports!();
 pub mod base_functions;
 pub mod claim_boost_only;
 pub mod exit_penalty;
+pub mod fees_collector;
+pub mod funded_rewards;
 pub mod progress_update;
+pub mod transfer_position;
 
 use base_functions::{ClaimRewardsResultType, DoubleMultiPayment, Wrapper};
 use common_structs::FarmTokenAttributes;
@@ -37,8 +40,11 @@ pub trait Farm:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
     + exit_penalty::ExitPenaltyModule
+    + fees_collector::FeesCollectorModule
+    + funded_rewards::FundedRewardsModule
     + progress_update::ProgressUpdateModule
     + claim_boost_only::ClaimBoostOnlyModule
+    + transfer_position::TransferPositionModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -205,6 +211,20 @@ pub trait Farm:
         )
     }
 
+    /// The amount of farming tokens that would be kept as penalty when exiting
+    /// `farm_token_amount` of a position with the given attributes at the current epoch.
+    ///
+    /// The penalty only depends on the position, not on its holder, so the view takes
+    /// the exited amount instead of the user, which also covers partial exits.
+    #[view(getExitPenalty)]
+    fn get_exit_penalty_view(
+        &self,
+        farm_token_amount: BigUint,
+        attributes: FarmTokenAttributes<Self::Api>,
+    ) -> BigUint {
+        Wrapper::<Self>::get_exit_penalty(self, &farm_token_amount, &attributes)
+    }
+
     #[payable("*")]
     #[endpoint(mergeFarmTokens)]
     fn merge_farm_tokens_endpoint(
@@ -221,6 +241,37 @@ pub trait Farm:
         merged_farm_token
     }
 
+    #[payable("*")]
+    #[endpoint(splitFarmToken)]
+    fn split_farm_token_endpoint(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let new_farm_tokens = self.split_farm_token::<Wrapper<Self>>(split_amounts);
+        self.send().direct_multi(&caller, &new_farm_tokens);
+
+        new_farm_tokens.into()
+    }
+
+    #[payable("*")]
+    #[endpoint(transferFarmPosition)]
+    fn transfer_farm_position_endpoint(
+        &self,
+        new_owner: ManagedAddress,
+    ) -> DoubleMultiPayment<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let transfer_result = self.transfer_farm_position(&caller, new_owner.clone());
+        self.send_payment_non_zero(&new_owner, &transfer_result.new_farm_token);
+        self.send_payment_non_zero(&caller, &transfer_result.boosted_rewards);
+
+        (
+            transfer_result.new_farm_token,
+            transfer_result.boosted_rewards,
+        )
+            .into()
+    }
+
     #[endpoint(startProduceRewards)]
     fn start_produce_rewards_endpoint(&self) {
         self.require_caller_has_admin_permissions();
@@ -233,6 +284,12 @@ pub trait Farm:
         self.end_produce_rewards::<Wrapper<Self>>();
     }
 
+    #[endpoint(processPendingPenalty)]
+    fn process_pending_penalty_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+        let _ = self.process_pending_penalty::<Wrapper<Self>>();
+    }
+
     #[endpoint(setPerBlockRewardAmount)]
     fn set_per_block_rewards_endpoint(&self, per_block_amount: BigUint) {
         self.require_caller_has_admin_permissions();
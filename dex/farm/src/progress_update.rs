@@ -12,16 +12,22 @@ pub trait ProgressUpdateModule:
     + utils::UtilsModule
 {
     fn check_claim_progress_for_merge(&self, caller: &ManagedAddress) {
-        let claim_progress_mapper = self.current_claim_progress(caller);
+        require!(
+            self.is_claim_progress_up_to_date(caller),
+            "The user claim progress must be up to date."
+        )
+    }
+
+    /// Users without any claim progress have nothing to lose, so they count as up to date
+    fn is_claim_progress_up_to_date(&self, user: &ManagedAddress) -> bool {
+        let claim_progress_mapper = self.current_claim_progress(user);
         if claim_progress_mapper.is_empty() {
-            return;
+            return true;
         }
 
         let current_week = self.get_current_week();
         let claim_progress = claim_progress_mapper.get();
-        require!(
-            claim_progress.week == current_week,
-            "The user claim progress must be up to date."
-        )
+
+        claim_progress.week == current_week
     }
 }
@@ -0,0 +1,106 @@
+multiversx_sc::imports!();
+
+use common_structs::FarmTokenAttributes;
+
+pub struct TransferPositionResultWrapper<M: ManagedTypeApi> {
+    pub new_farm_token: EsdtTokenPayment<M>,
+    pub boosted_rewards: EsdtTokenPayment<M>,
+}
+
+#[multiversx_sc::module]
+pub trait TransferPositionModule:
+    config::ConfigModule
+    + rewards::RewardsModule
+    + farm_token::FarmTokenModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + week_timekeeping::WeekTimekeepingModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + weekly_rewards_splitting::WeeklyRewardsSplittingModule
+    + weekly_rewards_splitting::events::WeeklyRewardsSplittingEventsModule
+    + weekly_rewards_splitting::global_info::WeeklyRewardsGlobalInfo
+    + weekly_rewards_splitting::locked_token_buckets::WeeklyRewardsLockedTokenBucketsModule
+    + weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule
+    + energy_query::EnergyQueryModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + crate::exit_penalty::ExitPenaltyModule
+    + farm_base_impl::base_farm_init::BaseFarmInitModule
+    + farm_base_impl::base_farm_validation::BaseFarmValidationModule
+    + farm_base_impl::enter_farm::BaseEnterFarmModule
+    + farm_base_impl::claim_rewards::BaseClaimRewardsModule
+    + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
+    + farm_base_impl::exit_farm::BaseExitFarmModule
+    + utils::UtilsModule
+    + farm_boosted_yields::FarmBoostedYieldsModule
+    + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
+    + crate::base_functions::BaseFunctionsModule
+    + crate::claim_boost_only::ClaimBoostOnlyModule
+    + crate::progress_update::ProgressUpdateModule
+{
+    /// Claims the pending boosted rewards of the current owner,
+    /// and re-creates the position with the new owner as `original_owner`.
+    /// Base rewards are not claimed, and stay in the position.
+    fn transfer_farm_position(
+        &self,
+        caller: &ManagedAddress,
+        new_owner: ManagedAddress,
+    ) -> TransferPositionResultWrapper<Self::Api> {
+        require!(
+            !new_owner.is_zero() && &new_owner != caller,
+            "Invalid new owner"
+        );
+
+        let payment = self.call_value().single_esdt();
+        let farm_token_mapper = self.farm_token();
+        farm_token_mapper.require_same_token(&payment.token_identifier);
+
+        let mut attributes: FarmTokenAttributes<Self::Api> =
+            self.get_attributes_as_part_of_fixed_supply(&payment, &farm_token_mapper);
+        require!(
+            &attributes.original_owner == caller,
+            "Only the original owner may transfer the position"
+        );
+
+        // the old owner's energy is not cleared, as they may still hold other positions.
+        // Their claim progress is brought up to date by the boosted rewards claim.
+        let boosted_rewards = self.claim_only_boosted_payment(caller, &payment);
+
+        // an outdated claim progress of the new owner is kept as it is,
+        // so the boosted rewards they have not claimed yet are not lost
+        if self.is_claim_progress_up_to_date(&new_owner) {
+            self.update_energy_and_progress(&new_owner);
+        }
+
+        farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        attributes.original_owner = new_owner.clone();
+        let new_farm_token = farm_token_mapper.nft_create(payment.amount, &attributes);
+
+        self.emit_transfer_farm_position_event(caller, &new_owner, &new_farm_token);
+
+        TransferPositionResultWrapper {
+            new_farm_token,
+            boosted_rewards,
+        }
+    }
+
+    fn emit_transfer_farm_position_event(
+        &self,
+        old_owner: &ManagedAddress,
+        new_owner: &ManagedAddress,
+        new_farm_token: &EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.transfer_farm_position_event(old_owner, new_owner, epoch, new_farm_token);
+    }
+
+    #[event("transfer_farm_position")]
+    fn transfer_farm_position_event(
+        &self,
+        #[indexed] old_owner: &ManagedAddress,
+        #[indexed] new_owner: &ManagedAddress,
+        #[indexed] epoch: u64,
+        new_farm_token: &EsdtTokenPayment,
+    );
+}
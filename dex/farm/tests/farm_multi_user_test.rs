@@ -2,6 +2,7 @@ use common_structs::FarmTokenAttributes;
 use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint, DebugApi};
 
 pub mod farm_setup;
+use farm::Farm;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::boosted_yields_factors::{BoostedYieldsConfig, BoostedYieldsFactors};
 use farm_setup::multi_user_farm_setup::*;
@@ -966,3 +967,100 @@ fn farm_claim_with_minimum_tokens() {
     farm_setup.check_remaining_boosted_rewards_to_distribute(2, 0);
     farm_setup.check_remaining_boosted_rewards_to_distribute(3, 0);
 }
+
+#[test]
+fn farm_transfer_position_keeps_sender_energy_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    farm_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+    farm_setup.set_boosted_yields_factors();
+    farm_setup.b_mock.set_block_epoch(2);
+
+    // first user enters twice, keeping two boosted positions
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.set_user_energy(&first_user, 1_000, 2, 1);
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    // second user enters, then stays inactive, so their claim progress becomes outdated
+    farm_setup.set_user_energy(&second_user, 4_000, 2, 1);
+    farm_setup.enter_farm(&second_user, farm_token_amount);
+
+    // advance blocks - 10 blocks - 10 * 1_000 = 10_000 total rewards
+    // 7_500 base farm, 2_500 boosted yields
+    farm_setup.b_mock.set_block_nonce(10);
+
+    // random tx on end of week 1, to cummulate rewards
+    let third_user = farm_setup.third_user.clone();
+    farm_setup.b_mock.set_block_epoch(6);
+    farm_setup.set_user_energy(&first_user, 1_000, 6, 1);
+    farm_setup.set_user_energy(&second_user, 4_000, 6, 1);
+    farm_setup.set_user_energy(&third_user, 1, 6, 1);
+    farm_setup.enter_farm(&third_user, 1);
+    farm_setup.exit_farm(&third_user, 4, 1, 1);
+
+    // advance 1 week
+    farm_setup.b_mock.set_block_epoch(10);
+    farm_setup.set_user_energy(&first_user, 1_000, 10, 1);
+    farm_setup.set_user_energy(&second_user, 4_000, 10, 1);
+
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_token_amount),
+            |sc| {
+                let (new_farm_token, _) = sc
+                    .transfer_farm_position_endpoint(managed_address!(&second_user))
+                    .into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 5);
+            },
+        )
+        .assert_ok();
+    farm_setup.last_farm_token_nonce += 1;
+
+    // the first user still has energy registered for their remaining position
+    farm_setup.check_farm_claim_progress_energy(1_000);
+    farm_setup
+        .b_mock
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            &first_user,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_token_amount),
+            None,
+        );
+    farm_setup
+        .b_mock
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            &second_user,
+            FARM_TOKEN_ID,
+            5,
+            &rust_biguint!(farm_token_amount),
+            None,
+        );
+
+    // the second user's outdated claim progress was kept,
+    // so they still receive their boosted rewards for week 1
+    let total_farm_tokens = 3 * farm_token_amount;
+    let second_base_farm_amt = farm_token_amount * 7_500 / total_farm_tokens;
+
+    // (2500 * 3 * 4_000 / 5_000 + 2500 * 2 * 100_000_000 / 300_000_000) / (3 + 2)
+    // (6000 + 1666) / (5) = 1533
+    let second_boosted_amt = 1533;
+    let second_received_reward_amt = farm_setup.claim_rewards(&second_user, 3, farm_token_amount);
+    assert_eq!(
+        second_received_reward_amt,
+        second_base_farm_amt + second_boosted_amt
+    );
+}
@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use multiversx_sc::{
+    contract_base::{CallableContract, ContractBase},
+    io::finish_multi,
+    storage::{
+        mappers::{SingleValueMapper, StorageMapper},
+        StorageKey,
+    },
+    types::{ManagedAddress, ManagedVec, MultiValueEncoded, TokenIdentifier},
+};
+use multiversx_sc_scenario::DebugApi;
+
+static DEPOSIT_FN_NAME: &str = "depositSwapFees";
+static GET_ALL_TOKENS_FN_NAME: &str = "getAllTokens";
+static GET_ALL_KNOWN_CONTRACTS_FN_NAME: &str = "getAllKnownContracts";
+
+static KNOWN_CONTRACTS_STORAGE_KEY: &[u8] = b"knownContracts";
+static KNOWN_TOKENS_STORAGE_KEY: &[u8] = b"allTokens";
+
+#[derive(Clone)]
+pub struct FeesCollectorMock {}
+
+impl ContractBase for FeesCollectorMock {
+    type Api = DebugApi;
+}
+
+impl CallableContract for FeesCollectorMock {
+    fn call(&self, fn_name: &str) -> bool {
+        if fn_name == GET_ALL_TOKENS_FN_NAME {
+            self.get_all_tokens();
+            true
+        } else if fn_name == GET_ALL_KNOWN_CONTRACTS_FN_NAME {
+            self.get_all_known_contracts();
+            true
+        } else {
+            fn_name == DEPOSIT_FN_NAME
+        }
+    }
+}
+
+impl FeesCollectorMock {
+    pub fn new() -> Self {
+        FeesCollectorMock {}
+    }
+
+    pub fn add_known_contract(&self, address: ManagedAddress<DebugApi>) {
+        self.known_contracts()
+            .update(|contracts| contracts.push(address));
+    }
+
+    pub fn add_known_token(&self, token_id: TokenIdentifier<DebugApi>) {
+        self.known_tokens().update(|tokens| tokens.push(token_id));
+    }
+
+    fn get_all_tokens(&self) {
+        let tokens: MultiValueEncoded<DebugApi, TokenIdentifier<DebugApi>> =
+            self.known_tokens().get().into();
+        finish_multi::<DebugApi, _>(&tokens);
+    }
+
+    fn get_all_known_contracts(&self) {
+        let contracts: MultiValueEncoded<DebugApi, ManagedAddress<DebugApi>> =
+            self.known_contracts().get().into();
+        finish_multi::<DebugApi, _>(&contracts);
+    }
+
+    fn known_contracts(
+        &self,
+    ) -> SingleValueMapper<DebugApi, ManagedVec<DebugApi, ManagedAddress<DebugApi>>> {
+        SingleValueMapper::new(StorageKey::new(KNOWN_CONTRACTS_STORAGE_KEY))
+    }
+
+    fn known_tokens(
+        &self,
+    ) -> SingleValueMapper<DebugApi, ManagedVec<DebugApi, TokenIdentifier<DebugApi>>> {
+        SingleValueMapper::new(StorageKey::new(KNOWN_TOKENS_STORAGE_KEY))
+    }
+}
@@ -1,3 +1,5 @@
 pub mod farm_rewards_distr_setup;
+pub mod fees_collector_mock;
 pub mod multi_user_farm_setup;
+pub mod pair_mock;
 pub mod single_user_farm_setup;
@@ -0,0 +1,167 @@
+#![allow(dead_code)]
+
+use multiversx_sc::{
+    contract_base::{CallableContract, ContractBase, ErrorHelper},
+    io::{finish_multi, load_endpoint_args},
+    storage::{
+        mappers::{SingleValueMapper, StorageMapper},
+        StorageKey,
+    },
+    types::{BigUint, EsdtTokenPayment, MultiValue2, TokenIdentifier},
+};
+use multiversx_sc_scenario::DebugApi;
+
+use super::single_user_farm_setup::{MEX_TOKEN_ID, WEGLD_TOKEN_ID};
+
+static GET_FIRST_TOKEN_ID_FN_NAME: &str = "getFirstTokenId";
+static GET_SECOND_TOKEN_ID_FN_NAME: &str = "getSecondTokenId";
+static SAFE_POSITION_FN_NAME: &str = "updateAndGetTokensForGivenPositionWithSafePrice";
+static SAFE_PRICE_FN_NAME: &str = "updateAndGetSafePrice";
+static REMOVE_LIQUIDITY_FN_NAME: &str = "removeLiquidity";
+static SWAP_FIXED_INPUT_FN_NAME: &str = "swapTokensFixedInput";
+
+static SWAP_RATE_PERCENT_STORAGE_KEY: &[u8] = b"swapRatePercent";
+const FULL_SWAP_RATE_PERCENT: u64 = 100;
+
+/// A WEGLD/MEX pair where one LP token is worth 1 WEGLD and 1 MEX, at a 1:1 safe price.
+/// The actual swap rate can be lowered to simulate a manipulated pool.
+#[derive(Clone)]
+pub struct PairMock {}
+
+impl ContractBase for PairMock {
+    type Api = DebugApi;
+}
+
+impl CallableContract for PairMock {
+    fn call(&self, fn_name: &str) -> bool {
+        if fn_name == GET_FIRST_TOKEN_ID_FN_NAME {
+            finish_multi::<DebugApi, _>(&TokenIdentifier::<DebugApi>::from_esdt_bytes(
+                WEGLD_TOKEN_ID,
+            ));
+        } else if fn_name == GET_SECOND_TOKEN_ID_FN_NAME {
+            finish_multi::<DebugApi, _>(&TokenIdentifier::<DebugApi>::from_esdt_bytes(
+                MEX_TOKEN_ID,
+            ));
+        } else if fn_name == SAFE_POSITION_FN_NAME {
+            self.get_safe_position();
+        } else if fn_name == SAFE_PRICE_FN_NAME {
+            self.get_safe_price();
+        } else if fn_name == REMOVE_LIQUIDITY_FN_NAME {
+            self.remove_liquidity();
+        } else if fn_name == SWAP_FIXED_INPUT_FN_NAME {
+            self.swap_fixed_input();
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl PairMock {
+    pub fn new() -> Self {
+        PairMock {}
+    }
+
+    pub fn set_swap_rate_percent(&self, swap_rate_percent: u64) {
+        self.swap_rate_percent().set(swap_rate_percent);
+    }
+
+    fn get_safe_position(&self) {
+        let (liquidity, ()) =
+            load_endpoint_args::<DebugApi, (BigUint<DebugApi>, ())>(("liquidity", ()));
+
+        finish_multi::<DebugApi, _>(&self.tokens_for_position(&liquidity));
+    }
+
+    fn get_safe_price(&self) {
+        let (input, ()) =
+            load_endpoint_args::<DebugApi, (EsdtTokenPayment<DebugApi>, ())>(("input", ()));
+
+        let output = EsdtTokenPayment::<DebugApi>::new(
+            self.other_token_id(&input.token_identifier),
+            0,
+            input.amount,
+        );
+        finish_multi::<DebugApi, _>(&output);
+    }
+
+    fn remove_liquidity(&self) {
+        let (first_token_amount_min, (second_token_amount_min, ())) =
+            load_endpoint_args::<DebugApi, (BigUint<DebugApi>, (BigUint<DebugApi>, ()))>((
+                "first_token_amount_min",
+                ("second_token_amount_min", ()),
+            ));
+
+        let payment = self.call_value().single_esdt();
+        let (first_payment, second_payment) =
+            self.tokens_for_position(&payment.amount).into_tuple();
+        if first_payment.amount < first_token_amount_min
+            || second_payment.amount < second_token_amount_min
+        {
+            ErrorHelper::<DebugApi>::signal_error_with_message("Slippage exceeded");
+        }
+
+        let caller = self.blockchain().get_caller();
+        for payment in [&first_payment, &second_payment] {
+            self.send()
+                .direct_esdt(&caller, &payment.token_identifier, 0, &payment.amount);
+        }
+
+        finish_multi::<DebugApi, _>(&MultiValue2::from((first_payment, second_payment)));
+    }
+
+    fn swap_fixed_input(&self) {
+        let (token_out, (amount_out_min, ())) = load_endpoint_args::<
+            DebugApi,
+            (TokenIdentifier<DebugApi>, (BigUint<DebugApi>, ())),
+        >(("token_out", ("amount_out_min", ())));
+
+        let payment = self.call_value().single_esdt();
+        let swap_rate_percent = if self.swap_rate_percent().is_empty() {
+            FULL_SWAP_RATE_PERCENT
+        } else {
+            self.swap_rate_percent().get()
+        };
+        let amount_out = payment.amount * swap_rate_percent / FULL_SWAP_RATE_PERCENT;
+        if amount_out < amount_out_min {
+            ErrorHelper::<DebugApi>::signal_error_with_message("Slippage exceeded");
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(&caller, &token_out, 0, &amount_out);
+
+        finish_multi::<DebugApi, _>(&EsdtTokenPayment::<DebugApi>::new(token_out, 0, amount_out));
+    }
+
+    fn tokens_for_position(
+        &self,
+        liquidity: &BigUint<DebugApi>,
+    ) -> MultiValue2<EsdtTokenPayment<DebugApi>, EsdtTokenPayment<DebugApi>> {
+        (
+            EsdtTokenPayment::new(
+                TokenIdentifier::from_esdt_bytes(WEGLD_TOKEN_ID),
+                0,
+                liquidity.clone(),
+            ),
+            EsdtTokenPayment::new(
+                TokenIdentifier::from_esdt_bytes(MEX_TOKEN_ID),
+                0,
+                liquidity.clone(),
+            ),
+        )
+            .into()
+    }
+
+    fn other_token_id(&self, token_id: &TokenIdentifier<DebugApi>) -> TokenIdentifier<DebugApi> {
+        if token_id == &TokenIdentifier::<DebugApi>::from_esdt_bytes(WEGLD_TOKEN_ID) {
+            TokenIdentifier::from_esdt_bytes(MEX_TOKEN_ID)
+        } else {
+            TokenIdentifier::from_esdt_bytes(WEGLD_TOKEN_ID)
+        }
+    }
+
+    fn swap_rate_percent(&self) -> SingleValueMapper<DebugApi, u64> {
+        SingleValueMapper::new(StorageKey::new(SWAP_RATE_PERCENT_STORAGE_KEY))
+    }
+}
@@ -1,8 +1,16 @@
 mod farm_setup;
 
+use common_structs::FarmTokenAttributes;
 use config::ConfigModule;
+use farm::exit_penalty::{ExitPenaltyModule, PenaltyDestination, PenaltySchedule};
+use farm::fees_collector::FeesCollectorModule;
+use farm::funded_rewards::FundedRewardsModule;
+use farm::Farm;
+use farm_setup::fees_collector_mock::FeesCollectorMock;
+use farm_setup::pair_mock::PairMock;
 use farm_setup::single_user_farm_setup::*;
-use multiversx_sc::types::EsdtLocalRole;
+use multiversx_sc::codec::{multi_types::OptionalValue, TopDecode};
+use multiversx_sc::types::{EsdtLocalRole, EsdtTokenPayment, MultiValueEncoded};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox::TxTokenTransfer,
     DebugApi,
@@ -77,6 +85,720 @@ fn test_exit_farm_with_penalty() {
     farm_setup.check_farm_token_supply(0);
 }
 
+#[test]
+fn test_exit_farm_with_linear_penalty() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_penalty_schedule(PenaltySchedule::Linear);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    // half of the minimum farming epochs passed, so only half the penalty is applied
+    farm_setup.set_block_epoch(1);
+    farm_setup.set_block_nonce(10);
+
+    let expected_penalty = farm_in_amount * PENALTY_PERCENT / 2 / MAX_PERCENT;
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = FarmTokenAttributes::<DebugApi> {
+                reward_per_share: managed_biguint!(0),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_in_amount),
+                original_owner: managed_address!(&farm_setup.user_address),
+            };
+            let penalty = sc.get_exit_penalty_view(managed_biguint!(farm_in_amount), attributes);
+            assert_eq!(penalty, managed_biguint!(expected_penalty));
+        })
+        .assert_ok();
+
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.exit_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        farm_in_amount - expected_penalty,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - expected_penalty),
+    );
+    farm_setup.check_farm_token_supply(0);
+}
+
+#[test]
+fn test_exit_farm_with_step_penalty() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_penalty_schedule(PenaltySchedule::Step);
+            },
+        )
+        .assert_user_error("No penalty steps set");
+
+    // percentages must be decreasing
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut steps = MultiValueEncoded::new();
+                steps.push((2u64, 100u64).into());
+                steps.push((5u64, 300u64).into());
+                sc.set_penalty_steps(steps);
+            },
+        )
+        .assert_user_error("Invalid penalty steps");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut steps = MultiValueEncoded::new();
+                steps.push((2u64, 300u64).into());
+                steps.push((5u64, 100u64).into());
+                sc.set_penalty_steps(steps);
+                sc.set_penalty_schedule(PenaltySchedule::Step);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    // 3 epochs farmed, so the second step applies
+    farm_setup.set_block_epoch(3);
+    farm_setup.set_block_nonce(10);
+
+    let expected_penalty = farm_in_amount * 100 / MAX_PERCENT;
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = FarmTokenAttributes::<DebugApi> {
+                reward_per_share: managed_biguint!(0),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_in_amount),
+                original_owner: managed_address!(&farm_setup.user_address),
+            };
+            let penalty = sc.get_exit_penalty_view(managed_biguint!(farm_in_amount), attributes);
+            assert_eq!(penalty, managed_biguint!(expected_penalty));
+        })
+        .assert_ok();
+
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.exit_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        farm_in_amount - expected_penalty,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - expected_penalty),
+    );
+    farm_setup.check_farm_token_supply(0);
+}
+
+#[test]
+fn test_exit_farm_with_penalty_sent_to_fees_collector() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+    let owner = farm_setup.owner_address.clone();
+    let farm_address = farm_setup.farm_wrapper.address_ref().clone();
+    let fees_collector = farm_setup.blockchain_wrapper.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner),
+        FeesCollectorMock::new,
+        "fees collector mock",
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_destination(PenaltyDestination::FeesCollector);
+        })
+        .assert_user_error("Fees collector address not set");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_fees_collector_address(managed_address!(fees_collector.address_ref()));
+        })
+        .assert_ok();
+
+    // the fees collector would reject deposits from this farm
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_destination(PenaltyDestination::FeesCollector);
+        })
+        .assert_user_error("Farm is not a known contract in fees collector");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &fees_collector, &rust_biguint!(0), |sc| {
+            sc.add_known_contract(managed_address!(&farm_address));
+        })
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_destination(PenaltyDestination::FeesCollector);
+        })
+        .assert_user_error("Farming token is not a known token in fees collector");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &fees_collector, &rust_biguint!(0), |sc| {
+            sc.add_known_token(managed_token_id!(LP_TOKEN_ID));
+        })
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_destination(PenaltyDestination::FeesCollector);
+        })
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    farm_setup.set_block_epoch(1);
+    farm_setup.set_block_nonce(10);
+
+    let expected_penalty = farm_in_amount * PENALTY_PERCENT / MAX_PERCENT;
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.exit_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        farm_in_amount - expected_penalty,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - expected_penalty),
+    );
+
+    // the penalty is kept in the farm, so exiting does not depend on the fees collector
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        fees_collector.address_ref(),
+        LP_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.pending_penalty().get(),
+                managed_biguint!(expected_penalty)
+            );
+        })
+        .assert_ok();
+
+    let user_address = farm_setup.user_address.clone();
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &user_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.process_pending_penalty_endpoint();
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_pending_penalty_endpoint();
+        })
+        .assert_ok();
+
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        fees_collector.address_ref(),
+        LP_TOKEN_ID,
+        &rust_biguint!(expected_penalty),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .check_esdt_balance(&farm_address, LP_TOKEN_ID, &rust_biguint!(0));
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_pending_penalty_endpoint();
+        })
+        .assert_user_error("No pending penalty");
+}
+
+#[test]
+fn test_exit_farm_with_redistributed_penalty() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+    let owner = farm_setup.owner_address.clone();
+    let farm_address = farm_setup.farm_wrapper.address_ref().clone();
+    let pair = farm_setup.blockchain_wrapper.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner),
+        PairMock::new,
+        "pair mock",
+    );
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        pair.address_ref(),
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_LP_TOKENS),
+    );
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        pair.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_LP_TOKENS),
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_destination(PenaltyDestination::Redistribute);
+        })
+        .assert_user_error("Pair contract address not set");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.pair_contract_address()
+                .set(&managed_address!(pair.address_ref()));
+            sc.reward_token_id()
+                .set(&managed_token_id!(b"OTHER-abcdef"));
+            sc.set_penalty_destination(PenaltyDestination::Redistribute);
+        })
+        .assert_user_error("Pair does not contain the reward token");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.pair_contract_address()
+                .set(&managed_address!(pair.address_ref()));
+            sc.set_penalty_destination(PenaltyDestination::Redistribute);
+        })
+        .assert_ok();
+
+    // the second user keeps farming, and receives the redistributed penalty
+    let farm_in_amount = 100_000_000;
+    farm_setup.enter_farm(farm_in_amount, &[], 1, 0, 0, 0);
+
+    let second_user = farm_setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0));
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &second_user,
+        LP_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &second_user,
+            &farm_setup.farm_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.enter_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    farm_setup.set_block_epoch(1);
+    farm_setup.set_block_nonce(10);
+
+    // the pool was manipulated, so swapping the WEGLD side is 10% below the safe price
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &pair, &rust_biguint!(0), |sc| {
+            sc.set_swap_rate_percent(90);
+        })
+        .assert_ok();
+
+    // exiting does not depend on the pair, the penalty is converted later
+    let user_address = farm_setup.user_address.clone();
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ =
+                    sc.exit_farm_endpoint(managed_biguint!(farm_in_amount), OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    let penalty = farm_in_amount * PENALTY_PERCENT / MAX_PERCENT;
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_address,
+        LP_TOKEN_ID,
+        &rust_biguint!(farm_in_amount + penalty),
+    );
+
+    let process_penalty = |farm_setup: &mut SingleUserFarmSetup<_>| {
+        farm_setup.blockchain_wrapper.execute_tx(
+            &owner,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.process_pending_penalty_endpoint();
+            },
+        )
+    };
+    process_penalty(&mut farm_setup).assert_user_error("Slippage exceeded");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_max_slippage(2_000);
+        })
+        .assert_ok();
+
+    let tx_result = process_penalty(&mut farm_setup);
+    tx_result.assert_ok();
+
+    // the penalized LP tokens are worth 100_000 WEGLD and 100_000 MEX,
+    // and the WEGLD is swapped for 90_000 MEX
+    let redistributed_amount = penalty + penalty * 90 / 100;
+    let first_user_rewards = 10 * PER_BLOCK_REWARD_AMOUNT / 2;
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        LP_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - penalty),
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(first_user_rewards),
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_address,
+        LP_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(10 * PER_BLOCK_REWARD_AMOUNT - first_user_rewards + redistributed_amount),
+    );
+
+    let redistribute_log = tx_result
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"redistribute_penalty".to_vec())
+        .expect("redistribute_penalty event not emitted");
+    farm_setup
+        .blockchain_wrapper
+        .execute_in_managed_environment(|| {
+            let redistributed_rewards =
+                EsdtTokenPayment::<DebugApi>::top_decode(&redistribute_log.data[..]).unwrap();
+            assert_eq!(
+                redistributed_rewards.token_identifier,
+                managed_token_id!(MEX_TOKEN_ID)
+            );
+            assert_eq!(
+                redistributed_rewards.amount,
+                managed_biguint!(redistributed_amount)
+            );
+        });
+
+    // all the farm rewards and the redistributed penalty go to the remaining farmer
+    farm_setup.set_block_epoch(3);
+    farm_setup.set_block_nonce(20);
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &second_user,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ =
+                    sc.exit_farm_endpoint(managed_biguint!(farm_in_amount), OptionalValue::None);
+            },
+        )
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &second_user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(20 * PER_BLOCK_REWARD_AMOUNT - first_user_rewards + redistributed_amount),
+    );
+}
+
+#[test]
+fn test_split_farm_token() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    let first_part = 30_000_000;
+    let second_part = farm_in_amount - first_part;
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let mut split_amounts = MultiValueEncoded::new();
+                split_amounts.push(managed_biguint!(first_part));
+                split_amounts.push(managed_biguint!(second_part));
+
+                let new_tokens = sc.split_farm_token_endpoint(split_amounts).to_vec();
+                assert_eq!(new_tokens.len(), 2);
+                assert_eq!(new_tokens.get(0).token_nonce, 2);
+                assert_eq!(new_tokens.get(0).amount, managed_biguint!(first_part));
+                assert_eq!(new_tokens.get(1).token_nonce, 3);
+                assert_eq!(new_tokens.get(1).amount, managed_biguint!(second_part));
+            },
+        )
+        .assert_ok();
+
+    let _ = DebugApi::dummy();
+    for (nonce, amount) in [(2, first_part), (3, second_part)] {
+        let expected_attributes = FarmTokenAttributes::<DebugApi> {
+            reward_per_share: managed_biguint!(0),
+            entering_epoch: 0,
+            compounded_reward: managed_biguint!(0),
+            current_farm_amount: managed_biguint!(amount),
+            original_owner: managed_address!(&farm_setup.user_address),
+        };
+        farm_setup.blockchain_wrapper.check_nft_balance(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            nonce,
+            &rust_biguint!(amount),
+            Some(&expected_attributes),
+        );
+    }
+    farm_setup
+        .blockchain_wrapper
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(0),
+            None,
+        );
+    farm_setup.check_farm_token_supply(farm_in_amount);
+}
+
+#[test]
+fn test_transfer_farm_position() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    let new_owner = farm_setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0));
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, boosted_rewards) = sc
+                    .transfer_farm_position_endpoint(managed_address!(&new_owner))
+                    .into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 2);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+                assert_eq!(boosted_rewards.amount, managed_biguint!(0));
+            },
+        )
+        .assert_ok();
+
+    let _ = DebugApi::dummy();
+    let expected_attributes = FarmTokenAttributes::<DebugApi> {
+        reward_per_share: managed_biguint!(0),
+        entering_epoch: 0,
+        compounded_reward: managed_biguint!(0),
+        current_farm_amount: managed_biguint!(farm_in_amount),
+        original_owner: managed_address!(&new_owner),
+    };
+    farm_setup.blockchain_wrapper.check_nft_balance(
+        &new_owner,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        Some(&expected_attributes),
+    );
+    farm_setup.check_farm_token_supply(farm_in_amount);
+
+    // the position cannot be transferred to its current owner
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &new_owner,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.transfer_farm_position_endpoint(managed_address!(&new_owner));
+            },
+        )
+        .assert_user_error("Invalid new owner");
+}
+
+#[test]
+fn test_exit_farm_funded_rewards_mode() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+
+    let first_top_up_amount = 30_000;
+    let second_top_up_amount = 20_000;
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &farm_setup.owner_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(first_top_up_amount + second_top_up_amount),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.end_produce_rewards_endpoint();
+                sc.set_funded_rewards_mode(true);
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(first_top_up_amount),
+            |sc| {
+                sc.top_up_rewards();
+                sc.start_produce_rewards_endpoint();
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(10);
+
+    // emission stops once the deposited rewards run out
+    farm_setup.exit_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        first_top_up_amount,
+        farm_in_amount,
+        &rust_biguint!(first_top_up_amount),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS),
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(second_top_up_amount),
+            |sc| {
+                sc.top_up_rewards();
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.withdraw_rewards();
+            },
+        )
+        .assert_user_error("Rewards production must be stopped first");
+
+    // the remaining deposits would be stranded outside of funded mode
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.end_produce_rewards_endpoint();
+                sc.set_funded_rewards_mode(false);
+            },
+        )
+        .assert_user_error("The remaining funded rewards must be withdrawn first");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.end_produce_rewards_endpoint();
+                let withdrawn_rewards = sc.withdraw_rewards();
+                assert_eq!(
+                    withdrawn_rewards.amount,
+                    managed_biguint!(second_top_up_amount)
+                );
+
+                sc.set_funded_rewards_mode(false);
+            },
+        )
+        .assert_ok();
+
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_setup.owner_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(second_top_up_amount),
+    );
+}
+
 #[test]
 fn test_claim_rewards() {
     let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           59
+// Endpoints:                           81
 // Async Callback:                       1
-// Total number of exported functions:  61
+// Total number of exported functions:  83
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -23,9 +23,13 @@ multiversx_sc_wasm_adapter::endpoints! {
         compoundRewards
         exitFarm
         calculateRewardsForGivenPosition
+        getExitPenalty
         mergeFarmTokens
+        splitFarmToken
+        transferFarmPosition
         startProduceRewards
         endProduceRewards
+        processPendingPenalty
         setPerBlockRewardAmount
         getRewardPerShare
         getRewardReserve
@@ -52,10 +56,27 @@ multiversx_sc_wasm_adapter::endpoints! {
         set_penalty_percent
         set_minimum_farming_epochs
         set_burn_gas_limit
+        setPenaltySchedule
+        setPenaltySteps
+        setPenaltyDestination
+        setFeesCollectorAddress
+        setPenaltyMaxSlippage
+        getPenaltyMaxSlippage
         getPenaltyPercent
         getMinimumFarmingEpoch
         getBurnGasLimit
         getPairContractManagedAddress
+        getPenaltySchedule
+        getPenaltySteps
+        getPenaltyDestination
+        getFeesCollectorAddress
+        getPendingPenalty
+        setFundedRewardsMode
+        topUpRewards
+        withdrawRewards
+        isFundedRewardsMode
+        getAccumulatedRewards
+        getRewardCapacity
         setBoostedYieldsRewardsPercentage
         collectUndistributedBoostedRewards
         getBoostedYieldsRewardsPercentage
@@ -74,6 +95,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTotalEnergyForWeek
         getTotalLockedTokensForWeek
         updateEnergyForUser
+        updateEnergyAfterDelegation
         getCurrentClaimProgress
         setEnergyFactoryAddress
         getEnergyFactoryAddress
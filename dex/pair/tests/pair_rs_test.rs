@@ -14,7 +14,7 @@ use multiversx_sc_scenario::{
 use pair::{config::MAX_PERCENTAGE, fee::FeeModule, locking_wrapper::LockingWrapperModule, Pair};
 use pair_setup::*;
 use simple_lock::{
-    locked_token::{LockedTokenAttributes, LockedTokenModule},
+    locked_token::{LockedTokenAttributes, LockedTokenModule, DEFAULT_ENERGY_MULTIPLIER},
     proxy_lp::{LpProxyTokenAttributes, ProxyLpModule},
     SimpleLock,
 };
@@ -199,6 +199,8 @@ fn test_locked_asset() {
             original_token_id: managed_token_id_wrapped!(WEGLD_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 10,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -335,6 +337,8 @@ fn add_liquidity_through_simple_lock_proxy() {
             original_token_id: managed_token_id_wrapped!(WEGLD_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 10,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -360,6 +364,8 @@ fn add_liquidity_through_simple_lock_proxy() {
             original_token_id: managed_token_id_wrapped!(MEX_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 15,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -481,6 +487,8 @@ fn add_liquidity_through_simple_lock_proxy() {
             original_token_id: managed_token_id_wrapped!(WEGLD_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 10,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
     pair_setup.b_mock.check_esdt_balance(
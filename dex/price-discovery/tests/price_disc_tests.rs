@@ -6,7 +6,7 @@ use price_discovery::redeem_token::*;
 use price_discovery::PriceDiscovery;
 
 mod tests_common;
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 use tests_common::*;
 
 const MIN_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
@@ -377,6 +377,8 @@ fn redeem_ok() {
             original_token_id: managed_token_id_wrapped!(LAUNCHED_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: UNLOCK_EPOCH,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -391,6 +393,8 @@ fn redeem_ok() {
             original_token_id: managed_token_id_wrapped!(LAUNCHED_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: UNLOCK_EPOCH,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -405,6 +409,8 @@ fn redeem_ok() {
             original_token_id: managed_token_id_wrapped!(ACCEPTED_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: UNLOCK_EPOCH,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
@@ -19,7 +19,7 @@ use multiversx_sc_scenario::{
     whitebox::BlockchainStateWrapper, whitebox::TxTokenTransfer, DebugApi,
 };
 use simple_lock::{
-    locked_token::{LockedTokenAttributes, LockedTokenModule},
+    locked_token::{LockedTokenAttributes, LockedTokenModule, DEFAULT_ENERGY_MULTIPLIER},
     SimpleLock,
 };
 
@@ -248,6 +248,8 @@ fn user_enable_pair_swaps_through_router_test() {
             original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -285,6 +287,8 @@ fn user_enable_pair_swaps_through_router_test() {
             original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
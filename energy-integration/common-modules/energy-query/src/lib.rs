@@ -4,8 +4,12 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 pub use energy_factory::energy::Energy;
+use energy_factory::energy_delegation::ProxyTrait as _;
 
 static USER_ENERGY_STORAGE_KEY: &[u8] = b"userEnergy";
+static TOTAL_ENERGY_STORAGE_KEY: &[u8] = b"totalEnergy";
+static ENERGY_DELEGATE_STORAGE_KEY: &[u8] = b"energyDelegate";
+static DELEGATED_ENERGY_STORAGE_KEY: &[u8] = b"delegatedEnergy";
 static LOCKED_TOKEN_ID_STORAGE_KEY: &[u8] = b"lockedTokenId";
 static BASE_TOKEN_ID_STORAGE_KEY: &[u8] = b"baseAssetTokenId";
 
@@ -34,24 +38,100 @@ pub trait EnergyQueryModule {
         energy
     }
 
+    /// The user's effective energy at the end of the given epoch, as checkpointed by the energy factory.
+    /// Only final for past epochs, as energy may still change during the current one.
+    fn get_energy_amount_at_epoch(&self, user: &ManagedAddress, epoch: u64) -> BigUint {
+        if self.energy_factory_address().is_empty() {
+            return BigUint::zero();
+        }
+
+        let energy_factory_address = self.energy_factory_address().get();
+        self.energy_factory_proxy(energy_factory_address)
+            .get_effective_energy_at_epoch(user.clone(), epoch)
+            .execute_on_dest_context()
+    }
+
+    fn get_energy_amount_at_epoch_non_zero(&self, user: &ManagedAddress, epoch: u64) -> BigUint {
+        let energy = self.get_energy_amount_at_epoch(user, epoch);
+        require!(energy > 0, "No energy");
+
+        energy
+    }
+
+    /// The user's effective energy: the own energy, unless delegated to another address,
+    /// plus the energy delegated to the user
     fn get_energy_entry(&self, user: &ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
         if self.energy_factory_address().is_empty() {
             return Energy::new_zero_energy(current_epoch);
         }
 
+        let delegate_buffer: ManagedBuffer =
+            self.read_user_storage_from_energy_factory(ENERGY_DELEGATE_STORAGE_KEY, user);
+        let mut user_energy = if delegate_buffer.is_empty() {
+            self.get_own_energy_entry(user)
+        } else {
+            Energy::new_zero_energy(current_epoch)
+        };
+
+        let delegated_energy_buffer: ManagedBuffer =
+            self.read_user_storage_from_energy_factory(DELEGATED_ENERGY_STORAGE_KEY, user);
+        let delegated_energy = self.decode_energy_entry(delegated_energy_buffer, current_epoch);
+        user_energy.add_entry(&delegated_energy);
+
+        user_energy
+    }
+
+    /// The energy entry of the user's own locked tokens, regardless of delegation.
+    /// Use this one when computing a new entry to be saved in the energy factory.
+    fn get_own_energy_entry(&self, user: &ManagedAddress) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        if self.energy_factory_address().is_empty() {
+            return Energy::new_zero_energy(current_epoch);
+        }
+
         let energy_buffer: ManagedBuffer = self.read_energy_from_factory(user);
+        self.decode_energy_entry(energy_buffer, current_epoch)
+    }
+
+    fn decode_energy_entry(
+        &self,
+        energy_buffer: ManagedBuffer,
+        current_epoch: u64,
+    ) -> Energy<Self::Api> {
         if !energy_buffer.is_empty() {
-            let mut user_energy: Energy<Self::Api> = Energy::top_decode(energy_buffer)
+            let mut energy: Energy<Self::Api> = Energy::top_decode(energy_buffer)
                 .unwrap_or_else(|_| sc_panic!("Failed decoding result from energy factory"));
-            user_energy.deplete(current_epoch);
+            energy.deplete(current_epoch);
 
-            user_energy
+            energy
         } else {
             Energy::new_zero_energy(current_epoch)
         }
     }
 
+    fn get_total_energy_amount(&self) -> BigUint {
+        let total_energy = self.get_total_energy_entry();
+        total_energy.get_energy_amount()
+    }
+
+    fn get_total_locked_tokens(&self) -> BigUint {
+        let total_energy = self.get_total_energy_entry();
+        total_energy.get_total_locked_tokens().clone()
+    }
+
+    fn get_total_energy_entry(&self) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        if self.energy_factory_address().is_empty() {
+            return Energy::new_zero_energy(current_epoch);
+        }
+
+        let energy_buffer: ManagedBuffer = self.read_raw_storage_from_energy_factory(
+            ManagedBuffer::new_from_bytes(TOTAL_ENERGY_STORAGE_KEY),
+        );
+        self.decode_energy_entry(energy_buffer, current_epoch)
+    }
+
     fn get_base_token_id(&self) -> TokenIdentifier {
         self.read_raw_storage_from_energy_factory(ManagedBuffer::new_from_bytes(
             BASE_TOKEN_ID_STORAGE_KEY,
@@ -65,7 +145,15 @@ pub trait EnergyQueryModule {
     }
 
     fn read_energy_from_factory<T: TopDecode>(&self, user: &ManagedAddress) -> T {
-        let mut key_buffer = ManagedBuffer::new_from_bytes(USER_ENERGY_STORAGE_KEY);
+        self.read_user_storage_from_energy_factory(USER_ENERGY_STORAGE_KEY, user)
+    }
+
+    fn read_user_storage_from_energy_factory<T: TopDecode>(
+        &self,
+        base_key: &[u8],
+        user: &ManagedAddress,
+    ) -> T {
+        let mut key_buffer = ManagedBuffer::new_from_bytes(base_key);
         key_buffer.append(user.as_managed_buffer());
 
         self.read_raw_storage_from_energy_factory(key_buffer)
@@ -29,6 +29,41 @@ pub trait UpdateClaimProgressEnergyModule:
         self.update_energy_and_progress(&user);
     }
 
+    /// Called by the energy factory after the user's energy was delegated or undelegated,
+    /// so the energy moved to another user is not counted for both of them.
+    ///
+    /// Progress from previous weeks is only updated if the user's energy decreased,
+    /// in which case the unclaimed rewards would be lost on claim anyway.
+    /// Otherwise, the new energy is picked up on the next claim.
+    #[endpoint(updateEnergyAfterDelegation)]
+    fn update_energy_after_delegation(&self, user: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.energy_factory_address().get(),
+            "Only the energy factory may update energy after delegation"
+        );
+
+        let claim_progress_mapper = self.current_claim_progress(&user);
+        if claim_progress_mapper.is_empty() {
+            return;
+        }
+
+        let claim_progress = claim_progress_mapper.get();
+        let current_week = self.get_current_week();
+        if claim_progress.week != current_week {
+            let current_epoch = self.blockchain().get_block_epoch();
+            let mut prev_energy = claim_progress.energy;
+            prev_energy.deplete(current_epoch);
+
+            let current_energy = self.get_energy_entry(&user);
+            if current_energy.get_energy_amount_raw() >= prev_energy.get_energy_amount_raw() {
+                return;
+            }
+        }
+
+        self.update_energy_and_progress(&user);
+    }
+
     fn update_energy_and_progress(&self, caller: &ManagedAddress) {
         let current_week = self.get_current_week();
         let current_user_energy = self.get_energy_entry(caller);
@@ -43,6 +43,22 @@ pub trait EnergyFactoryMock {
         }
     }
 
+    /// Energy history is not tracked, so the current entry is depleted up to the given epoch
+    #[view(getEffectiveEnergyAtEpoch)]
+    fn get_effective_energy_at_epoch(&self, user: ManagedAddress, epoch: u64) -> BigUint {
+        let mapper = self.user_energy(&user);
+        if mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let mut energy = mapper.get();
+        if epoch > energy.get_last_update_epoch() {
+            energy.deplete(epoch);
+        }
+
+        energy.get_energy_amount()
+    }
+
     #[endpoint(setUserEnergyAfterLockedTokenTransfer)]
     fn set_user_energy_after_locked_token_transfer(
         &self,
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            4
+// Endpoints:                            5
 // Async Callback (empty):               1
-// Total number of exported functions:   6
+// Total number of exported functions:   7
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -21,6 +21,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         setUserEnergy
         getEnergyAmountForUser
         getEnergyEntryForUser
+        getEffectiveEnergyAtEpoch
         setUserEnergyAfterLockedTokenTransfer
     )
 }
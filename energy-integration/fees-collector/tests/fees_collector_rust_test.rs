@@ -1,15 +1,16 @@
 mod fees_collector_test_setup;
 
+use energy_factory::energy_delegation::EnergyDelegationModule;
 use energy_query::Energy;
 use fees_collector::additional_locked_tokens::{AdditionalLockedTokensModule, BLOCKS_IN_WEEK};
 use fees_collector::fees_accumulation::FeesAccumulationModule;
 use fees_collector_test_setup::*;
-use multiversx_sc::types::{BigInt, EsdtTokenPayment, ManagedVec};
+use multiversx_sc::types::{BigInt, EsdtTokenPayment, ManagedVec, MultiValueEncoded};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
     DebugApi,
 };
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 use weekly_rewards_splitting::locked_token_buckets::LockedTokensBucket;
 use weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule;
 use weekly_rewards_splitting::{
@@ -1089,6 +1090,8 @@ fn claim_locked_rewards_with_energy_update_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 1440,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -1184,6 +1187,8 @@ fn claim_locked_rewards_with_energy_update_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 1440,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -1348,3 +1353,110 @@ fn additional_locked_tokens_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn delegate_energy_updates_claim_progress_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut fc_setup =
+        FeesCollectorSetup::new(fees_collector::contract_obj, energy_factory::contract_obj);
+
+    let first_user = fc_setup.b_mock.create_user_account(&rust_zero);
+    let second_user = fc_setup.b_mock.create_user_account(&rust_zero);
+
+    fc_setup.set_energy(&first_user, 500, 1_000);
+    fc_setup.set_energy(&second_user, 500, 3_000);
+
+    fc_setup.claim(&first_user).assert_ok();
+    fc_setup.claim(&second_user).assert_ok();
+
+    let fc_address = fc_setup.fc_wrapper.address_ref().clone();
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &fc_setup.owner_address,
+            &fc_setup.energy_factory_wrapper,
+            &rust_zero,
+            |sc| {
+                let mut consumers = MultiValueEncoded::new();
+                consumers.push(managed_address!(&fc_address));
+                sc.add_energy_consumers(consumers);
+            },
+        )
+        .assert_ok();
+
+    // only the energy factory may update the progress after delegation
+    fc_setup
+        .b_mock
+        .execute_tx(&first_user, &fc_setup.fc_wrapper, &rust_zero, |sc| {
+            sc.update_energy_after_delegation(managed_address!(&first_user));
+        })
+        .assert_user_error("Only the energy factory may update energy after delegation");
+
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &fc_setup.energy_factory_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.delegate_energy(managed_address!(&second_user));
+            },
+        )
+        .assert_ok();
+
+    // the delegated energy is only counted once
+    let current_epoch = fc_setup.current_epoch;
+    fc_setup
+        .b_mock
+        .execute_query(&fc_setup.fc_wrapper, |sc| {
+            assert_eq!(sc.total_energy_for_week(1).get(), 4_000);
+            assert_eq!(sc.total_locked_tokens_for_week(1).get(), 1_000);
+
+            assert!(sc
+                .current_claim_progress(&managed_address!(&first_user))
+                .is_empty());
+            assert_eq!(
+                sc.current_claim_progress(&managed_address!(&second_user))
+                    .get(),
+                ClaimProgress {
+                    energy: Energy::new(
+                        BigInt::from(managed_biguint!(4_000)),
+                        current_epoch,
+                        managed_biguint!(1_000),
+                    ),
+                    week: 1
+                }
+            );
+        })
+        .assert_ok();
+
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &fc_setup.energy_factory_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.undelegate_energy();
+            },
+        )
+        .assert_ok();
+
+    // the first user is registered again on their next claim
+    fc_setup
+        .b_mock
+        .execute_query(&fc_setup.fc_wrapper, |sc| {
+            assert_eq!(sc.total_energy_for_week(1).get(), 3_000);
+            assert_eq!(sc.total_locked_tokens_for_week(1).get(), 500);
+        })
+        .assert_ok();
+
+    fc_setup.claim(&first_user).assert_ok();
+    fc_setup
+        .b_mock
+        .execute_query(&fc_setup.fc_wrapper, |sc| {
+            assert_eq!(sc.total_energy_for_week(1).get(), 4_000);
+            assert_eq!(sc.total_locked_tokens_for_week(1).get(), 1_000);
+        })
+        .assert_ok();
+}
@@ -14,7 +14,9 @@ use fees_collector::{config::ConfigModule, fees_accumulation::FeesAccumulationMo
 use locking_module::lock_with_energy_module::LockWithEnergyModule;
 use multiversx_sc_modules::pause::PauseModule;
 use sc_whitelist_module::SCWhitelistModule;
-use simple_lock::locked_token::{LockedTokenAttributes, LockedTokenModule};
+use simple_lock::locked_token::{
+    LockedTokenAttributes, LockedTokenModule, DEFAULT_ENERGY_MULTIPLIER,
+};
 use week_timekeeping::{Week, WeekTimekeepingModule, EPOCHS_IN_WEEK};
 
 pub const INIT_EPOCH: u64 = 5;
@@ -117,6 +119,8 @@ where
                 original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
                 original_token_nonce: 1,
                 unlock_epoch: 100,
+                perpetual_lock_epochs: 0,
+                energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
             },
         );
 
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           35
+// Endpoints:                           36
 // Async Callback (empty):               1
-// Total number of exported functions:  37
+// Total number of exported functions:  38
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -33,6 +33,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTotalEnergyForWeek
         getTotalLockedTokensForWeek
         updateEnergyForUser
+        updateEnergyAfterDelegation
         getCurrentClaimProgress
         depositSwapFees
         getAccumulatedFees
@@ -86,7 +86,8 @@ pub trait GovernanceV2:
         );
 
         let proposer = self.blockchain().get_caller();
-        let user_energy = self.get_energy_amount_non_zero(&proposer);
+        let snapshot_epoch = self.get_energy_snapshot_epoch();
+        let user_energy = self.get_energy_amount_at_epoch_non_zero(&proposer, snapshot_epoch);
         let min_energy_for_propose = self.min_energy_for_propose().get();
 
         require!(
@@ -140,13 +141,16 @@ pub trait GovernanceV2:
 
         let current_block = self.blockchain().get_block_nonce();
         self.proposal_start_block(proposal_id).set(current_block);
+        self.proposal_energy_snapshot_epoch(proposal_id)
+            .set(snapshot_epoch);
 
         self.proposal_created_event(proposal_id, &proposer, current_block, &proposal);
 
         proposal_id
     }
 
-    /// Vote on a proposal. The voting power depends on the user's energy.
+    /// Vote on a proposal. The voting power is the user's energy at the proposal's snapshot epoch,
+    /// so energy delegated or transferred after the proposal was created may not be used again.
     #[endpoint]
     fn vote(&self, proposal_id: ProposalId, vote: VoteType) {
         self.require_caller_not_self();
@@ -160,7 +164,8 @@ pub trait GovernanceV2:
         let new_user = self.user_voted_proposals(&voter).insert(proposal_id);
         require!(new_user, ALREADY_VOTED_ERR_MSG);
 
-        let user_energy = self.get_energy_amount_non_zero(&voter);
+        let snapshot_epoch = self.proposal_energy_snapshot_epoch(proposal_id).get();
+        let user_energy = self.get_energy_amount_at_epoch_non_zero(&voter, snapshot_epoch);
 
         match vote {
             VoteType::UpVote => {
@@ -292,6 +297,12 @@ pub trait GovernanceV2:
         self.proposal_canceled_event(proposal_id);
     }
 
+    /// The last epoch before the proposal's creation, as checkpoints of the current epoch are not final yet
+    fn get_energy_snapshot_epoch(&self) -> u64 {
+        let current_epoch = self.blockchain().get_block_epoch();
+        current_epoch.saturating_sub(1)
+    }
+
     fn total_gas_needed(
         &self,
         actions: &ArrayVec<GovernanceAction<Self::Api>, MAX_GOVERNANCE_PROPOSAL_ACTIONS>,
@@ -60,6 +60,7 @@ pub trait ProposalStorageModule {
     fn clear_proposal(&self, proposal_id: ProposalId) {
         self.proposals().clear_entry(proposal_id);
         self.proposal_start_block(proposal_id).clear();
+        self.proposal_energy_snapshot_epoch(proposal_id).clear();
         self.proposal_queue_block(proposal_id).clear();
         self.proposal_votes(proposal_id).clear();
     }
@@ -70,6 +71,11 @@ pub trait ProposalStorageModule {
     #[storage_mapper("proposalStartBlock")]
     fn proposal_start_block(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
 
+    /// The epoch at which the voting power of proposers and voters is taken
+    #[view(getProposalEnergySnapshotEpoch)]
+    #[storage_mapper("proposalEnergySnapshotEpoch")]
+    fn proposal_energy_snapshot_epoch(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
+
     #[storage_mapper("proposalQueueBlock")]
     fn proposal_queue_block(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
 
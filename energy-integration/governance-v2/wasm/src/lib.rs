@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           27
+// Endpoints:                           28
 // Async Callback (empty):               1
-// Total number of exported functions:  29
+// Total number of exported functions:  30
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -36,6 +36,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getVotingPeriodInBlocks
         getLockTimeAfterVotingEndsInBlocks
         getFeeTokenId
+        getProposalEnergySnapshotEpoch
         getProposalVotes
         depositTokensForProposal
         claimDepositedTokens
@@ -1,6 +1,8 @@
 use fixed_supply_token::FixedSupplyToken;
 use multiversx_sc::codec::{NestedDecodeInput, TopDecodeInput};
 
+use crate::farm_stacks::{FarmStackId, DEFAULT_FARM_STACK_ID};
+
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -11,6 +13,7 @@ pub struct DualYieldTokenAttributes<M: ManagedTypeApi> {
     pub virtual_pos_token_nonce: u64,
     pub virtual_pos_token_amount: BigUint<M>,
     pub real_pos_token_amount: BigUint<M>,
+    pub farm_stack_id: FarmStackId,
 }
 
 impl<M: ManagedTypeApi> DualYieldTokenAttributes<M> {
@@ -26,6 +29,7 @@ impl<M: ManagedTypeApi> DualYieldTokenAttributes<M> {
             virtual_pos_token_nonce,
             virtual_pos_token_amount,
             real_pos_token_amount: BigUint::zero(),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }
     }
 
@@ -61,6 +65,11 @@ impl<M: ManagedTypeApi> NestedDecode for DualYieldTokenAttributes<M> {
         }
 
         let real_pos_token_amount = BigUint::dep_decode(input)?;
+        let farm_stack_id = if input.is_depleted() {
+            DEFAULT_FARM_STACK_ID
+        } else {
+            FarmStackId::dep_decode(input)?
+        };
 
         if !input.is_depleted() {
             return Result::Err(DecodeError::INPUT_TOO_LONG);
@@ -72,6 +81,7 @@ impl<M: ManagedTypeApi> NestedDecode for DualYieldTokenAttributes<M> {
             virtual_pos_token_nonce,
             virtual_pos_token_amount,
             real_pos_token_amount,
+            farm_stack_id,
         })
     }
 }
@@ -97,8 +107,18 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for DualYieldTokenAttributes<M> {
             virtual_pos_token_nonce: self.virtual_pos_token_nonce,
             virtual_pos_token_amount: new_virtual_pos_amount,
             real_pos_token_amount: new_real_pos_amount,
+            farm_stack_id: self.farm_stack_id,
         }
     }
+
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self {
+        let part = self.clone().into_part(payment_amount);
+        self.lp_farm_token_amount -= &part.lp_farm_token_amount;
+        self.virtual_pos_token_amount -= &part.virtual_pos_token_amount;
+        self.real_pos_token_amount -= &part.real_pos_token_amount;
+
+        part
+    }
 }
 
 #[multiversx_sc::module]
@@ -10,8 +10,11 @@ use farm_staking::{
 };
 use pair::safe_price::ProxyTrait as _;
 
-use crate::result_types::*;
-use pair::RemoveLiquidityResultType;
+use crate::{farm_stacks::FarmStack, result_types::*};
+use pair::{AddLiquidityResultType, RemoveLiquidityResultType};
+
+static PAIR_FIRST_TOKEN_ID_KEY: &[u8] = b"first_token_id";
+static PAIR_SECOND_TOKEN_ID_KEY: &[u8] = b"second_token_id";
 
 pub type SafePriceResult<Api> = MultiValue2<EsdtTokenPayment<Api>, EsdtTokenPayment<Api>>;
 
@@ -21,14 +24,39 @@ pub trait ExternalContractsInteractionsModule:
 {
     // lp farm
 
+    fn lp_farm_enter(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        orig_caller: ManagedAddress,
+        lp_tokens: EsdtTokenPayment<Self::Api>,
+        additional_lp_farm_tokens: PaymentsVec<Self::Api>,
+    ) -> LpFarmEnterResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(lp_tokens);
+        payments.append_vec(additional_lp_farm_tokens);
+
+        let lp_farm_address = farm_stack.lp_farm_address.clone();
+        let enter_result: EnterFarmResultType<Self::Api> = self
+            .lp_farm_proxy_obj(lp_farm_address)
+            .enter_farm_endpoint(orig_caller)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (new_lp_farm_tokens, boosted_rewards) = enter_result.into_tuple();
+
+        LpFarmEnterResult {
+            new_lp_farm_tokens,
+            boosted_rewards,
+        }
+    }
+
     fn lp_farm_claim_rewards(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         lp_farm_token_id: TokenIdentifier,
         lp_farm_token_nonce: u64,
         lp_farm_token_amount: BigUint,
     ) -> LpFarmClaimRewardsResult<Self::Api> {
-        let lp_farm_address = self.lp_farm_address().get();
+        let lp_farm_address = farm_stack.lp_farm_address.clone();
         let lp_farm_result: ClaimRewardsResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .claim_rewards_endpoint(orig_caller)
@@ -44,13 +72,14 @@ pub trait ExternalContractsInteractionsModule:
 
     fn lp_farm_exit(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         lp_farm_token_nonce: u64,
         lp_farm_token_amount: BigUint,
         exit_amount: BigUint,
     ) -> LpFarmExitResult<Self::Api> {
-        let lp_farm_token_id = self.lp_farm_token_id().get();
-        let lp_farm_address = self.lp_farm_address().get();
+        let lp_farm_token_id = farm_stack.lp_farm_token_id.clone();
+        let lp_farm_address = farm_stack.lp_farm_address.clone();
         let exit_farm_result: ExitFarmWithPartialPosResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .exit_farm_endpoint(exit_amount, orig_caller)
@@ -67,6 +96,7 @@ pub trait ExternalContractsInteractionsModule:
 
     fn merge_lp_farm_tokens(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         base_lp_token: EsdtTokenPayment,
         mut additional_lp_tokens: PaymentsVec<Self::Api>,
@@ -77,7 +107,7 @@ pub trait ExternalContractsInteractionsModule:
 
         additional_lp_tokens.push(base_lp_token);
 
-        let lp_farm_address = self.lp_farm_address().get();
+        let lp_farm_address = farm_stack.lp_farm_address.clone();
         self.lp_farm_proxy_obj(lp_farm_address)
             .merge_farm_tokens_endpoint(orig_caller)
             .with_multi_token_transfer(additional_lp_tokens)
@@ -88,11 +118,12 @@ pub trait ExternalContractsInteractionsModule:
 
     fn staking_farm_enter(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         staking_token_amount: BigUint,
         staking_farm_tokens: PaymentsVec<Self::Api>,
     ) -> StakingFarmEnterResult<Self::Api> {
-        let staking_farm_address = self.staking_farm_address().get();
+        let staking_farm_address = farm_stack.staking_farm_address.clone();
         let enter_result: EnterFarmResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .stake_farm_through_proxy(staking_token_amount, orig_caller)
@@ -106,15 +137,40 @@ pub trait ExternalContractsInteractionsModule:
         }
     }
 
+    fn staking_farm_stake(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        orig_caller: ManagedAddress,
+        staking_tokens: EsdtTokenPayment<Self::Api>,
+        staking_farm_tokens: PaymentsVec<Self::Api>,
+    ) -> StakingFarmEnterResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(staking_tokens);
+        payments.append_vec(staking_farm_tokens);
+
+        let staking_farm_address = farm_stack.staking_farm_address.clone();
+        let enter_result: EnterFarmResultType<Self::Api> = self
+            .staking_farm_proxy_obj(staking_farm_address)
+            .stake_farm_endpoint(orig_caller)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (received_staking_farm_token, boosted_rewards) = enter_result.into_tuple();
+
+        StakingFarmEnterResult {
+            received_staking_farm_token,
+            boosted_rewards,
+        }
+    }
+
     fn staking_farm_claim_rewards(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         staking_farm_token_id: TokenIdentifier,
         staking_farm_token_nonce: u64,
         staking_farm_token_amount: BigUint,
         new_staking_farm_value: BigUint,
     ) -> StakingFarmClaimRewardsResult<Self::Api> {
-        let staking_farm_address = self.staking_farm_address().get();
+        let staking_farm_address = farm_stack.staking_farm_address.clone();
         let staking_farm_result: ClaimRewardsResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .claim_rewards_with_new_value(new_staking_farm_value, orig_caller)
@@ -134,13 +190,14 @@ pub trait ExternalContractsInteractionsModule:
 
     fn staking_farm_unstake(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         staking_tokens: EsdtTokenPayment<Self::Api>,
         farm_token_nonce: u64,
         farm_token_amount: BigUint,
         exit_amount: BigUint,
     ) -> StakingFarmExitResult<Self::Api> {
-        let staking_farm_token_id = self.staking_farm_token_id().get();
+        let staking_farm_token_id = farm_stack.staking_farm_token_id.clone();
         let mut payments = ManagedVec::from_single_item(staking_tokens);
         payments.push(EsdtTokenPayment::new(
             staking_farm_token_id,
@@ -148,7 +205,7 @@ pub trait ExternalContractsInteractionsModule:
             farm_token_amount,
         ));
 
-        let staking_farm_address = self.staking_farm_address().get();
+        let staking_farm_address = farm_stack.staking_farm_address.clone();
         let unstake_result: ExitFarmWithPartialPosResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .unstake_farm_through_proxy(exit_amount, orig_caller)
@@ -166,13 +223,14 @@ pub trait ExternalContractsInteractionsModule:
 
     fn staking_farm_unstake_user_position(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         orig_caller: ManagedAddress,
         farm_token_nonce: u64,
         farm_token_amount: BigUint,
         exit_amount: BigUint,
     ) -> StakingFarmExitResult<Self::Api> {
-        let staking_farm_token_id = self.staking_farm_token_id().get();
-        let staking_farm_address = self.staking_farm_address().get();
+        let staking_farm_token_id = farm_stack.staking_farm_token_id.clone();
+        let staking_farm_address = farm_stack.staking_farm_address.clone();
         let unstake_result: ExitFarmWithPartialPosResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .unstake_farm(exit_amount, orig_caller)
@@ -190,13 +248,86 @@ pub trait ExternalContractsInteractionsModule:
 
     // pair
 
+    fn pair_add_liquidity(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        first_token_payment: EsdtTokenPayment<Self::Api>,
+        second_token_payment: EsdtTokenPayment<Self::Api>,
+        first_token_min_amount: BigUint,
+        second_token_min_amount: BigUint,
+    ) -> PairAddLiquidityResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(first_token_payment.clone());
+        payments.push(second_token_payment.clone());
+
+        let pair_address = farm_stack.pair_address.clone();
+        let add_liq_result: AddLiquidityResultType<Self::Api> = self
+            .pair_proxy_obj(pair_address)
+            .add_liquidity(first_token_min_amount, second_token_min_amount)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (lp_tokens, first_token_used, second_token_used) = add_liq_result.into_tuple();
+
+        let first_token_leftover = EsdtTokenPayment::new(
+            first_token_payment.token_identifier,
+            0,
+            first_token_payment.amount - first_token_used.amount,
+        );
+        let second_token_leftover = EsdtTokenPayment::new(
+            second_token_payment.token_identifier,
+            0,
+            second_token_payment.amount - second_token_used.amount,
+        );
+
+        PairAddLiquidityResult {
+            lp_tokens,
+            first_token_leftover,
+            second_token_leftover,
+        }
+    }
+
+    fn pair_swap_fixed_input(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        input_tokens: EsdtTokenPayment<Self::Api>,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        let pair_address = farm_stack.pair_address.clone();
+        self.pair_proxy_obj(pair_address)
+            .swap_tokens_fixed_input(token_out, amount_out_min)
+            .with_esdt_transfer(input_tokens)
+            .execute_on_dest_context()
+    }
+
+    fn get_pair_token_ids(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+    ) -> (TokenIdentifier, TokenIdentifier) {
+        let pair_address = farm_stack.pair_address.clone();
+        let first_token_id = self.read_storage_from_pair(&pair_address, PAIR_FIRST_TOKEN_ID_KEY);
+        let second_token_id = self.read_storage_from_pair(&pair_address, PAIR_SECOND_TOKEN_ID_KEY);
+
+        (first_token_id, second_token_id)
+    }
+
+    fn read_storage_from_pair<T: TopDecode>(
+        &self,
+        pair_address: &ManagedAddress,
+        storage_key: &[u8],
+    ) -> T {
+        let key_buffer = ManagedBuffer::new_from_bytes(storage_key);
+        self.storage_raw()
+            .read_from_address(pair_address, key_buffer)
+    }
+
     fn pair_remove_liquidity(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         lp_tokens: EsdtTokenPayment<Self::Api>,
         pair_first_token_min_amount: BigUint,
         pair_second_token_min_amount: BigUint,
     ) -> PairRemoveLiquidityResult<Self::Api> {
-        let pair_address = self.pair_address().get();
+        let pair_address = farm_stack.pair_address.clone();
         let pair_withdraw_result: RemoveLiquidityResultType<Self::Api> = self
             .pair_proxy_obj(pair_address)
             .remove_liquidity(pair_first_token_min_amount, pair_second_token_min_amount)
@@ -205,7 +336,7 @@ pub trait ExternalContractsInteractionsModule:
         let (pair_first_token_payment, pair_second_token_payment) =
             pair_withdraw_result.into_tuple();
 
-        let staking_token_id = self.staking_token_id().get();
+        let staking_token_id = farm_stack.staking_token_id.clone();
         let (staking_token_payment, other_token_payment) =
             if pair_first_token_payment.token_identifier == staking_token_id {
                 (pair_first_token_payment, pair_second_token_payment)
@@ -221,14 +352,18 @@ pub trait ExternalContractsInteractionsModule:
         }
     }
 
-    fn get_lp_tokens_safe_price(&self, lp_tokens_amount: BigUint) -> BigUint {
-        let pair_address = self.pair_address().get();
+    fn get_lp_tokens_safe_price(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        lp_tokens_amount: BigUint,
+    ) -> BigUint {
+        let pair_address = farm_stack.pair_address.clone();
         let result: SafePriceResult<Self::Api> = self
             .pair_proxy_obj(pair_address)
             .update_and_get_tokens_for_given_position_with_safe_price(lp_tokens_amount)
             .execute_on_dest_context();
         let (first_token_info, second_token_info) = result.into_tuple();
-        let staking_token_id = self.staking_token_id().get();
+        let staking_token_id = farm_stack.staking_token_id.clone();
 
         if first_token_info.token_identifier == staking_token_id {
             first_token_info.amount
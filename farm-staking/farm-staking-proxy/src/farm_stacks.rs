@@ -0,0 +1,205 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type FarmStackId = u64;
+
+/// The stack configured at deploy. Dual yield tokens created before
+/// the registry existed decode with this ID.
+pub const DEFAULT_FARM_STACK_ID: FarmStackId = 0;
+
+static PAIR_LP_TOKEN_ID_KEY: &[u8] = b"lpTokenIdentifier";
+static FARMING_TOKEN_ID_KEY: &[u8] = b"farming_token_id";
+static FARM_TOKEN_ID_KEY: &[u8] = b"farm_token_id";
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct FarmStack<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub lp_farm_address: ManagedAddress<M>,
+    pub staking_farm_address: ManagedAddress<M>,
+    pub staking_token_id: TokenIdentifier<M>,
+    pub lp_farm_token_id: TokenIdentifier<M>,
+    pub staking_farm_token_id: TokenIdentifier<M>,
+    pub lp_token_id: TokenIdentifier<M>,
+}
+
+#[multiversx_sc::module]
+pub trait FarmStacksModule:
+    crate::external_contracts_interactions::ExternalContractsInteractionsModule
+    + crate::lp_farm_token::LpFarmTokenModule
+    + utils::UtilsModule
+{
+    /// Registers a new (pair, LP farm, staking farm) stack, and returns its ID.
+    /// Each LP token and LP farm token may only belong to a single stack,
+    /// as these are used to route the stake actions.
+    /// The token IDs must match the ones used by the given contracts.
+    #[only_owner]
+    #[endpoint(addFarmStack)]
+    fn add_farm_stack(
+        &self,
+        lp_farm_address: ManagedAddress,
+        staking_farm_address: ManagedAddress,
+        pair_address: ManagedAddress,
+        staking_token_id: TokenIdentifier,
+        lp_farm_token_id: TokenIdentifier,
+        staking_farm_token_id: TokenIdentifier,
+        lp_token_id: TokenIdentifier,
+    ) -> FarmStackId {
+        self.require_sc_address(&lp_farm_address);
+        self.require_sc_address(&staking_farm_address);
+        self.require_sc_address(&pair_address);
+
+        self.require_valid_token_id(&staking_token_id);
+        self.require_valid_token_id(&lp_farm_token_id);
+        self.require_valid_token_id(&staking_farm_token_id);
+        self.require_valid_token_id(&lp_token_id);
+
+        require!(
+            self.find_farm_stack_id_for_lp_farm_token(&lp_farm_token_id)
+                .is_none(),
+            "LP farm token already registered"
+        );
+        require!(
+            self.find_farm_stack_id_for_lp_token(&lp_token_id).is_none(),
+            "LP token already registered"
+        );
+
+        let farm_stack = FarmStack {
+            pair_address,
+            lp_farm_address,
+            staking_farm_address,
+            staking_token_id,
+            lp_farm_token_id,
+            staking_farm_token_id,
+            lp_token_id,
+        };
+        self.require_farm_stack_tokens_match(&farm_stack);
+
+        let farm_stack_id = self.last_farm_stack_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.farm_stack_id_for_lp_farm_token(&farm_stack.lp_farm_token_id)
+            .set(farm_stack_id);
+        self.farm_stack_id_for_lp_token(&farm_stack.lp_token_id)
+            .set(farm_stack_id);
+        self.farm_stacks(farm_stack_id).set(&farm_stack);
+
+        farm_stack_id
+    }
+
+    #[view(getFarmStack)]
+    fn get_farm_stack(&self, farm_stack_id: FarmStackId) -> FarmStack<Self::Api> {
+        if farm_stack_id == DEFAULT_FARM_STACK_ID {
+            return FarmStack {
+                pair_address: self.pair_address().get(),
+                lp_farm_address: self.lp_farm_address().get(),
+                staking_farm_address: self.staking_farm_address().get(),
+                staking_token_id: self.staking_token_id().get(),
+                lp_farm_token_id: self.lp_farm_token_id().get(),
+                staking_farm_token_id: self.staking_farm_token_id().get(),
+                lp_token_id: self.lp_token_id().get(),
+            };
+        }
+
+        let mapper = self.farm_stacks(farm_stack_id);
+        require!(!mapper.is_empty(), "Unknown farm stack");
+
+        mapper.get()
+    }
+
+    fn require_farm_stack_tokens_match(&self, farm_stack: &FarmStack<Self::Api>) {
+        let pair_lp_token_id =
+            self.read_token_id_from_address(&farm_stack.pair_address, PAIR_LP_TOKEN_ID_KEY);
+        require!(
+            pair_lp_token_id == farm_stack.lp_token_id,
+            "Pair LP token does not match"
+        );
+
+        let (first_token_id, second_token_id) = self.get_pair_token_ids(farm_stack);
+        require!(
+            farm_stack.staking_token_id == first_token_id
+                || farm_stack.staking_token_id == second_token_id,
+            "Staking token is not in the pair"
+        );
+
+        let lp_farm_farming_token_id =
+            self.read_token_id_from_address(&farm_stack.lp_farm_address, FARMING_TOKEN_ID_KEY);
+        let lp_farm_token_id =
+            self.read_token_id_from_address(&farm_stack.lp_farm_address, FARM_TOKEN_ID_KEY);
+        require!(
+            lp_farm_farming_token_id == farm_stack.lp_token_id
+                && lp_farm_token_id == farm_stack.lp_farm_token_id,
+            "LP farm tokens do not match"
+        );
+
+        let staking_farm_farming_token_id =
+            self.read_token_id_from_address(&farm_stack.staking_farm_address, FARMING_TOKEN_ID_KEY);
+        let staking_farm_token_id =
+            self.read_token_id_from_address(&farm_stack.staking_farm_address, FARM_TOKEN_ID_KEY);
+        require!(
+            staking_farm_farming_token_id == farm_stack.staking_token_id
+                && staking_farm_token_id == farm_stack.staking_farm_token_id,
+            "Staking farm tokens do not match"
+        );
+    }
+
+    fn read_token_id_from_address(
+        &self,
+        sc_address: &ManagedAddress,
+        storage_key: &[u8],
+    ) -> TokenIdentifier {
+        let key_buffer = ManagedBuffer::new_from_bytes(storage_key);
+        self.storage_raw().read_from_address(sc_address, key_buffer)
+    }
+
+    fn find_farm_stack_id_for_lp_farm_token(
+        &self,
+        lp_farm_token_id: &TokenIdentifier,
+    ) -> Option<FarmStackId> {
+        if lp_farm_token_id == &self.lp_farm_token_id().get() {
+            return Some(DEFAULT_FARM_STACK_ID);
+        }
+
+        let mapper = self.farm_stack_id_for_lp_farm_token(lp_farm_token_id);
+        if mapper.is_empty() {
+            None
+        } else {
+            Some(mapper.get())
+        }
+    }
+
+    fn find_farm_stack_id_for_lp_token(
+        &self,
+        lp_token_id: &TokenIdentifier,
+    ) -> Option<FarmStackId> {
+        if lp_token_id == &self.lp_token_id().get() {
+            return Some(DEFAULT_FARM_STACK_ID);
+        }
+
+        let mapper = self.farm_stack_id_for_lp_token(lp_token_id);
+        if mapper.is_empty() {
+            None
+        } else {
+            Some(mapper.get())
+        }
+    }
+
+    #[storage_mapper("farmStacks")]
+    fn farm_stacks(&self, farm_stack_id: FarmStackId) -> SingleValueMapper<FarmStack<Self::Api>>;
+
+    #[storage_mapper("farmStackIdForLpFarmToken")]
+    fn farm_stack_id_for_lp_farm_token(
+        &self,
+        lp_farm_token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<FarmStackId>;
+
+    #[storage_mapper("farmStackIdForLpToken")]
+    fn farm_stack_id_for_lp_token(
+        &self,
+        lp_token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<FarmStackId>;
+
+    #[view(getLastFarmStackId)]
+    #[storage_mapper("lastFarmStackId")]
+    fn last_farm_stack_id(&self) -> SingleValueMapper<FarmStackId>;
+}
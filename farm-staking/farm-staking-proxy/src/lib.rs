@@ -4,6 +4,7 @@ multiversx_sc::imports!();
 
 pub mod dual_yield_token;
 pub mod external_contracts_interactions;
+pub mod farm_stacks;
 pub mod lp_farm_token;
 pub mod proxy_actions;
 pub mod result_types;
@@ -13,6 +14,7 @@ pub trait FarmStakingProxy:
     dual_yield_token::DualYieldTokenModule
     + external_contracts_interactions::ExternalContractsInteractionsModule
     + lp_farm_token::LpFarmTokenModule
+    + farm_stacks::FarmStacksModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + utils::UtilsModule
     + token_send::TokenSendModule
@@ -21,6 +23,7 @@ pub trait FarmStakingProxy:
     + proxy_actions::claim::ProxyClaimModule
     + proxy_actions::unstake::ProxyUnstakeModule
     + proxy_actions::merge_pos::ProxyMergePosModule
+    + proxy_actions::compound::ProxyCompoundModule
 {
     #[init]
     fn init(
@@ -1,4 +1,7 @@
-use crate::{dual_yield_token::DualYieldTokenAttributes, result_types::ClaimDualYieldResult};
+use crate::{
+    dual_yield_token::DualYieldTokenAttributes, farm_stacks::FarmStack,
+    result_types::ClaimDualYieldResult,
+};
 
 multiversx_sc::imports!();
 
@@ -13,6 +16,7 @@ pub trait ProxyClaimModule:
     crate::dual_yield_token::DualYieldTokenModule
     + crate::external_contracts_interactions::ExternalContractsInteractionsModule
     + crate::lp_farm_token::LpFarmTokenModule
+    + crate::farm_stacks::FarmStacksModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + utils::UtilsModule
     + token_send::TokenSendModule
@@ -31,9 +35,11 @@ pub trait ProxyClaimModule:
         let caller = self.blockchain().get_caller();
         let attributes: DualYieldTokenAttributes<Self::Api> =
             self.get_attributes_as_part_of_fixed_supply(&payment, &dual_yield_token_mapper);
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+        let farm_stack = self.get_farm_stack(attributes.farm_stack_id);
         let internal_claim_result = self.claim_dual_yield(
-            &caller,
-            opt_orig_caller,
+            &farm_stack,
+            orig_caller,
             attributes.get_total_staking_token_amount(),
             attributes,
         );
@@ -55,29 +61,30 @@ pub trait ProxyClaimModule:
 
     fn claim_dual_yield(
         &self,
-        caller: &ManagedAddress,
-        opt_orig_caller: OptionalValue<ManagedAddress>,
+        farm_stack: &FarmStack<Self::Api>,
+        orig_caller: ManagedAddress,
         staking_claim_amount: BigUint,
         attributes: DualYieldTokenAttributes<Self::Api>,
     ) -> InternalClaimResult<Self::Api> {
-        let orig_caller = self.get_orig_caller_from_opt(caller, opt_orig_caller);
-
         let lp_tokens_in_position = self.get_lp_tokens_in_farm_position(
+            farm_stack,
             attributes.lp_farm_token_nonce,
             &attributes.lp_farm_token_amount,
         );
-        let lp_tokens_safe_price = self.get_lp_tokens_safe_price(lp_tokens_in_position);
+        let lp_tokens_safe_price = self.get_lp_tokens_safe_price(farm_stack, lp_tokens_in_position);
         let new_staking_farm_value = &lp_tokens_safe_price + &attributes.real_pos_token_amount;
 
-        let staking_farm_token_id = self.staking_farm_token_id().get();
-        let lp_farm_token_id = self.lp_farm_token_id().get();
+        let staking_farm_token_id = farm_stack.staking_farm_token_id.clone();
+        let lp_farm_token_id = farm_stack.lp_farm_token_id.clone();
         let lp_farm_claim_rewards_result = self.lp_farm_claim_rewards(
+            farm_stack,
             orig_caller.clone(),
             lp_farm_token_id,
             attributes.lp_farm_token_nonce,
             attributes.lp_farm_token_amount,
         );
         let staking_farm_claim_rewards_result = self.staking_farm_claim_rewards(
+            farm_stack,
             orig_caller,
             staking_farm_token_id,
             attributes.virtual_pos_token_nonce,
@@ -93,6 +100,7 @@ pub trait ProxyClaimModule:
             virtual_pos_token_nonce: new_staking_farm_tokens.token_nonce,
             virtual_pos_token_amount: lp_tokens_safe_price,
             real_pos_token_amount: attributes.real_pos_token_amount,
+            farm_stack_id: attributes.farm_stack_id,
         };
 
         InternalClaimResult {
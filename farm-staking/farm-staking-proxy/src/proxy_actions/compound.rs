@@ -0,0 +1,201 @@
+use crate::{
+    dual_yield_token::DualYieldTokenAttributes, farm_stacks::FarmStack,
+    result_types::CompoundDualYieldResult,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait ProxyCompoundModule:
+    crate::dual_yield_token::DualYieldTokenModule
+    + crate::external_contracts_interactions::ExternalContractsInteractionsModule
+    + crate::lp_farm_token::LpFarmTokenModule
+    + crate::farm_stacks::FarmStacksModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + utils::UtilsModule
+    + token_send::TokenSendModule
+    + sc_whitelist_module::SCWhitelistModule
+    + super::claim::ProxyClaimModule
+{
+    /// Claims both yields and adds them to the position:
+    /// - staking farm rewards are staked as part of the user's real staking position
+    /// - LP farm rewards, which must be one of the pair tokens, are half swapped
+    /// into the other pair token, added as liquidity and entered in the LP farm
+    ///
+    /// Tokens not used for adding liquidity and any boosted rewards are sent back.
+    ///
+    /// Arguments:
+    /// - swap_amount_out_min - minimum amount of the other pair token received for the swap
+    /// - lp_token_min_amount - minimum amount of LP tokens received when adding liquidity
+    ///
+    /// Stacks whose LP farm rewards are not pair tokens, e.g. locked rewards farms,
+    /// cannot be compounded.
+    #[payable("*")]
+    #[endpoint(compoundDualYield)]
+    fn compound_dual_yield(
+        &self,
+        swap_amount_out_min: BigUint,
+        lp_token_min_amount: BigUint,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> CompoundDualYieldResult<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        let dual_yield_token_mapper = self.dual_yield_token();
+        dual_yield_token_mapper.require_same_token(&payment.token_identifier);
+
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+        let attributes: DualYieldTokenAttributes<Self::Api> =
+            self.get_attributes_as_part_of_fixed_supply(&payment, &dual_yield_token_mapper);
+        let farm_stack = self.get_farm_stack(attributes.farm_stack_id);
+        let claim_result = self.claim_dual_yield(
+            &farm_stack,
+            orig_caller.clone(),
+            attributes.get_total_staking_token_amount(),
+            attributes,
+        );
+        dual_yield_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let mut new_attributes = claim_result.new_dual_yield_attributes;
+        let mut remaining_payments = ManagedVec::new();
+
+        let lp_farm_rewards = claim_result.lp_farm_rewards;
+        if lp_farm_rewards.amount > 0 {
+            let lp_tokens = self.convert_rewards_to_lp_tokens(
+                &farm_stack,
+                lp_farm_rewards,
+                swap_amount_out_min,
+                lp_token_min_amount,
+                &mut remaining_payments,
+            );
+            let lp_farm_enter_result = self.lp_farm_enter(
+                &farm_stack,
+                orig_caller.clone(),
+                lp_tokens.clone(),
+                ManagedVec::from_single_item(EsdtTokenPayment::new(
+                    farm_stack.lp_farm_token_id.clone(),
+                    new_attributes.lp_farm_token_nonce,
+                    new_attributes.lp_farm_token_amount.clone(),
+                )),
+            );
+            remaining_payments.push(lp_farm_enter_result.boosted_rewards);
+
+            let added_staking_token_amount =
+                self.get_lp_tokens_safe_price(&farm_stack, lp_tokens.amount);
+            let staking_farm_enter_result = self.staking_farm_enter(
+                &farm_stack,
+                orig_caller.clone(),
+                added_staking_token_amount.clone(),
+                ManagedVec::from_single_item(
+                    self.get_staking_farm_position(&farm_stack, &new_attributes),
+                ),
+            );
+            remaining_payments.push(staking_farm_enter_result.boosted_rewards);
+
+            let new_lp_farm_tokens = lp_farm_enter_result.new_lp_farm_tokens;
+            new_attributes.lp_farm_token_nonce = new_lp_farm_tokens.token_nonce;
+            new_attributes.lp_farm_token_amount = new_lp_farm_tokens.amount;
+            new_attributes.virtual_pos_token_nonce = staking_farm_enter_result
+                .received_staking_farm_token
+                .token_nonce;
+            new_attributes.virtual_pos_token_amount += added_staking_token_amount;
+        }
+
+        let staking_farm_rewards = claim_result.staking_farm_rewards;
+        if staking_farm_rewards.amount > 0 {
+            let added_staking_token_amount = staking_farm_rewards.amount.clone();
+            let staking_farm_enter_result = self.staking_farm_stake(
+                &farm_stack,
+                orig_caller,
+                staking_farm_rewards,
+                ManagedVec::from_single_item(
+                    self.get_staking_farm_position(&farm_stack, &new_attributes),
+                ),
+            );
+            remaining_payments.push(staking_farm_enter_result.boosted_rewards);
+
+            new_attributes.virtual_pos_token_nonce = staking_farm_enter_result
+                .received_staking_farm_token
+                .token_nonce;
+            new_attributes.real_pos_token_amount += added_staking_token_amount;
+        }
+
+        let new_dual_yield_tokens =
+            self.create_dual_yield_tokens(&dual_yield_token_mapper, &new_attributes);
+        let compound_result = CompoundDualYieldResult {
+            new_dual_yield_tokens,
+            remaining_payments,
+        };
+
+        compound_result.send_and_return(self, &caller)
+    }
+
+    /// Swaps half of the reward tokens into the other pair token, and adds liquidity.
+    /// Leftovers from adding liquidity are pushed to `remaining_payments`.
+    fn convert_rewards_to_lp_tokens(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        reward_tokens: EsdtTokenPayment,
+        swap_amount_out_min: BigUint,
+        lp_token_min_amount: BigUint,
+        remaining_payments: &mut ManagedVec<EsdtTokenPayment>,
+    ) -> EsdtTokenPayment {
+        let (first_token_id, second_token_id) = self.get_pair_token_ids(farm_stack);
+        let reward_is_first_token = reward_tokens.token_identifier == first_token_id;
+        require!(
+            reward_is_first_token || reward_tokens.token_identifier == second_token_id,
+            "LP farm rewards are not pair tokens, cannot compound locked rewards farms"
+        );
+
+        let other_token_id = if reward_is_first_token {
+            second_token_id
+        } else {
+            first_token_id
+        };
+        let swap_amount = &reward_tokens.amount / 2u32;
+        let remaining_reward_tokens = EsdtTokenPayment::new(
+            reward_tokens.token_identifier.clone(),
+            0,
+            &reward_tokens.amount - &swap_amount,
+        );
+        let other_tokens = self.pair_swap_fixed_input(
+            farm_stack,
+            EsdtTokenPayment::new(reward_tokens.token_identifier, 0, swap_amount),
+            other_token_id,
+            swap_amount_out_min,
+        );
+
+        let (first_token_payment, second_token_payment) = if reward_is_first_token {
+            (remaining_reward_tokens, other_tokens)
+        } else {
+            (other_tokens, remaining_reward_tokens)
+        };
+        let add_liq_result = self.pair_add_liquidity(
+            farm_stack,
+            first_token_payment,
+            second_token_payment,
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+        );
+        require!(
+            add_liq_result.lp_tokens.amount >= lp_token_min_amount,
+            "Slippage exceeded"
+        );
+
+        remaining_payments.push(add_liq_result.first_token_leftover);
+        remaining_payments.push(add_liq_result.second_token_leftover);
+
+        add_liq_result.lp_tokens
+    }
+
+    fn get_staking_farm_position(
+        &self,
+        farm_stack: &FarmStack<Self::Api>,
+        attributes: &DualYieldTokenAttributes<Self::Api>,
+    ) -> EsdtTokenPayment {
+        EsdtTokenPayment::new(
+            farm_stack.staking_farm_token_id.clone(),
+            attributes.virtual_pos_token_nonce,
+            attributes.get_total_staking_token_amount(),
+        )
+    }
+}
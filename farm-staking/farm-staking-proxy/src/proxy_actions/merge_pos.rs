@@ -1,6 +1,8 @@
 use common_structs::PaymentsVec;
 
-use crate::{dual_yield_token::DualYieldTokenAttributes, result_types::MergeResult};
+use crate::{
+    dual_yield_token::DualYieldTokenAttributes, farm_stacks::FarmStack, result_types::MergeResult,
+};
 
 use mergeable::Mergeable;
 use unwrappable::Unwrappable;
@@ -13,6 +15,7 @@ pub trait ProxyMergePosModule:
     crate::dual_yield_token::DualYieldTokenModule
     + crate::external_contracts_interactions::ExternalContractsInteractionsModule
     + crate::lp_farm_token::LpFarmTokenModule
+    + crate::farm_stacks::FarmStacksModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + utils::UtilsModule
     + token_send::TokenSendModule
@@ -37,7 +40,9 @@ pub trait ProxyMergePosModule:
         dual_yield_token_mapper.nft_burn(dual_yield_token.token_nonce, &dual_yield_token.amount);
 
         let caller = self.blockchain().get_caller();
-        let staking_farm_rewards = self.claim_staking_rewards_before_merge(&caller, &payments);
+        let farm_stack = self.get_farm_stack(attributes.farm_stack_id);
+        let staking_farm_rewards =
+            self.claim_staking_rewards_before_merge(&farm_stack, &caller, &payments);
 
         let staking_amount_before_merge = attributes.get_total_staking_token_amount();
         for farm_staking_token in &payments {
@@ -45,8 +50,8 @@ pub trait ProxyMergePosModule:
         }
 
         let mut dual_yield_claim_result = self.claim_dual_yield(
-            &caller,
-            OptionalValue::None,
+            &farm_stack,
+            caller.clone(),
             staking_amount_before_merge,
             attributes,
         );
@@ -67,20 +72,42 @@ pub trait ProxyMergePosModule:
         merge_result.send_and_return(self, &caller)
     }
 
+    #[payable("*")]
+    #[endpoint(splitDualYieldToken)]
+    fn split_dual_yield_token(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let dual_yield_token_mapper = self.dual_yield_token();
+        let new_dual_yield_tokens = self
+            .split_and_create_tokens::<DualYieldTokenAttributes<Self::Api>>(
+                &payment,
+                split_amounts,
+                &dual_yield_token_mapper,
+            );
+        self.send().direct_multi(&caller, &new_dual_yield_tokens);
+
+        new_dual_yield_tokens.into()
+    }
+
     fn claim_staking_rewards_before_merge(
         &self,
+        farm_stack: &FarmStack<Self::Api>,
         caller: &ManagedAddress,
         farm_staking_tokens: &PaymentsVec<Self::Api>,
     ) -> EsdtTokenPayment {
-        let staking_farm_token_id = self.staking_farm_token_id().get();
+        let staking_farm_token_id = &farm_stack.staking_farm_token_id;
         let mut opt_staking_farm_rewards = Option::<EsdtTokenPayment>::None;
         for farm_staking_token in farm_staking_tokens {
             require!(
-                farm_staking_token.token_identifier == staking_farm_token_id,
+                &farm_staking_token.token_identifier == staking_farm_token_id,
                 "Invalid staking farm token"
             );
 
             let staking_claim_result = self.staking_farm_claim_rewards(
+                farm_stack,
                 caller.clone(),
                 farm_staking_token.token_identifier,
                 farm_staking_token.token_nonce,
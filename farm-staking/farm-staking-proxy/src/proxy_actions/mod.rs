@@ -1,4 +1,5 @@
 pub mod claim;
+pub mod compound;
 pub mod merge_pos;
 pub mod stake;
 pub mod unstake;
@@ -1,4 +1,8 @@
-use crate::{dual_yield_token::DualYieldTokenAttributes, result_types::StakeProxyResult};
+use crate::{
+    dual_yield_token::DualYieldTokenAttributes,
+    farm_stacks::{FarmStack, FarmStackId},
+    result_types::{PaymentsVec, StakeFromPairTokensResult, StakeProxyResult},
+};
 
 multiversx_sc::imports!();
 
@@ -7,6 +11,7 @@ pub trait ProxyStakeModule:
     crate::dual_yield_token::DualYieldTokenModule
     + crate::external_contracts_interactions::ExternalContractsInteractionsModule
     + crate::lp_farm_token::LpFarmTokenModule
+    + crate::farm_stacks::FarmStacksModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + utils::UtilsModule
     + token_send::TokenSendModule
@@ -24,22 +29,154 @@ pub trait ProxyStakeModule:
         let lp_farm_token_payment = payments.get(0);
         let additional_payments = payments.slice(1, payments.len()).unwrap_or_default();
 
-        let lp_farm_token_id = self.lp_farm_token_id().get();
+        let farm_stack_id = match self
+            .find_farm_stack_id_for_lp_farm_token(&lp_farm_token_payment.token_identifier)
+        {
+            Some(id) => id,
+            None => sc_panic!("Invalid first payment"),
+        };
+        let farm_stack = self.get_farm_stack(farm_stack_id);
+
+        let output_payments = self.stake_farm_tokens_common(
+            farm_stack_id,
+            &farm_stack,
+            orig_caller,
+            lp_farm_token_payment,
+            additional_payments,
+        );
+
+        output_payments.send_and_return(self, &caller)
+    }
+
+    /// Enters the LP farm with the given LP tokens, and metastakes the resulting position.
+    /// Additional dual yield tokens may be sent after the LP tokens, to be merged.
+    #[payable("*")]
+    #[endpoint(stakeFarmFromLpTokens)]
+    fn stake_farm_from_lp_tokens(
+        &self,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> StakeProxyResult<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+        let payments = self.get_non_empty_payments();
+        let lp_token_payment = payments.get(0);
+        let additional_payments = payments.slice(1, payments.len()).unwrap_or_default();
+
+        require!(lp_token_payment.token_nonce == 0, "Invalid first payment");
+        let farm_stack_id =
+            match self.find_farm_stack_id_for_lp_token(&lp_token_payment.token_identifier) {
+                Some(id) => id,
+                None => sc_panic!("Invalid first payment"),
+            };
+        let farm_stack = self.get_farm_stack(farm_stack_id);
+
+        let lp_farm_token_payment = self
+            .lp_farm_enter(
+                &farm_stack,
+                orig_caller.clone(),
+                lp_token_payment,
+                ManagedVec::new(),
+            )
+            .new_lp_farm_tokens;
+        let output_payments = self.stake_farm_tokens_common(
+            farm_stack_id,
+            &farm_stack,
+            orig_caller,
+            lp_farm_token_payment,
+            additional_payments,
+        );
+
+        output_payments.send_and_return(self, &caller)
+    }
+
+    /// Adds liquidity with the two tokens of the given farm stack's pair, in the pair's token order,
+    /// then enters the LP farm and metastakes the resulting position.
+    /// Additional dual yield tokens may be sent after the pair tokens, to be merged.
+    /// Any pair tokens that were not used for adding liquidity are sent back.
+    ///
+    /// The min amounts guard against slippage when adding liquidity:
+    /// the minimum amounts of each pair token to be used, and the minimum LP tokens to receive.
+    #[payable("*")]
+    #[endpoint(stakeFarmFromPairTokens)]
+    fn stake_farm_from_pair_tokens(
+        &self,
+        farm_stack_id: FarmStackId,
+        first_token_min_amount: BigUint,
+        second_token_min_amount: BigUint,
+        lp_token_min_amount: BigUint,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> StakeFromPairTokensResult<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+        let payments = self.get_non_empty_payments();
+        require!(payments.len() >= 2, "Invalid payments");
+
+        let first_token_payment = payments.get(0);
+        let second_token_payment = payments.get(1);
+        let additional_payments = payments.slice(2, payments.len()).unwrap_or_default();
+
+        let farm_stack = self.get_farm_stack(farm_stack_id);
+        let add_liq_result = self.pair_add_liquidity(
+            &farm_stack,
+            first_token_payment,
+            second_token_payment,
+            first_token_min_amount,
+            second_token_min_amount,
+        );
         require!(
-            lp_farm_token_payment.token_identifier == lp_farm_token_id,
-            "Invalid first payment"
+            add_liq_result.lp_tokens.amount >= lp_token_min_amount,
+            "Slippage exceeded"
+        );
+
+        let lp_farm_token_payment = self
+            .lp_farm_enter(
+                &farm_stack,
+                orig_caller.clone(),
+                add_liq_result.lp_tokens,
+                ManagedVec::new(),
+            )
+            .new_lp_farm_tokens;
+        let stake_result = self.stake_farm_tokens_common(
+            farm_stack_id,
+            &farm_stack,
+            orig_caller,
+            lp_farm_token_payment,
+            additional_payments,
         );
 
+        let output_payments = StakeFromPairTokensResult {
+            dual_yield_tokens: stake_result.dual_yield_tokens,
+            boosted_rewards: stake_result.boosted_rewards,
+            first_token_leftover: add_liq_result.first_token_leftover,
+            second_token_leftover: add_liq_result.second_token_leftover,
+        };
+
+        output_payments.send_and_return(self, &caller)
+    }
+
+    fn stake_farm_tokens_common(
+        &self,
+        farm_stack_id: FarmStackId,
+        farm_stack: &FarmStack<Self::Api>,
+        orig_caller: ManagedAddress,
+        lp_farm_token_payment: EsdtTokenPayment,
+        additional_payments: PaymentsVec<Self::Api>,
+    ) -> StakeProxyResult<Self::Api> {
+        let lp_farm_token_id = farm_stack.lp_farm_token_id.clone();
         let dual_yield_token_mapper = self.dual_yield_token();
         dual_yield_token_mapper.require_all_same_token(&additional_payments);
 
-        let staking_farm_token_id = self.staking_farm_token_id().get();
+        let staking_farm_token_id = farm_stack.staking_farm_token_id.clone();
         let mut additional_staking_farm_tokens = ManagedVec::new();
         let mut additional_lp_farm_tokens = ManagedVec::new();
         let mut total_user_real_staking_tokens = BigUint::zero();
         for p in &additional_payments {
             let attributes: DualYieldTokenAttributes<Self::Api> =
                 self.get_attributes_as_part_of_fixed_supply(&p, &dual_yield_token_mapper);
+            require!(
+                attributes.farm_stack_id == farm_stack_id,
+                "Cannot merge positions of different farm stacks"
+            );
 
             additional_staking_farm_tokens.push(EsdtTokenPayment::new(
                 staking_farm_token_id.clone(),
@@ -59,11 +196,13 @@ pub trait ProxyStakeModule:
         }
 
         let lp_tokens_in_farm = self.get_lp_tokens_in_farm_position(
+            farm_stack,
             lp_farm_token_payment.token_nonce,
             &lp_farm_token_payment.amount,
         );
-        let staking_token_amount = self.get_lp_tokens_safe_price(lp_tokens_in_farm);
+        let staking_token_amount = self.get_lp_tokens_safe_price(farm_stack, lp_tokens_in_farm);
         let staking_farm_enter_result = self.staking_farm_enter(
+            farm_stack,
             orig_caller.clone(),
             staking_token_amount,
             additional_staking_farm_tokens,
@@ -71,6 +210,7 @@ pub trait ProxyStakeModule:
         let received_staking_farm_token = staking_farm_enter_result.received_staking_farm_token;
 
         let merged_lp_farm_tokens = self.merge_lp_farm_tokens(
+            farm_stack,
             orig_caller,
             lp_farm_token_payment,
             additional_lp_farm_tokens,
@@ -84,14 +224,14 @@ pub trait ProxyStakeModule:
             virtual_pos_token_nonce: received_staking_farm_token.token_nonce,
             virtual_pos_token_amount: new_staking_farm_token_amount,
             real_pos_token_amount: total_user_real_staking_tokens,
+            farm_stack_id,
         };
         let new_dual_yield_tokens =
             self.create_dual_yield_tokens(&dual_yield_token_mapper, &new_attributes);
-        let output_payments = StakeProxyResult {
+
+        StakeProxyResult {
             dual_yield_tokens: new_dual_yield_tokens,
             boosted_rewards: staking_farm_enter_result.boosted_rewards,
-        };
-
-        output_payments.send_and_return(self, &caller)
+        }
     }
 }
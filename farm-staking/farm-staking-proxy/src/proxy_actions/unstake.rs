@@ -1,7 +1,10 @@
 use fixed_supply_token::FixedSupplyToken;
 use mergeable::Mergeable;
 
-use crate::{dual_yield_token::DualYieldTokenAttributes, result_types::UnstakeResult};
+use crate::{
+    dual_yield_token::DualYieldTokenAttributes,
+    result_types::{UnstakeResult, UnstakeToUnderlyingResult},
+};
 
 multiversx_sc::imports!();
 
@@ -10,6 +13,7 @@ pub trait ProxyUnstakeModule:
     crate::dual_yield_token::DualYieldTokenModule
     + crate::external_contracts_interactions::ExternalContractsInteractionsModule
     + crate::lp_farm_token::LpFarmTokenModule
+    + crate::farm_stacks::FarmStacksModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + utils::UtilsModule
     + token_send::TokenSendModule
@@ -27,6 +31,79 @@ pub trait ProxyUnstakeModule:
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
         let payment = self.call_value().single_esdt();
+        let unstake_result = self.unstake_farm_tokens_common(
+            orig_caller,
+            payment,
+            pair_first_token_min_amount,
+            pair_second_token_min_amount,
+            exit_amount,
+        );
+
+        unstake_result.send_and_return(self, &caller)
+    }
+
+    /// Exits the full metastaking position in one call.
+    /// Liquidity is removed, with the staking token portion going through unbonding,
+    /// while the other pair token and all the rewards are sent back directly.
+    /// Rewards of the same token are merged into a single payment.
+    ///
+    /// The min amounts are passed to the pair's `removeLiquidity`, in the pair's token order.
+    #[payable("*")]
+    #[endpoint(unstakeFarmTokensToUnderlying)]
+    fn unstake_farm_tokens_to_underlying(
+        &self,
+        pair_first_token_min_amount: BigUint,
+        pair_second_token_min_amount: BigUint,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> UnstakeToUnderlyingResult<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+        let payment = self.call_value().single_esdt();
+        let exit_amount = payment.amount.clone();
+
+        let unstake_result = self.unstake_farm_tokens_common(
+            orig_caller,
+            payment,
+            pair_first_token_min_amount,
+            pair_second_token_min_amount,
+            exit_amount,
+        );
+
+        let mut unbond_staking_farm_tokens =
+            ManagedVec::from_single_item(unstake_result.unbond_staking_farm_token);
+        if let Some(unbond_for_user_pos) = unstake_result.opt_unbond_staking_farm_token_for_user_pos
+        {
+            unbond_staking_farm_tokens.push(unbond_for_user_pos);
+        }
+
+        let mut lp_farm_rewards = unstake_result.lp_farm_rewards;
+        let staking_rewards = unstake_result.staking_rewards;
+        let rewards = if lp_farm_rewards.can_merge_with(&staking_rewards) {
+            lp_farm_rewards.merge_with(staking_rewards);
+            ManagedVec::from_single_item(lp_farm_rewards)
+        } else {
+            let mut rewards = ManagedVec::from_single_item(lp_farm_rewards);
+            rewards.push(staking_rewards);
+            rewards
+        };
+
+        let output_payments = UnstakeToUnderlyingResult {
+            other_token_payment: unstake_result.other_token_payment,
+            rewards,
+            unbond_staking_farm_tokens,
+        };
+
+        output_payments.send_and_return(self, &caller)
+    }
+
+    fn unstake_farm_tokens_common(
+        &self,
+        orig_caller: ManagedAddress,
+        payment: EsdtTokenPayment,
+        pair_first_token_min_amount: BigUint,
+        pair_second_token_min_amount: BigUint,
+        exit_amount: BigUint,
+    ) -> UnstakeResult<Self::Api> {
         let dual_yield_token_mapper = self.dual_yield_token();
         dual_yield_token_mapper.require_same_token(&payment.token_identifier);
 
@@ -42,17 +119,20 @@ pub trait ProxyUnstakeModule:
             "Invalid exit amount"
         );
 
+        let farm_stack = self.get_farm_stack(full_attributes.farm_stack_id);
         let full_staking_token_amount = full_attributes.get_total_staking_token_amount();
         let exit_attributes: DualYieldTokenAttributes<Self::Api> =
             full_attributes.clone().into_part(&exit_amount);
 
         let lp_farm_exit_result = self.lp_farm_exit(
+            &farm_stack,
             orig_caller.clone(),
             full_attributes.lp_farm_token_nonce,
             full_attributes.lp_farm_token_amount,
             exit_attributes.lp_farm_token_amount,
         );
         let remove_liq_result = self.pair_remove_liquidity(
+            &farm_stack,
             lp_farm_exit_result.lp_tokens,
             pair_first_token_min_amount,
             pair_second_token_min_amount,
@@ -61,6 +141,7 @@ pub trait ProxyUnstakeModule:
         let remaining_total_staking_farm_tokens =
             &full_staking_token_amount - &exit_attributes.virtual_pos_token_amount;
         let staking_farm_exit_result = self.staking_farm_unstake(
+            &farm_stack,
             orig_caller.clone(),
             remove_liq_result.staking_token_payment,
             full_attributes.virtual_pos_token_nonce,
@@ -70,6 +151,7 @@ pub trait ProxyUnstakeModule:
 
         let opt_unstake_user_pos_result = if exit_attributes.real_pos_token_amount > 0 {
             let res = self.staking_farm_unstake_user_position(
+                &farm_stack,
                 orig_caller,
                 full_attributes.virtual_pos_token_nonce,
                 remaining_total_staking_farm_tokens,
@@ -92,6 +174,7 @@ pub trait ProxyUnstakeModule:
                 virtual_pos_token_nonce: full_attributes.virtual_pos_token_nonce,
                 virtual_pos_token_amount: remaining_virtual_farm_tokens,
                 real_pos_token_amount: remaining_real_farm_tokens,
+                farm_stack_id: full_attributes.farm_stack_id,
             };
             let new_dual_yield_tokens =
                 self.create_dual_yield_tokens(&dual_yield_token_mapper, &new_attributes);
@@ -108,7 +191,6 @@ pub trait ProxyUnstakeModule:
             res.unbond_staking_farm_token
         });
 
-        let caller = self.blockchain().get_caller();
         let unstake_result = UnstakeResult {
             other_token_payment: remove_liq_result.other_token_payment,
             lp_farm_rewards: lp_farm_exit_result.lp_farm_rewards,
@@ -120,6 +202,6 @@ pub trait ProxyUnstakeModule:
 
         dual_yield_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
 
-        unstake_result.send_and_return(self, &caller)
+        unstake_result
     }
 }
@@ -5,6 +5,11 @@ pub type PaymentsVec<M> = ManagedVec<M, EsdtTokenPayment<M>>;
 
 // lp farm
 
+pub struct LpFarmEnterResult<M: ManagedTypeApi> {
+    pub new_lp_farm_tokens: EsdtTokenPayment<M>,
+    pub boosted_rewards: EsdtTokenPayment<M>,
+}
+
 pub struct LpFarmClaimRewardsResult<M: ManagedTypeApi> {
     pub new_lp_farm_tokens: EsdtTokenPayment<M>,
     pub lp_farm_rewards: EsdtTokenPayment<M>,
@@ -41,6 +46,12 @@ pub struct PairRemoveLiquidityResult<M: ManagedTypeApi> {
     pub other_token_payment: EsdtTokenPayment<M>,
 }
 
+pub struct PairAddLiquidityResult<M: ManagedTypeApi> {
+    pub lp_tokens: EsdtTokenPayment<M>,
+    pub first_token_leftover: EsdtTokenPayment<M>,
+    pub second_token_leftover: EsdtTokenPayment<M>,
+}
+
 // proxy return types
 
 #[derive(TypeAbi, TopEncode, TopDecode)]
@@ -62,6 +73,32 @@ impl<M: ManagedTypeApi> StakeProxyResult<M> {
     }
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct StakeFromPairTokensResult<M: ManagedTypeApi> {
+    pub dual_yield_tokens: EsdtTokenPayment<M>,
+    pub boosted_rewards: EsdtTokenPayment<M>,
+    pub first_token_leftover: EsdtTokenPayment<M>,
+    pub second_token_leftover: EsdtTokenPayment<M>,
+}
+
+impl<M: ManagedTypeApi> StakeFromPairTokensResult<M> {
+    pub fn send_and_return<SC: token_send::TokenSendModule<Api = M>>(
+        self,
+        sc: &SC,
+        to: &ManagedAddress<M>,
+    ) -> Self {
+        let mut payments = ManagedVec::new();
+        payments.push(self.dual_yield_tokens.clone());
+        payments.push(self.boosted_rewards.clone());
+        payments.push(self.first_token_leftover.clone());
+        payments.push(self.second_token_leftover.clone());
+
+        sc.send_multiple_tokens_if_not_zero(to, &payments);
+
+        self
+    }
+}
+
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct ClaimDualYieldResult<M: ManagedTypeApi> {
     pub lp_farm_rewards: EsdtTokenPayment<M>,
@@ -86,6 +123,27 @@ impl<M: ManagedTypeApi> ClaimDualYieldResult<M> {
     }
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct CompoundDualYieldResult<M: ManagedTypeApi> {
+    pub new_dual_yield_tokens: EsdtTokenPayment<M>,
+    pub remaining_payments: PaymentsVec<M>,
+}
+
+impl<M: ManagedTypeApi> CompoundDualYieldResult<M> {
+    pub fn send_and_return<SC: token_send::TokenSendModule<Api = M>>(
+        self,
+        sc: &SC,
+        to: &ManagedAddress<M>,
+    ) -> Self {
+        let mut payments = ManagedVec::from_single_item(self.new_dual_yield_tokens.clone());
+        payments.append_vec(self.remaining_payments.clone());
+
+        sc.send_multiple_tokens_if_not_zero(to, &payments);
+
+        self
+    }
+}
+
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct UnstakeResult<M: ManagedTypeApi> {
     pub other_token_payment: EsdtTokenPayment<M>,
@@ -122,6 +180,29 @@ impl<M: ManagedTypeApi> UnstakeResult<M> {
     }
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct UnstakeToUnderlyingResult<M: ManagedTypeApi> {
+    pub other_token_payment: EsdtTokenPayment<M>,
+    pub rewards: PaymentsVec<M>,
+    pub unbond_staking_farm_tokens: PaymentsVec<M>,
+}
+
+impl<M: ManagedTypeApi> UnstakeToUnderlyingResult<M> {
+    pub fn send_and_return<SC: token_send::TokenSendModule<Api = M>>(
+        self,
+        sc: &SC,
+        to: &ManagedAddress<M>,
+    ) -> Self {
+        let mut payments = ManagedVec::from_single_item(self.other_token_payment.clone());
+        payments.append_vec(self.rewards.clone());
+        payments.append_vec(self.unbond_staking_farm_tokens.clone());
+
+        sc.send_multiple_tokens_if_not_zero(to, &payments);
+
+        self
+    }
+}
+
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct MergeResult<M: ManagedTypeApi> {
     pub lp_farm_rewards: EsdtTokenPayment<M>,
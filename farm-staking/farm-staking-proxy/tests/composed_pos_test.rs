@@ -8,6 +8,7 @@ multiversx_sc::imports!();
 use constants::*;
 use farm_staking::stake_farm::StakeFarmModule;
 use farm_staking_proxy::dual_yield_token::DualYieldTokenAttributes;
+use farm_staking_proxy::farm_stacks::DEFAULT_FARM_STACK_ID;
 use farm_staking_proxy::proxy_actions::claim::ProxyClaimModule;
 use farm_staking_proxy::proxy_actions::merge_pos::ProxyMergePosModule;
 
@@ -126,6 +127,7 @@ fn combine_metastaking_with_staking_pos_test() {
             virtual_pos_token_nonce: 6,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -155,6 +157,7 @@ fn combine_metastaking_with_staking_pos_test() {
             virtual_pos_token_nonce: 7,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -184,6 +187,7 @@ fn combine_metastaking_with_staking_pos_test() {
             virtual_pos_token_nonce: 8,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -332,6 +336,7 @@ fn combine_metastaking_with_staking_pos_partial_actions_test() {
             virtual_pos_token_nonce: 6,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount / 2),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -361,6 +366,7 @@ fn combine_metastaking_with_staking_pos_partial_actions_test() {
             virtual_pos_token_nonce: 7,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount / 4),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount / 2),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -416,6 +422,7 @@ fn combine_metastaking_with_staking_pos_partial_actions_test() {
             virtual_pos_token_nonce: 7,
             virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount / 8),
             real_pos_token_amount: managed_biguint!(composed_pos_full_amount / 4),
+            farm_stack_id: DEFAULT_FARM_STACK_ID,
         }),
     );
 
@@ -7,11 +7,21 @@ multiversx_sc::imports!();
 
 use constants::*;
 use farm_staking_proxy::dual_yield_token::DualYieldTokenAttributes;
+use farm_staking_proxy::farm_stacks::{FarmStacksModule, DEFAULT_FARM_STACK_ID};
 
+use farm_staking_proxy::proxy_actions::compound::ProxyCompoundModule;
+use farm_staking_proxy::proxy_actions::stake::ProxyStakeModule;
 use farm_staking_proxy::proxy_actions::unstake::ProxyUnstakeModule;
 
+use ::config::ConfigModule as _;
+use farm_token::FarmTokenModule;
 use multiversx_sc::codec::Empty;
-use multiversx_sc_scenario::{managed_biguint, managed_token_id, rust_biguint, DebugApi};
+use multiversx_sc::storage::mappers::StorageTokenWrapper;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox::TxTokenTransfer,
+    DebugApi,
+};
+use pair::config::ConfigModule as _;
 use staking_farm_with_lp_staking_contract_interactions::*;
 
 #[test]
@@ -305,6 +315,7 @@ fn unstake_partial_position_test() {
         virtual_pos_token_nonce: 1,
         virtual_pos_token_amount: managed_biguint!(1_001_000_000 / 2),
         real_pos_token_amount: managed_biguint!(0),
+        farm_stack_id: DEFAULT_FARM_STACK_ID,
     };
     let new_dual_yield_token_nonce = dual_yield_token_nonce_after_stake + 1;
     let new_dual_yield_token_amount = dual_yield_token_amount / 2;
@@ -465,6 +476,7 @@ fn test_stake_farm_through_proxy_with_merging() {
                 virtual_pos_token_nonce: 1,
                 virtual_pos_token_amount: managed_biguint!(400_000_000),
                 real_pos_token_amount: managed_biguint!(0),
+                farm_stack_id: DEFAULT_FARM_STACK_ID,
             }),
         )
     });
@@ -496,6 +508,7 @@ fn test_stake_farm_through_proxy_with_merging() {
                 virtual_pos_token_nonce: 2,
                 virtual_pos_token_amount: managed_biguint!(1_000_000_000),
                 real_pos_token_amount: managed_biguint!(0),
+                farm_stack_id: DEFAULT_FARM_STACK_ID,
             }),
         )
     });
@@ -518,3 +531,422 @@ fn test_stake_farm_through_proxy_with_merging() {
         )
     });
 }
+
+#[test]
+fn test_stake_farm_from_pair_tokens() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    // pool reserves are equal, so all tokens are used for adding liquidity
+    let token_amount = 100_000_000;
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(token_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: RIDE_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(token_amount),
+        },
+    ];
+
+    // not enough LP tokens minted
+    setup
+        .b_mock
+        .execute_esdt_multi_transfer(&setup.user_addr, &setup.proxy_wrapper, &payments, |sc| {
+            let _ = sc.stake_farm_from_pair_tokens(
+                DEFAULT_FARM_STACK_ID,
+                managed_biguint!(1),
+                managed_biguint!(1),
+                managed_biguint!(token_amount + 1),
+                OptionalValue::None,
+            );
+        })
+        .assert_user_error("Slippage exceeded");
+
+    let mut dual_yield_nonce = 0;
+    setup
+        .b_mock
+        .execute_esdt_multi_transfer(&setup.user_addr, &setup.proxy_wrapper, &payments, |sc| {
+            let result = sc.stake_farm_from_pair_tokens(
+                DEFAULT_FARM_STACK_ID,
+                managed_biguint!(1),
+                managed_biguint!(1),
+                managed_biguint!(token_amount),
+                OptionalValue::None,
+            );
+            dual_yield_nonce = result.dual_yield_tokens.token_nonce;
+
+            assert_eq!(
+                result.dual_yield_tokens.amount,
+                managed_biguint!(token_amount)
+            );
+            assert_eq!(result.first_token_leftover.amount, managed_biguint!(0));
+            assert_eq!(result.second_token_leftover.amount, managed_biguint!(0));
+        })
+        .assert_ok();
+
+    setup.b_mock.execute_in_managed_environment(|| {
+        setup.b_mock.check_nft_balance(
+            &setup.user_addr,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_nonce,
+            &rust_biguint!(token_amount),
+            Some(&DualYieldTokenAttributes::<DebugApi> {
+                lp_farm_token_nonce: 2,
+                lp_farm_token_amount: managed_biguint!(token_amount),
+                virtual_pos_token_nonce: 1,
+                virtual_pos_token_amount: managed_biguint!(token_amount),
+                real_pos_token_amount: managed_biguint!(0),
+                farm_stack_id: DEFAULT_FARM_STACK_ID,
+            }),
+        )
+    });
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000_000 - token_amount),
+    );
+}
+
+#[test]
+fn test_unstake_to_underlying() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    let expected_staking_token_amount = 1_001_000_000;
+    let dual_yield_token_nonce_after_stake =
+        setup.stake_farm_lp_proxy(1, USER_TOTAL_LP_TOKENS, 1, expected_staking_token_amount);
+
+    setup
+        .b_mock
+        .set_block_nonce(BLOCK_NONCE_AFTER_PAIR_SETUP + 20);
+    setup.b_mock.set_block_epoch(20);
+
+    let dual_yield_token_amount = 1_001_000_000;
+
+    // min amount not reached
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_addr,
+            &setup.proxy_wrapper,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_token_nonce_after_stake,
+            &rust_biguint!(dual_yield_token_amount),
+            |sc| {
+                let _ = sc.unstake_farm_tokens_to_underlying(
+                    managed_biguint!(1),
+                    managed_biguint!(1_001_000_001),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Slippage amount does not match");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_addr,
+            &setup.proxy_wrapper,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_token_nonce_after_stake,
+            &rust_biguint!(dual_yield_token_amount),
+            |sc| {
+                let result = sc.unstake_farm_tokens_to_underlying(
+                    managed_biguint!(1_001_000_000),
+                    managed_biguint!(1_001_000_000),
+                    OptionalValue::None,
+                );
+
+                assert_eq!(
+                    result.other_token_payment.amount,
+                    managed_biguint!(1_001_000_000)
+                );
+
+                // LP farm and staking farm rewards are both RIDE tokens
+                assert_eq!(result.rewards.len(), 1);
+                assert_eq!(
+                    result.rewards.get(0).amount,
+                    managed_biguint!(99_999 + 1_899)
+                );
+
+                assert_eq!(result.unbond_staking_farm_tokens.len(), 1);
+                assert_eq!(
+                    result.unbond_staking_farm_tokens.get(0).amount,
+                    managed_biguint!(1_001_000_000)
+                );
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn test_compound_dual_yield() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    let expected_staking_token_amount = 1_001_000_000;
+    let dual_yield_token_nonce_after_stake =
+        setup.stake_farm_lp_proxy(1, USER_TOTAL_LP_TOKENS, 1, expected_staking_token_amount);
+
+    setup
+        .b_mock
+        .set_block_nonce(BLOCK_NONCE_AFTER_PAIR_SETUP + 20);
+    setup.b_mock.set_block_epoch(20);
+
+    // half of the LP farm rewards cannot be swapped for this many tokens
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_addr,
+            &setup.proxy_wrapper,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_token_nonce_after_stake,
+            &rust_biguint!(expected_staking_token_amount),
+            |sc| {
+                let _ = sc.compound_dual_yield(
+                    managed_biguint!(1_000_000),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_addr,
+            &setup.proxy_wrapper,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_token_nonce_after_stake,
+            &rust_biguint!(expected_staking_token_amount),
+            |sc| {
+                let result = sc.compound_dual_yield(
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+
+                // LP farm rewards were added as liquidity, increasing the position
+                assert!(result.new_dual_yield_tokens.amount > expected_staking_token_amount);
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_addr,
+        DUAL_YIELD_TOKEN_ID,
+        dual_yield_token_nonce_after_stake,
+        &rust_biguint!(0),
+        None,
+    );
+}
+
+#[test]
+fn test_add_farm_stack() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    let pair_addr = setup.pair_wrapper.address_ref().clone();
+    let lp_farm_addr = setup.lp_farm_wrapper.address_ref().clone();
+    let staking_farm_addr = setup.staking_farm_wrapper.address_ref().clone();
+
+    // second stack, reusing the staking token
+    let new_lp_token_id = b"LPTOK2-abcdef";
+    let new_lp_farm_token_id = b"LPFARM2-abcdef";
+    let new_staking_farm_token_id = b"STKFARM2-abcdef";
+    let new_pair_wrapper = setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&setup.owner_addr),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    let new_lp_farm_wrapper = setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&setup.owner_addr),
+        farm::contract_obj,
+        FARM_WASM_PATH,
+    );
+    let new_staking_farm_wrapper = setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&setup.owner_addr),
+        farm_staking::contract_obj,
+        STAKING_FARM_WASM_PATH,
+    );
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &new_pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.first_token_id().set(&managed_token_id!(WEGLD_TOKEN_ID));
+                sc.second_token_id().set(&managed_token_id!(RIDE_TOKEN_ID));
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(new_lp_token_id));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &new_lp_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.farming_token_id()
+                    .set(&managed_token_id!(new_lp_token_id));
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(new_lp_farm_token_id));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &new_staking_farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.farming_token_id()
+                    .set(&managed_token_id!(STAKING_TOKEN_ID));
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(new_staking_farm_token_id));
+            },
+        )
+        .assert_ok();
+    let new_pair_addr = new_pair_wrapper.address_ref().clone();
+    let new_lp_farm_addr = new_lp_farm_wrapper.address_ref().clone();
+    let new_staking_farm_addr = new_staking_farm_wrapper.address_ref().clone();
+
+    // the LP farm token is already used by the default stack
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &setup.proxy_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.add_farm_stack(
+                    managed_address!(&lp_farm_addr),
+                    managed_address!(&staking_farm_addr),
+                    managed_address!(&pair_addr),
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_token_id!(LP_FARM_TOKEN_ID),
+                    managed_token_id!(STAKING_FARM_TOKEN_ID),
+                    managed_token_id!(b"LPTOK2-abcdef"),
+                );
+            },
+        )
+        .assert_user_error("LP farm token already registered");
+
+    // the token IDs must match the ones used by the contracts
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &setup.proxy_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.add_farm_stack(
+                    managed_address!(&lp_farm_addr),
+                    managed_address!(&staking_farm_addr),
+                    managed_address!(&new_pair_addr),
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_token_id!(new_lp_farm_token_id),
+                    managed_token_id!(new_staking_farm_token_id),
+                    managed_token_id!(new_lp_token_id),
+                );
+            },
+        )
+        .assert_user_error("LP farm tokens do not match");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &setup.proxy_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.add_farm_stack(
+                    managed_address!(&new_lp_farm_addr),
+                    managed_address!(&staking_farm_addr),
+                    managed_address!(&new_pair_addr),
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_token_id!(new_lp_farm_token_id),
+                    managed_token_id!(new_staking_farm_token_id),
+                    managed_token_id!(new_lp_token_id),
+                );
+            },
+        )
+        .assert_user_error("Staking farm tokens do not match");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_addr,
+            &setup.proxy_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let farm_stack_id = sc.add_farm_stack(
+                    managed_address!(&new_lp_farm_addr),
+                    managed_address!(&new_staking_farm_addr),
+                    managed_address!(&new_pair_addr),
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_token_id!(new_lp_farm_token_id),
+                    managed_token_id!(new_staking_farm_token_id),
+                    managed_token_id!(new_lp_token_id),
+                );
+                assert_eq!(farm_stack_id, 1);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.proxy_wrapper, |sc| {
+            let default_stack = sc.get_farm_stack(DEFAULT_FARM_STACK_ID);
+            assert_eq!(
+                default_stack.lp_farm_token_id,
+                managed_token_id!(LP_FARM_TOKEN_ID)
+            );
+
+            let new_stack = sc.get_farm_stack(1);
+            assert_eq!(
+                new_stack.lp_farm_token_id,
+                managed_token_id!(new_lp_farm_token_id)
+            );
+            assert_eq!(new_stack.lp_token_id, managed_token_id!(new_lp_token_id));
+            assert_eq!(new_stack.pair_address, managed_address!(&new_pair_addr));
+            assert_eq!(sc.last_farm_stack_id().get(), 1);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.proxy_wrapper, |sc| {
+            let _ = sc.get_farm_stack(2);
+        })
+        .assert_user_error("Unknown farm stack");
+}
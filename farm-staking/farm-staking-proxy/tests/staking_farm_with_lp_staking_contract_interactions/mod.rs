@@ -12,6 +12,7 @@ use farm_staking::{
     unstake_farm::UnstakeFarmModule,
 };
 use farm_staking_proxy::dual_yield_token::DualYieldTokenAttributes;
+use farm_staking_proxy::farm_stacks::DEFAULT_FARM_STACK_ID;
 use farm_staking_proxy::proxy_actions::claim::ProxyClaimModule;
 
 use farm_staking_proxy::proxy_actions::stake::ProxyStakeModule;
@@ -157,6 +158,7 @@ where
                 virtual_pos_token_nonce: expected_staking_farm_token_nonce,
                 virtual_pos_token_amount: managed_biguint!(expected_staking_token_amount),
                 real_pos_token_amount: managed_biguint!(0),
+                farm_stack_id: DEFAULT_FARM_STACK_ID,
             };
 
             self.b_mock.check_nft_balance(
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           16
+// Endpoints:                           24
 // Async Callback:                       1
-// Total number of exported functions:  18
+// Total number of exported functions:  26
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -27,13 +27,21 @@ multiversx_sc_wasm_adapter::endpoints! {
         getFarmTokenId
         getLpTokenId
         getLpFarmTokenId
+        addFarmStack
+        getFarmStack
+        getLastFarmStackId
         addSCAddressToWhitelist
         removeSCAddressFromWhitelist
         isSCAddressWhitelisted
         stakeFarmTokens
+        stakeFarmFromLpTokens
+        stakeFarmFromPairTokens
         claimDualYield
         unstakeFarmTokens
+        unstakeFarmTokensToUnderlying
         mergeMetastakingWithStakingToken
+        splitDualYieldToken
+        compoundDualYield
         callBack
     )
 }
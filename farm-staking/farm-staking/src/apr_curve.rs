@@ -0,0 +1,105 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use math::linear_interpolation;
+
+use crate::custom_rewards::MAX_PERCENT;
+
+pub const MAX_APR_CURVE_POINTS: usize = 10;
+
+/// `staked_ratio` is the staked supply relative to the target staked supply,
+/// using MAX_PERCENT precision, i.e. 10_000 means the target was reached.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, Default)]
+pub struct AprCurvePoint {
+    pub staked_ratio: u64,
+    pub apr: u64,
+}
+
+pub type AllAprCurvePoints = ArrayVec<AprCurvePoint, MAX_APR_CURVE_POINTS>;
+
+/// When set, the APR cap is no longer fixed, but instead given by the curve,
+/// interpolating linearly between the points around the current staked ratio.
+/// Below the first point and above the last one, the cap is the respective point's APR.
+#[multiversx_sc::module]
+pub trait AprCurveModule {
+    fn set_apr_curve_points(
+        &self,
+        target_staked_supply: BigUint,
+        points: MultiValueEncoded<MultiValue2<u64, u64>>,
+    ) {
+        require!(
+            points.len() <= MAX_APR_CURVE_POINTS,
+            "Too many curve points"
+        );
+
+        let mut all_points = AllAprCurvePoints::new();
+        let mut opt_prev_point: Option<AprCurvePoint> = None;
+        for pair in points {
+            let (staked_ratio, apr) = pair.into_tuple();
+            require!(apr > 0, "Invalid APR");
+
+            if let Some(prev_point) = opt_prev_point {
+                require!(
+                    staked_ratio > prev_point.staked_ratio && apr <= prev_point.apr,
+                    "Invalid curve points"
+                );
+            }
+
+            let point = AprCurvePoint { staked_ratio, apr };
+            unsafe {
+                all_points.push_unchecked(point);
+            }
+            opt_prev_point = Some(point);
+        }
+
+        if !all_points.is_empty() {
+            require!(target_staked_supply > 0, "Invalid target staked supply");
+        }
+
+        self.target_staked_supply().set(&target_staked_supply);
+        self.apr_curve_points().set(&all_points);
+    }
+
+    /// Returns None if no curve is set
+    fn get_curve_apr(&self, staked_supply: &BigUint) -> Option<u64> {
+        let points = self.apr_curve_points().get();
+        if points.is_empty() {
+            return None;
+        }
+
+        let target_staked_supply = self.target_staked_supply().get();
+        let staked_ratio = (staked_supply * MAX_PERCENT / target_staked_supply)
+            .to_u64()
+            .unwrap_or(u64::MAX);
+
+        let first_point = points[0];
+        if staked_ratio <= first_point.staked_ratio {
+            return Some(first_point.apr);
+        }
+
+        for i in 1..points.len() {
+            let prev_point = points[i - 1];
+            let next_point = points[i];
+            if staked_ratio <= next_point.staked_ratio {
+                let apr = linear_interpolation::<Self::Api, _>(
+                    prev_point.staked_ratio,
+                    next_point.staked_ratio,
+                    staked_ratio,
+                    prev_point.apr,
+                    next_point.apr,
+                );
+                return Some(apr);
+            }
+        }
+
+        Some(points[points.len() - 1].apr)
+    }
+
+    #[view(getTargetStakedSupply)]
+    #[storage_mapper("targetStakedSupply")]
+    fn target_staked_supply(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getAprCurvePoints)]
+    #[storage_mapper("aprCurvePoints")]
+    fn apr_curve_points(&self) -> SingleValueMapper<AllAprCurvePoints>;
+}
@@ -6,7 +6,7 @@ use common_structs::FarmToken;
 use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
 
-use crate::token_attributes::StakingFarmTokenAttributes;
+use crate::token_attributes::{StakingFarmTokenAttributes, DEFAULT_REWARD_MULTIPLIER};
 
 pub trait FarmStakingTraits =
     crate::custom_rewards::CustomRewardsModule
@@ -37,7 +37,8 @@ where
         let token_rps = token_attributes.get_reward_per_share();
         if storage_cache.reward_per_share > token_rps {
             let rps_diff = &storage_cache.reward_per_share - &token_rps;
-            farm_token_amount * &rps_diff / &storage_cache.division_safety_constant
+            let reward_weight = token_attributes.get_reward_weight(farm_token_amount);
+            reward_weight * &rps_diff / &storage_cache.division_safety_constant
         } else {
             BigUint::zero()
         }
@@ -57,6 +58,19 @@ where
 
         sc.claim_boosted_yields_rewards(caller, farm_token_amount)
     }
+
+    /// The tier multiplier only applies until the position unlocks
+    pub fn get_active_reward_multiplier(
+        sc: &<Self as FarmContract>::FarmSc,
+        token_attributes: &<Self as FarmContract>::AttributesType,
+    ) -> u64 {
+        let current_epoch = sc.blockchain().get_block_epoch();
+        if current_epoch >= token_attributes.unlock_epoch {
+            DEFAULT_REWARD_MULTIPLIER
+        } else {
+            token_attributes.reward_multiplier
+        }
+    }
 }
 
 impl<T> FarmContract for FarmStakingWrapper<T>
@@ -88,7 +102,8 @@ where
             Self::calculate_per_block_rewards(sc, current_block_nonce, last_reward_nonce);
 
         let farm_token_supply = sc.farm_token_supply().get();
-        let extra_rewards_apr_bounded_per_block = sc.get_amount_apr_bounded(&farm_token_supply);
+        let weighted_farm_supply = sc.get_weighted_farm_supply(&farm_token_supply);
+        let extra_rewards_apr_bounded_per_block = sc.get_amount_apr_bounded(&weighted_farm_supply);
 
         let block_nonce_diff = current_block_nonce - last_reward_nonce;
         let extra_rewards_apr_bounded = extra_rewards_apr_bounded_per_block * block_nonce_diff;
@@ -108,7 +123,7 @@ where
         let remaining_rewards = &reward_capacity - &accumulated_rewards;
 
         let mut total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
-        total_reward = core::cmp::min(total_reward, remaining_rewards);
+        total_reward = core::cmp::min(total_reward, remaining_rewards.clone());
         if total_reward == 0 {
             return;
         }
@@ -117,10 +132,15 @@ where
         accumulated_rewards += &total_reward;
         accumulated_rewards_mapper.set(&accumulated_rewards);
 
+        let new_remaining_rewards = reward_capacity - accumulated_rewards;
+        sc.emit_low_reward_capacity_event_if_needed(&remaining_rewards, &new_remaining_rewards);
+
         let split_rewards = sc.take_reward_slice(total_reward);
         if storage_cache.farm_token_supply > 0 {
+            let weighted_farm_supply =
+                sc.get_weighted_farm_supply(&storage_cache.farm_token_supply);
             let increase = (&split_rewards.base_farm * &storage_cache.division_safety_constant)
-                / &storage_cache.farm_token_supply;
+                / &weighted_farm_supply;
             storage_cache.reward_per_share += &increase;
         }
     }
@@ -155,25 +175,30 @@ where
             compounded_reward: BigUint::zero(),
             current_farm_amount: farming_token_amount,
             original_owner: caller,
+            unlock_epoch: 0,
+            reward_multiplier: DEFAULT_REWARD_MULTIPLIER,
         }
     }
 
     fn create_claim_rewards_initial_attributes(
-        _sc: &Self::FarmSc,
+        sc: &Self::FarmSc,
         caller: ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         first_token_attributes: Self::AttributesType,
         current_reward_per_share: BigUint<<Self::FarmSc as ContractBase>::Api>,
     ) -> Self::AttributesType {
+        let reward_multiplier = Self::get_active_reward_multiplier(sc, &first_token_attributes);
         StakingFarmTokenAttributes {
             reward_per_share: current_reward_per_share,
             compounded_reward: first_token_attributes.compounded_reward,
             current_farm_amount: first_token_attributes.current_farm_amount,
             original_owner: caller,
+            unlock_epoch: first_token_attributes.unlock_epoch,
+            reward_multiplier,
         }
     }
 
     fn create_compound_rewards_initial_attributes(
-        _sc: &Self::FarmSc,
+        sc: &Self::FarmSc,
         caller: ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         first_token_attributes: Self::AttributesType,
         current_reward_per_share: BigUint<<Self::FarmSc as ContractBase>::Api>,
@@ -181,11 +206,14 @@ where
     ) -> Self::AttributesType {
         let new_pos_compounded_reward = first_token_attributes.compounded_reward + reward;
         let new_pos_current_farm_amount = first_token_attributes.current_farm_amount + reward;
+        let reward_multiplier = Self::get_active_reward_multiplier(sc, &first_token_attributes);
         StakingFarmTokenAttributes {
             reward_per_share: current_reward_per_share,
             compounded_reward: new_pos_compounded_reward,
             current_farm_amount: new_pos_current_farm_amount,
             original_owner: caller,
+            unlock_epoch: first_token_attributes.unlock_epoch,
+            reward_multiplier,
         }
     }
 }
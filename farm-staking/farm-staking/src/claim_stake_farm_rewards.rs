@@ -78,6 +78,21 @@ pub trait ClaimStakeFarmRewardsModule:
             self.set_farm_supply_for_current_week(&claim_result.storage_cache.farm_token_supply);
         }
 
+        let first_farm_token = &claim_result.context.first_farm_token;
+        if first_farm_token.attributes.reward_multiplier
+            != virtual_farm_token.attributes.reward_multiplier
+            || first_farm_token.payment.amount != virtual_farm_token.payment.amount
+        {
+            self.remove_extra_reward_weight(
+                &first_farm_token.payment.amount,
+                first_farm_token.attributes.reward_multiplier,
+            );
+            self.add_extra_reward_weight(
+                &virtual_farm_token.payment.amount,
+                virtual_farm_token.attributes.reward_multiplier,
+            );
+        }
+
         let new_farm_token_nonce = self.send().esdt_nft_create_compact(
             &virtual_farm_token.payment.token_identifier,
             &virtual_farm_token.payment.amount,
@@ -36,6 +36,16 @@ pub trait CompoundStakeFarmRewardsModule:
         let compound_result =
             self.compound_rewards_base::<FarmStakingWrapper<Self>>(caller.clone(), payments);
 
+        let first_farm_token = &compound_result.context.first_farm_token;
+        let new_reward_multiplier = compound_result.new_farm_token.attributes.reward_multiplier;
+        if first_farm_token.attributes.reward_multiplier != new_reward_multiplier {
+            self.remove_extra_reward_weight(
+                &first_farm_token.payment.amount,
+                first_farm_token.attributes.reward_multiplier,
+            );
+        }
+        self.add_extra_reward_weight(&compound_result.compounded_rewards, new_reward_multiplier);
+
         let new_farm_token = compound_result.new_farm_token.payment.clone();
         self.send_payment_non_zero(&caller, &new_farm_token);
 
@@ -9,11 +9,14 @@ use crate::base_impl_wrapper::FarmStakingWrapper;
 
 pub const MAX_PERCENT: u64 = 10_000;
 pub const BLOCKS_IN_YEAR: u64 = 31_536_000 / 6; // seconds_in_year / 6_seconds_per_block
+pub const BLOCKS_IN_EPOCH: u64 = 86_400 / 6; // seconds_in_day / 6_seconds_per_block
 const MAX_MIN_UNBOND_EPOCHS: u64 = 30;
 
 #[multiversx_sc::module]
 pub trait CustomRewardsModule:
-    rewards::RewardsModule
+    crate::staking_tiers::StakingTiersModule
+    + crate::apr_curve::AprCurveModule
+    + rewards::RewardsModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + farm_token::FarmTokenModule
@@ -71,6 +74,22 @@ pub trait CustomRewardsModule:
         self.max_annual_percentage_rewards().set(&max_apr);
     }
 
+    /// Pairs of (staked_ratio, apr), replacing the existing curve.
+    /// Staked ratios must be strictly increasing, and APRs must not increase.
+    /// Passing no points disables the curve, falling back to the fixed max APR.
+    #[endpoint(setAprCurve)]
+    fn set_apr_curve(
+        &self,
+        target_staked_supply: BigUint,
+        points: MultiValueEncoded<MultiValue2<u64, u64>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.set_apr_curve_points(target_staked_supply, points);
+    }
+
     #[endpoint(setMinUnbondEpochs)]
     fn set_min_unbond_epochs_endpoint(&self, min_unbond_epochs: Epoch) {
         self.require_caller_has_admin_permissions();
@@ -87,10 +106,101 @@ pub trait CustomRewardsModule:
     }
 
     fn get_amount_apr_bounded(&self, amount: &BigUint) -> BigUint {
-        let max_apr = self.max_annual_percentage_rewards().get();
+        let max_apr = self.get_effective_max_apr();
         amount * &max_apr / MAX_PERCENT / BLOCKS_IN_YEAR
     }
 
+    /// The APR cap given by the APR curve for the current staked supply,
+    /// or the fixed max APR if no curve is set
+    #[view(getEffectiveMaxApr)]
+    fn get_effective_max_apr(&self) -> BigUint {
+        let farm_token_supply = self.farm_token_supply().get();
+        match self.get_curve_apr(&farm_token_supply) {
+            Some(apr) => BigUint::from(apr),
+            None => self.max_annual_percentage_rewards().get(),
+        }
+    }
+
+    /// The maximum APR a position staked for the given number of epochs may earn
+    #[view(getMaxAprForLockEpochs)]
+    fn get_max_apr_for_lock_epochs(&self, lock_epochs: Epoch) -> BigUint {
+        let max_apr = self.get_effective_max_apr();
+        let reward_multiplier = self.get_tier_reward_multiplier(lock_epochs);
+
+        max_apr * reward_multiplier / MAX_PERCENT
+    }
+
+    /// Once the remaining reward capacity drops below this threshold,
+    /// a `low_reward_capacity` event is emitted. Setting it to 0 disables the event.
+    #[only_owner]
+    #[endpoint(setLowRewardCapacityThreshold)]
+    fn set_low_reward_capacity_threshold(&self, threshold: BigUint) {
+        self.low_reward_capacity_threshold().set(threshold);
+    }
+
+    /// Returns (rewards_per_block, remaining_blocks, remaining_epochs),
+    /// i.e. for how long the remaining reward capacity can sustain the current emission,
+    /// as bounded by the per block reward amount and the APR cap for the current supply.
+    /// The remaining blocks and epochs are 0 if no rewards are being produced.
+    #[view(getRewardRunway)]
+    fn get_reward_runway(&self) -> MultiValue3<BigUint, BigUint, BigUint> {
+        let rewards_per_block = self.get_current_rewards_per_block();
+        if rewards_per_block == 0 {
+            return (BigUint::zero(), BigUint::zero(), BigUint::zero()).into();
+        }
+
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        let last_reward_nonce = self.last_reward_block_nonce().get();
+        let remaining_rewards = self.get_remaining_reward_capacity();
+        let pending_rewards = if current_block_nonce > last_reward_nonce {
+            &rewards_per_block * (current_block_nonce - last_reward_nonce)
+        } else {
+            BigUint::zero()
+        };
+        if pending_rewards >= remaining_rewards {
+            return (rewards_per_block, BigUint::zero(), BigUint::zero()).into();
+        }
+
+        let remaining_blocks = (remaining_rewards - pending_rewards) / &rewards_per_block;
+        let remaining_epochs = &remaining_blocks / BLOCKS_IN_EPOCH;
+
+        (rewards_per_block, remaining_blocks, remaining_epochs).into()
+    }
+
+    fn get_current_rewards_per_block(&self) -> BigUint {
+        if !self.produce_rewards_enabled().get() {
+            return BigUint::zero();
+        }
+
+        let per_block_reward_amount = self.per_block_reward_amount().get();
+        let farm_token_supply = self.farm_token_supply().get();
+        let weighted_farm_supply = self.get_weighted_farm_supply(&farm_token_supply);
+        let apr_bounded_per_block = self.get_amount_apr_bounded(&weighted_farm_supply);
+
+        core::cmp::min(per_block_reward_amount, apr_bounded_per_block)
+    }
+
+    fn get_remaining_reward_capacity(&self) -> BigUint {
+        let reward_capacity = self.reward_capacity().get();
+        let accumulated_rewards = self.accumulated_rewards().get();
+
+        reward_capacity - accumulated_rewards
+    }
+
+    fn emit_low_reward_capacity_event_if_needed(
+        &self,
+        prev_remaining_rewards: &BigUint,
+        remaining_rewards: &BigUint,
+    ) {
+        let threshold = self.low_reward_capacity_threshold().get();
+        if threshold == 0 || prev_remaining_rewards < &threshold || remaining_rewards >= &threshold
+        {
+            return;
+        }
+
+        self.low_reward_capacity_event(remaining_rewards, &threshold);
+    }
+
     #[endpoint(startProduceRewards)]
     fn start_produce_rewards_endpoint(&self) {
         self.require_caller_has_admin_permissions();
@@ -109,7 +219,18 @@ pub trait CustomRewardsModule:
     #[storage_mapper("annualPercentageRewards")]
     fn max_annual_percentage_rewards(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getLowRewardCapacityThreshold)]
+    #[storage_mapper("lowRewardCapacityThreshold")]
+    fn low_reward_capacity_threshold(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getMinUnbondEpochs)]
     #[storage_mapper("minUnbondEpochs")]
     fn min_unbond_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[event("low_reward_capacity")]
+    fn low_reward_capacity_event(
+        &self,
+        #[indexed] remaining_rewards: &BigUint,
+        #[indexed] threshold: &BigUint,
+    );
 }
@@ -11,6 +11,7 @@ use token_attributes::StakingFarmTokenAttributes;
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod apr_curve;
 pub mod base_impl_wrapper;
 pub mod claim_only_boosted_staking_rewards;
 pub mod claim_stake_farm_rewards;
@@ -18,6 +19,7 @@ pub mod compound_stake_farm_rewards;
 pub mod custom_rewards;
 pub mod farm_token_roles;
 pub mod stake_farm;
+pub mod staking_tiers;
 pub mod token_attributes;
 pub mod unbond_farm;
 pub mod unstake_farm;
@@ -48,6 +50,7 @@ pub trait FarmStaking:
     + compound_stake_farm_rewards::CompoundStakeFarmRewardsModule
     + unstake_farm::UnstakeFarmModule
     + unbond_farm::UnbondFarmModule
+    + farm::fees_collector::FeesCollectorModule
     + claim_only_boosted_staking_rewards::ClaimOnlyBoostedStakingRewardsModule
     + farm_boosted_yields::FarmBoostedYieldsModule
     + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
@@ -58,6 +61,8 @@ pub trait FarmStaking:
     + weekly_rewards_splitting::locked_token_buckets::WeeklyRewardsLockedTokenBucketsModule
     + weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule
     + energy_query::EnergyQueryModule
+    + staking_tiers::StakingTiersModule
+    + apr_curve::AprCurveModule
 {
     #[init]
     fn init(
@@ -102,6 +107,26 @@ pub trait FarmStaking:
         merged_farm_token
     }
 
+    #[payable("*")]
+    #[endpoint(splitFarmToken)]
+    fn split_farm_token_endpoint(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let token_mapper = self.farm_token();
+        let new_farm_tokens = self
+            .split_and_create_tokens::<StakingFarmTokenAttributes<Self::Api>>(
+                &payment,
+                split_amounts,
+                &token_mapper,
+            );
+        self.send().direct_multi(&caller, &new_farm_tokens);
+
+        new_farm_tokens.into()
+    }
+
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
@@ -1,13 +1,19 @@
 multiversx_sc::imports!();
 
-use common_structs::PaymentsVec;
+use common_structs::{Epoch, PaymentAttributesPair, PaymentsVec};
+use contexts::{enter_farm_context::EnterFarmContext, storage_cache::StorageCache};
 use farm::EnterFarmResultType;
+use farm_base_impl::base_traits_impl::FarmContract;
 
-use crate::base_impl_wrapper::FarmStakingWrapper;
+use crate::{
+    base_impl_wrapper::FarmStakingWrapper,
+    token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes},
+};
 
 #[multiversx_sc::module]
 pub trait StakeFarmModule:
     crate::custom_rewards::CustomRewardsModule
+    + crate::staking_tiers::StakingTiersModule
     + crate::claim_only_boosted_staking_rewards::ClaimOnlyBoostedStakingRewardsModule
     + rewards::RewardsModule
     + config::ConfigModule
@@ -66,6 +72,139 @@ pub trait StakeFarmModule:
         self.stake_farm_common(original_caller, payments)
     }
 
+    /// Stakes the payment on behalf of the beneficiary, who receives the new farm position.
+    /// Boosted yields are accounted for the beneficiary,
+    /// while the caller keeps no rights over the position.
+    ///
+    /// The beneficiary's claim progress is left untouched,
+    /// so others may not reset their unclaimed boosted rewards.
+    #[payable("*")]
+    #[endpoint(stakeFarmFor)]
+    fn stake_farm_for(&self, beneficiary: ManagedAddress) -> EnterFarmResultType<Self::Api> {
+        require!(!beneficiary.is_zero(), "Invalid beneficiary");
+
+        let payment = self.call_value().single_esdt();
+        let enter_result = self.enter_farm_base::<FarmStakingWrapper<Self>>(
+            beneficiary.clone(),
+            ManagedVec::from_single_item(payment),
+        );
+
+        let new_farm_token = enter_result.new_farm_token.payment.clone();
+        self.send_payment_non_zero(&beneficiary, &new_farm_token);
+
+        self.set_farm_supply_for_current_week(&enter_result.storage_cache.farm_token_supply);
+
+        self.emit_enter_farm_event(
+            &beneficiary,
+            enter_result.context.farming_token_payment,
+            enter_result.new_farm_token,
+            enter_result.created_with_merge,
+            enter_result.storage_cache,
+        );
+
+        let boosted_rewards =
+            EsdtTokenPayment::new(self.reward_token_id().get(), 0, BigUint::zero());
+
+        (new_farm_token, boosted_rewards).into()
+    }
+
+    /// Stakes for a fixed term, chosen from the configured staking tiers.
+    /// The position earns rewards scaled by the tier's multiplier,
+    /// and cannot be unstaked before the lock expires.
+    /// The first claim or compound after the lock expires resets the multiplier.
+    #[payable("*")]
+    #[endpoint(stakeFarmLocked)]
+    fn stake_farm_locked_endpoint(
+        &self,
+        lock_epochs: Epoch,
+        opt_original_caller: OptionalValue<ManagedAddress>,
+    ) -> EnterFarmResultType<Self::Api> {
+        require!(lock_epochs > 0, "Invalid lock epochs");
+        let reward_multiplier = self.get_tier_reward_multiplier(lock_epochs);
+
+        let caller = self.blockchain().get_caller();
+        let original_caller = self.get_orig_caller_from_opt(&caller, opt_original_caller);
+        let payment = self.call_value().single_esdt();
+
+        let mut storage_cache = StorageCache::new(self);
+        self.validate_contract_state(storage_cache.contract_state, &storage_cache.farm_token_id);
+
+        let enter_farm_context = EnterFarmContext::new(
+            ManagedVec::from_single_item(payment),
+            &storage_cache.farming_token_id,
+            &storage_cache.farm_token_id,
+        );
+        let staked_amount = enter_farm_context.farming_token_payment.amount.clone();
+
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        storage_cache.farm_token_supply += &staked_amount;
+        self.add_extra_reward_weight(&staked_amount, reward_multiplier);
+
+        let base_attributes = FarmStakingWrapper::<Self>::create_enter_farm_initial_attributes(
+            self,
+            original_caller,
+            staked_amount.clone(),
+            storage_cache.reward_per_share.clone(),
+        );
+        let attributes = StakingFarmTokenAttributes {
+            unlock_epoch: self.blockchain().get_block_epoch() + lock_epochs,
+            reward_multiplier,
+            ..base_attributes
+        };
+        let new_farm_token = PaymentAttributesPair {
+            payment: self.farm_token().nft_create(staked_amount, &attributes),
+            attributes,
+        };
+
+        let output_payment = new_farm_token.payment.clone();
+        self.send_payment_non_zero(&caller, &output_payment);
+
+        self.set_farm_supply_for_current_week(&storage_cache.farm_token_supply);
+
+        self.emit_enter_farm_event(
+            &caller,
+            enter_farm_context.farming_token_payment,
+            new_farm_token,
+            false,
+            storage_cache,
+        );
+
+        let boosted_rewards =
+            EsdtTokenPayment::new(self.reward_token_id().get(), 0, BigUint::zero());
+
+        (output_payment, boosted_rewards).into()
+    }
+
+    /// Burns the given unbond SFTs, and re-stakes their tokens as a new farm position
+    #[payable("*")]
+    #[endpoint(cancelUnbond)]
+    fn cancel_unbond(&self) -> EnterFarmResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let unbond_payments = self.get_non_empty_payments();
+        let farm_token_mapper = self.farm_token();
+
+        let mut total_unbond_amount = BigUint::zero();
+        for payment in &unbond_payments {
+            farm_token_mapper.require_same_token(&payment.token_identifier);
+
+            let _: UnbondSftAttributes =
+                farm_token_mapper.get_token_attributes(payment.token_nonce);
+            farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+            total_unbond_amount += payment.amount;
+        }
+
+        let staked_token_id = self.farming_token_id().get();
+        let staked_token_simulated_payment =
+            EsdtTokenPayment::new(staked_token_id, 0, total_unbond_amount);
+
+        self.stake_farm_common(
+            caller,
+            ManagedVec::from_single_item(staked_token_simulated_payment),
+        )
+    }
+
     fn stake_farm_common(
         &self,
         original_caller: ManagedAddress,
@@ -0,0 +1,116 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::Epoch;
+use math::safe_sub;
+
+use crate::{custom_rewards::MAX_PERCENT, token_attributes::DEFAULT_REWARD_MULTIPLIER};
+
+pub const MAX_STAKING_TIERS: usize = 10;
+pub const MAX_TIER_LOCK_EPOCHS: Epoch = 1_440;
+pub const MAX_REWARD_MULTIPLIER: u64 = 10 * MAX_PERCENT;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, Default)]
+pub struct StakingTier {
+    pub lock_epochs: Epoch,
+    pub reward_multiplier: u64,
+}
+
+pub type AllStakingTiers = ArrayVec<StakingTier, MAX_STAKING_TIERS>;
+
+/// Positions staked for a fixed term earn rewards based on their farm amount
+/// scaled by the tier's reward multiplier.
+///
+/// The extra weight of all such positions is kept separately from the farm token supply,
+/// so rewards are split over `farm_token_supply + total_extra_reward_weight`.
+#[multiversx_sc::module]
+pub trait StakingTiersModule: permissions_module::PermissionsModule {
+    /// Pairs of (lock_epochs, reward_multiplier), replacing the existing tiers.
+    /// The multiplier uses MAX_PERCENT precision, i.e. 15_000 means 1.5x rewards.
+    ///
+    /// Lock epochs must be strictly increasing, and multipliers must be increasing as well.
+    #[endpoint(setStakingTiers)]
+    fn set_staking_tiers(&self, tiers: MultiValueEncoded<MultiValue2<Epoch, u64>>) {
+        self.require_caller_has_admin_permissions();
+        require!(tiers.len() <= MAX_STAKING_TIERS, "Too many tiers");
+
+        let mut all_tiers = AllStakingTiers::new();
+        let mut prev_lock_epochs = 0;
+        let mut prev_reward_multiplier = DEFAULT_REWARD_MULTIPLIER;
+        for pair in tiers {
+            let (lock_epochs, reward_multiplier) = pair.into_tuple();
+            require!(
+                lock_epochs > prev_lock_epochs && lock_epochs <= MAX_TIER_LOCK_EPOCHS,
+                "Invalid lock epochs"
+            );
+            require!(
+                reward_multiplier > prev_reward_multiplier
+                    && reward_multiplier <= MAX_REWARD_MULTIPLIER,
+                "Invalid reward multiplier"
+            );
+
+            unsafe {
+                all_tiers.push_unchecked(StakingTier {
+                    lock_epochs,
+                    reward_multiplier,
+                });
+            }
+
+            prev_lock_epochs = lock_epochs;
+            prev_reward_multiplier = reward_multiplier;
+        }
+
+        self.staking_tiers().set(&all_tiers);
+    }
+
+    fn get_tier_reward_multiplier(&self, lock_epochs: Epoch) -> u64 {
+        if lock_epochs == 0 {
+            return DEFAULT_REWARD_MULTIPLIER;
+        }
+
+        let tiers = self.staking_tiers().get();
+        for tier in tiers.iter() {
+            if tier.lock_epochs == lock_epochs {
+                return tier.reward_multiplier;
+            }
+        }
+
+        sc_panic!("Invalid lock epochs");
+    }
+
+    fn get_extra_reward_weight(&self, farm_amount: &BigUint, reward_multiplier: u64) -> BigUint {
+        farm_amount * (reward_multiplier - DEFAULT_REWARD_MULTIPLIER) / MAX_PERCENT
+    }
+
+    fn add_extra_reward_weight(&self, farm_amount: &BigUint, reward_multiplier: u64) {
+        if reward_multiplier == DEFAULT_REWARD_MULTIPLIER {
+            return;
+        }
+
+        let extra_weight = self.get_extra_reward_weight(farm_amount, reward_multiplier);
+        self.total_extra_reward_weight()
+            .update(|total| *total += extra_weight);
+    }
+
+    fn remove_extra_reward_weight(&self, farm_amount: &BigUint, reward_multiplier: u64) {
+        if reward_multiplier == DEFAULT_REWARD_MULTIPLIER {
+            return;
+        }
+
+        let extra_weight = self.get_extra_reward_weight(farm_amount, reward_multiplier);
+        self.total_extra_reward_weight()
+            .update(|total| *total = safe_sub(total.clone(), extra_weight));
+    }
+
+    fn get_weighted_farm_supply(&self, farm_token_supply: &BigUint) -> BigUint {
+        farm_token_supply + &self.total_extra_reward_weight().get()
+    }
+
+    #[view(getStakingTiers)]
+    #[storage_mapper("stakingTiers")]
+    fn staking_tiers(&self) -> SingleValueMapper<AllStakingTiers>;
+
+    #[view(getTotalExtraRewardWeight)]
+    #[storage_mapper("totalExtraRewardWeight")]
+    fn total_extra_reward_weight(&self) -> SingleValueMapper<BigUint>;
+}
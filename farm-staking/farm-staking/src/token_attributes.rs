@@ -1,20 +1,33 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::{FarmToken, FarmTokenAttributes};
+use common_structs::{Epoch, FarmToken, FarmTokenAttributes};
 use fixed_supply_token::FixedSupplyToken;
 use math::weighted_average_round_up;
 use mergeable::Mergeable;
 use multiversx_sc::codec::{NestedDecodeInput, TopDecodeInput};
 
+use crate::custom_rewards::MAX_PERCENT;
+
 static NOT_IMPLEMENTED_ERR_MSG: &[u8] = b"Conversion not implemented";
 
+pub const DEFAULT_REWARD_MULTIPLIER: u64 = MAX_PERCENT;
+
 #[derive(ManagedVecItem, TopEncode, NestedEncode, TypeAbi, Clone, PartialEq, Debug)]
 pub struct StakingFarmTokenAttributes<M: ManagedTypeApi> {
     pub reward_per_share: BigUint<M>,
     pub compounded_reward: BigUint<M>,
     pub current_farm_amount: BigUint<M>,
     pub original_owner: ManagedAddress<M>,
+    pub unlock_epoch: Epoch,
+    pub reward_multiplier: u64,
+}
+
+impl<M: ManagedTypeApi> StakingFarmTokenAttributes<M> {
+    /// The farm amount, scaled by the staking tier reward multiplier
+    pub fn get_reward_weight(&self, farm_token_amount: &BigUint<M>) -> BigUint<M> {
+        farm_token_amount * self.reward_multiplier / MAX_PERCENT
+    }
 }
 
 impl<M: ManagedTypeApi> TopDecode for StakingFarmTokenAttributes<M> {
@@ -39,6 +52,12 @@ impl<M: ManagedTypeApi> NestedDecode for StakingFarmTokenAttributes<M> {
             ManagedAddress::zero()
         };
 
+        let (unlock_epoch, reward_multiplier) = if !input.is_depleted() {
+            (Epoch::dep_decode(input)?, u64::dep_decode(input)?)
+        } else {
+            (0, DEFAULT_REWARD_MULTIPLIER)
+        };
+
         if !input.is_depleted() {
             return Result::Err(DecodeError::INPUT_TOO_LONG);
         }
@@ -48,6 +67,8 @@ impl<M: ManagedTypeApi> NestedDecode for StakingFarmTokenAttributes<M> {
             compounded_reward,
             current_farm_amount,
             original_owner,
+            unlock_epoch,
+            reward_multiplier,
         })
     }
 }
@@ -102,14 +123,25 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for StakingFarmTokenAttributes<M> {
             compounded_reward: new_compounded_reward,
             current_farm_amount: new_current_farm_amount,
             original_owner: self.original_owner,
+            unlock_epoch: self.unlock_epoch,
+            reward_multiplier: self.reward_multiplier,
         }
     }
+
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self {
+        let part = self.clone().into_part(payment_amount);
+        self.compounded_reward -= &part.compounded_reward;
+        self.current_farm_amount -= &part.current_farm_amount;
+
+        part
+    }
 }
 
 impl<M: ManagedTypeApi> Mergeable<M> for StakingFarmTokenAttributes<M> {
     #[inline]
     fn can_merge_with(&self, other: &Self) -> bool {
         self.original_owner == other.original_owner
+            && self.reward_multiplier == other.reward_multiplier
     }
 
     fn merge_with(&mut self, other: Self) {
@@ -126,6 +158,8 @@ impl<M: ManagedTypeApi> Mergeable<M> for StakingFarmTokenAttributes<M> {
 
         self.compounded_reward += other.compounded_reward;
         self.current_farm_amount += other.current_farm_amount;
+
+        self.unlock_epoch = core::cmp::max(self.unlock_epoch, other.unlock_epoch);
     }
 }
 
@@ -1,8 +1,22 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
+use common_errors::ERROR_PARAMETERS;
+use common_structs::Epoch;
 use contexts::storage_cache::StorageCache;
+use math::linear_interpolation;
 
-use crate::token_attributes::UnbondSftAttributes;
+use crate::{custom_rewards::MAX_PERCENT, token_attributes::UnbondSftAttributes};
+
+/// RewardReserve: the penalty is added to the reward capacity,
+/// i.e. it will be distributed to the stakers as future rewards.
+///
+/// FeesCollector: the penalty is deposited into the fees collector.
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum EarlyUnbondPenaltyDestination {
+    RewardReserve,
+    FeesCollector,
+}
 
 #[multiversx_sc::module]
 pub trait UnbondFarmModule:
@@ -28,6 +42,7 @@ pub trait UnbondFarmModule:
     + weekly_rewards_splitting::locked_token_buckets::WeeklyRewardsLockedTokenBucketsModule
     + weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule
     + energy_query::EnergyQueryModule
+    + farm::fees_collector::FeesCollectorModule
 {
     #[payable("*")]
     #[endpoint(unbondFarm)]
@@ -57,4 +72,125 @@ pub trait UnbondFarmModule:
 
         farming_tokens
     }
+
+    /// Unbonds before `unlock_epoch`, paying a penalty.
+    /// The penalty starts at the max early unbond penalty percent for a full unbond period,
+    /// and decreases linearly with the remaining epochs.
+    #[payable("*")]
+    #[endpoint(unbondEarly)]
+    fn unbond_early(&self) -> EsdtTokenPayment {
+        let storage_cache = StorageCache::new(self);
+        self.validate_contract_state(storage_cache.contract_state, &storage_cache.farm_token_id);
+
+        let max_penalty_percent = self.early_unbond_max_penalty_percent().get();
+        require!(max_penalty_percent > 0, "Early unbond is not enabled");
+
+        let farm_token_mapper = self.farm_token();
+        let payment = self.call_value().single_esdt();
+        farm_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: UnbondSftAttributes =
+            farm_token_mapper.get_token_attributes(payment.token_nonce);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch < attributes.unlock_epoch,
+            "Unbond period is over, use unbondFarm instead"
+        );
+
+        farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let remaining_epochs = attributes.unlock_epoch - current_epoch;
+        let penalty_amount =
+            self.get_early_unbond_penalty_amount(&payment.amount, remaining_epochs);
+        self.send_early_unbond_penalty(&storage_cache.farming_token_id, &penalty_amount);
+
+        let caller = self.blockchain().get_caller();
+        let farming_tokens = EsdtTokenPayment::new(
+            storage_cache.farming_token_id.clone(),
+            0,
+            payment.amount - penalty_amount,
+        );
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        farming_tokens
+    }
+
+    /// Zero disables early unbonding
+    #[endpoint(setEarlyUnbondMaxPenaltyPercent)]
+    fn set_early_unbond_max_penalty_percent(&self, max_penalty_percent: u64) {
+        self.require_caller_has_admin_permissions();
+        require!(max_penalty_percent <= MAX_PERCENT, ERROR_PARAMETERS);
+
+        self.early_unbond_max_penalty_percent()
+            .set(max_penalty_percent);
+    }
+
+    #[only_owner]
+    #[endpoint(setEarlyUnbondPenaltyDestination)]
+    fn set_early_unbond_penalty_destination(&self, destination: EarlyUnbondPenaltyDestination) {
+        if destination == EarlyUnbondPenaltyDestination::FeesCollector {
+            self.require_fees_collector_accepts(&self.farming_token_id().get());
+        }
+
+        self.early_unbond_penalty_destination().set(destination);
+    }
+
+    #[view(getEarlyUnbondPenalty)]
+    fn get_early_unbond_penalty(&self, amount: BigUint, unlock_epoch: Epoch) -> BigUint {
+        let current_epoch = self.blockchain().get_block_epoch();
+        if current_epoch >= unlock_epoch {
+            return BigUint::zero();
+        }
+
+        self.get_early_unbond_penalty_amount(&amount, unlock_epoch - current_epoch)
+    }
+
+    fn get_early_unbond_penalty_amount(
+        &self,
+        amount: &BigUint,
+        remaining_epochs: Epoch,
+    ) -> BigUint {
+        if remaining_epochs == 0 {
+            return BigUint::zero();
+        }
+
+        // positions unstaked before min_unbond_epochs was lowered may have more epochs remaining
+        let min_unbond_epochs = self.min_unbond_epochs().get();
+        let max_epochs = core::cmp::max(min_unbond_epochs, remaining_epochs);
+        let max_penalty_percent = self.early_unbond_max_penalty_percent().get();
+        let penalty_percent = linear_interpolation::<Self::Api, _>(
+            0,
+            max_epochs,
+            remaining_epochs,
+            0,
+            max_penalty_percent,
+        );
+
+        amount * penalty_percent / MAX_PERCENT
+    }
+
+    fn send_early_unbond_penalty(&self, farming_token_id: &TokenIdentifier, amount: &BigUint) {
+        if amount == &0 {
+            return;
+        }
+
+        match self.early_unbond_penalty_destination().get() {
+            EarlyUnbondPenaltyDestination::RewardReserve => {
+                // farming token and reward token are the same
+                self.reward_capacity().update(|r| *r += amount);
+            }
+            EarlyUnbondPenaltyDestination::FeesCollector => {
+                self.send_to_fees_collector(farming_token_id, amount);
+            }
+        }
+    }
+
+    #[view(getEarlyUnbondMaxPenaltyPercent)]
+    #[storage_mapper("earlyUnbondMaxPenaltyPercent")]
+    fn early_unbond_max_penalty_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getEarlyUnbondPenaltyDestination)]
+    #[storage_mapper("earlyUnbondPenaltyDestination")]
+    fn early_unbond_penalty_destination(&self) -> SingleValueMapper<EarlyUnbondPenaltyDestination>;
 }
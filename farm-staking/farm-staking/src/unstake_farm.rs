@@ -3,11 +3,15 @@ multiversx_sc::imports!();
 use farm::ExitFarmWithPartialPosResultType;
 use mergeable::Mergeable;
 
-use crate::{base_impl_wrapper::FarmStakingWrapper, token_attributes::UnbondSftAttributes};
+use crate::{
+    base_impl_wrapper::FarmStakingWrapper,
+    token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes},
+};
 
 #[multiversx_sc::module]
 pub trait UnstakeFarmModule:
     crate::custom_rewards::CustomRewardsModule
+    + crate::staking_tiers::StakingTiersModule
     + crate::claim_only_boosted_staking_rewards::ClaimOnlyBoostedStakingRewardsModule
     + rewards::RewardsModule
     + config::ConfigModule
@@ -86,6 +90,17 @@ pub trait UnstakeFarmModule:
             "Exit amount is bigger than the payment amount"
         );
 
+        let farm_token_mapper = self.farm_token();
+        farm_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: StakingFarmTokenAttributes<Self::Api> =
+            farm_token_mapper.get_token_attributes(payment.token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch >= attributes.unlock_epoch,
+            "Staking position is still locked"
+        );
+
         let boosted_rewards_full_position =
             self.claim_only_boosted_payment(&original_caller, &payment);
         let remaining_farm_payment = EsdtTokenPayment::new(
@@ -98,6 +113,7 @@ pub trait UnstakeFarmModule:
 
         let mut exit_result =
             self.exit_farm_base::<FarmStakingWrapper<Self>>(original_caller.clone(), payment);
+        self.remove_extra_reward_weight(&exit_amount, attributes.reward_multiplier);
         exit_result
             .reward_payment
             .merge_with(boosted_rewards_full_position);
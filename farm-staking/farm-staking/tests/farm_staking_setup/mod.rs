@@ -16,7 +16,9 @@ use farm_boosted_yields::FarmBoostedYieldsModule;
 use farm_staking::claim_stake_farm_rewards::ClaimStakeFarmRewardsModule;
 use farm_staking::custom_rewards::CustomRewardsModule;
 use farm_staking::stake_farm::StakeFarmModule;
-use farm_staking::token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes};
+use farm_staking::token_attributes::{
+    StakingFarmTokenAttributes, UnbondSftAttributes, DEFAULT_REWARD_MULTIPLIER,
+};
 use farm_staking::unbond_farm::UnbondFarmModule;
 use farm_staking::unstake_farm::UnstakeFarmModule;
 use farm_staking::*;
@@ -196,6 +198,8 @@ where
             compounded_reward: managed_biguint!(expected_compounded_reward),
             current_farm_amount: managed_biguint!(expected_total_out_amount),
             original_owner: managed_address!(&self.user_address),
+            unlock_epoch: 0,
+            reward_multiplier: DEFAULT_REWARD_MULTIPLIER,
         };
         self.b_mock.check_nft_balance(
             &self.user_address,
@@ -253,6 +257,8 @@ where
             compounded_reward: managed_biguint!(0),
             current_farm_amount: managed_biguint!(farm_token_amount),
             original_owner: managed_address!(&self.user_address),
+            unlock_epoch: 0,
+            reward_multiplier: DEFAULT_REWARD_MULTIPLIER,
         };
 
         self.b_mock.check_nft_balance(
@@ -1,11 +1,26 @@
-use multiversx_sc_scenario::{rust_biguint, whitebox::TxTokenTransfer, DebugApi};
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::types::MultiValueEncoded;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, rust_biguint, whitebox::TxTokenTransfer, DebugApi,
+};
 
 pub mod farm_staking_setup;
+use farm::fees_collector::FeesCollectorModule;
 use farm_staking::{
-    custom_rewards::{BLOCKS_IN_YEAR, MAX_PERCENT},
-    token_attributes::UnbondSftAttributes,
+    claim_stake_farm_rewards::ClaimStakeFarmRewardsModule,
+    compound_stake_farm_rewards::CompoundStakeFarmRewardsModule,
+    custom_rewards::{CustomRewardsModule, BLOCKS_IN_EPOCH, BLOCKS_IN_YEAR, MAX_PERCENT},
+    stake_farm::StakeFarmModule,
+    staking_tiers::StakingTiersModule,
+    token_attributes::{
+        StakingFarmTokenAttributes, UnbondSftAttributes, DEFAULT_REWARD_MULTIPLIER,
+    },
+    unbond_farm::{EarlyUnbondPenaltyDestination, UnbondFarmModule},
+    unstake_farm::UnstakeFarmModule,
+    FarmStaking,
 };
 use farm_staking_setup::*;
+use weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule;
 
 #[test]
 fn test_farm_setup() {
@@ -97,6 +112,157 @@ fn test_claim_rewards() {
     farm_setup.check_farm_token_supply(farm_in_amount);
 }
 
+#[test]
+fn test_split_farm_token() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    // farming tokens cannot be split
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let mut split_amounts = MultiValueEncoded::new();
+                split_amounts.push(managed_biguint!(400));
+                split_amounts.push(managed_biguint!(600));
+
+                let _ = sc.split_farm_token_endpoint(split_amounts);
+            },
+        )
+        .assert_user_error("Invalid token ID");
+
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let mut split_amounts = MultiValueEncoded::new();
+                split_amounts.push(managed_biguint!(40_000_000));
+                split_amounts.push(managed_biguint!(60_000_000));
+
+                let new_tokens = sc.split_farm_token_endpoint(split_amounts).to_vec();
+                assert_eq!(new_tokens.len(), 2);
+            },
+        )
+        .assert_ok();
+
+    for (nonce, amount) in [(2, 40_000_000u64), (3, 60_000_000u64)] {
+        let expected_attributes = StakingFarmTokenAttributes::<DebugApi> {
+            reward_per_share: managed_biguint!(0),
+            compounded_reward: managed_biguint!(0),
+            current_farm_amount: managed_biguint!(amount),
+            original_owner: managed_address!(&farm_setup.user_address),
+            unlock_epoch: 0,
+            reward_multiplier: DEFAULT_REWARD_MULTIPLIER,
+        };
+        farm_setup.b_mock.check_nft_balance(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            nonce,
+            &rust_biguint!(amount),
+            Some(&expected_attributes),
+        );
+    }
+    farm_setup.check_farm_token_supply(farm_in_amount);
+}
+
+#[test]
+fn test_split_farm_token_uneven_parts() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.stake_farm(farm_in_amount, &[], 1, 0, 0);
+
+    // 40 rewards are compounded into the position
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(10);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.compound_rewards();
+            },
+        )
+        .assert_ok();
+
+    let compounded_reward = 40u64;
+    let total_amount = farm_in_amount + compounded_reward;
+    let attributes: StakingFarmTokenAttributes<DebugApi> = farm_setup
+        .b_mock
+        .get_nft_attributes(&farm_setup.user_address, FARM_TOKEN_ID, 2)
+        .unwrap();
+    assert_eq!(
+        attributes.compounded_reward,
+        managed_biguint!(compounded_reward)
+    );
+    assert_eq!(
+        attributes.current_farm_amount,
+        managed_biguint!(total_amount)
+    );
+
+    // the parts of the compounded reward are rounded down, and the last part gets the rest
+    let split_amounts = [33_333_347u64, 33_333_347u64, 33_333_346u64];
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(total_amount),
+            |sc| {
+                let mut split_amounts_arg = MultiValueEncoded::new();
+                for amount in split_amounts {
+                    split_amounts_arg.push(managed_biguint!(amount));
+                }
+
+                let new_tokens = sc.split_farm_token_endpoint(split_amounts_arg).to_vec();
+                assert_eq!(new_tokens.len(), split_amounts.len());
+            },
+        )
+        .assert_ok();
+
+    let expected_compounded_rewards = [13u64, 13u64, 14u64];
+    for (i, amount) in split_amounts.iter().enumerate() {
+        let nonce = 3 + i as u64;
+        let attributes: StakingFarmTokenAttributes<DebugApi> = farm_setup
+            .b_mock
+            .get_nft_attributes(&farm_setup.user_address, FARM_TOKEN_ID, nonce)
+            .unwrap();
+        assert_eq!(attributes.current_farm_amount, managed_biguint!(*amount));
+        assert_eq!(
+            attributes.compounded_reward,
+            managed_biguint!(expected_compounded_rewards[i])
+        );
+    }
+    assert_eq!(
+        expected_compounded_rewards.iter().sum::<u64>(),
+        compounded_reward
+    );
+    farm_setup.check_farm_token_supply(total_amount);
+}
+
 fn steps_enter_farm_twice<FarmObjBuilder, EnergyFactoryBuilder>(
     farm_builder: FarmObjBuilder,
     energy_factory_builder: EnergyFactoryBuilder,
@@ -229,3 +395,693 @@ fn test_unbond() {
         USER_TOTAL_RIDE_TOKENS + expected_rewards,
     );
 }
+
+#[test]
+fn test_stake_farm_locked() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let lock_epochs = 10;
+    let reward_multiplier = 2 * MAX_PERCENT;
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut tiers = MultiValueEncoded::new();
+                tiers.push((lock_epochs, reward_multiplier).into());
+                sc.set_staking_tiers(tiers);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_locked_endpoint(lock_epochs, OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    // tier not configured
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_locked_endpoint(lock_epochs + 1, OptionalValue::None);
+            },
+        )
+        .assert_user_error("Invalid lock epochs");
+
+    let expected_attributes = StakingFarmTokenAttributes::<DebugApi> {
+        reward_per_share: managed_biguint!(0),
+        compounded_reward: managed_biguint!(0),
+        current_farm_amount: managed_biguint!(farm_in_amount),
+        original_owner: managed_address!(&farm_setup.user_address),
+        unlock_epoch: lock_epochs,
+        reward_multiplier,
+    };
+    farm_setup.b_mock.check_nft_balance(
+        &farm_setup.user_address,
+        FARM_TOKEN_ID,
+        expected_farm_token_nonce,
+        &rust_biguint!(farm_in_amount),
+        Some(&expected_attributes),
+    );
+    farm_setup.check_farm_token_supply(farm_in_amount);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.total_extra_reward_weight().get(),
+                managed_biguint!(farm_in_amount)
+            );
+        })
+        .assert_ok();
+
+    // position still locked
+    farm_setup.set_block_epoch(lock_epochs - 1);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm(managed_biguint!(farm_in_amount), OptionalValue::None);
+            },
+        )
+        .assert_user_error("Staking position is still locked");
+
+    farm_setup.set_block_epoch(lock_epochs);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm(managed_biguint!(farm_in_amount), OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    farm_setup.check_farm_token_supply(0);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.total_extra_reward_weight().get(), managed_biguint!(0));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_stake_farm_locked_multiplier_expires() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let lock_epochs = 10;
+    let reward_multiplier = 2 * MAX_PERCENT;
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut tiers = MultiValueEncoded::new();
+                tiers.push((lock_epochs, reward_multiplier).into());
+                sc.set_staking_tiers(tiers);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_locked_endpoint(lock_epochs, OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    // claiming before the lock expires keeps the multiplier
+    farm_setup.set_block_epoch(lock_epochs - 1);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    let mut expected_attributes = StakingFarmTokenAttributes::<DebugApi> {
+        reward_per_share: managed_biguint!(0),
+        compounded_reward: managed_biguint!(0),
+        current_farm_amount: managed_biguint!(farm_in_amount),
+        original_owner: managed_address!(&farm_setup.user_address),
+        unlock_epoch: lock_epochs,
+        reward_multiplier,
+    };
+    farm_setup.b_mock.check_nft_balance(
+        &farm_setup.user_address,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        Some(&expected_attributes),
+    );
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.total_extra_reward_weight().get(),
+                managed_biguint!(farm_in_amount)
+            );
+        })
+        .assert_ok();
+
+    // the first claim after the lock expires resets the multiplier
+    farm_setup.set_block_epoch(lock_epochs);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.claim_rewards(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    expected_attributes.reward_multiplier = DEFAULT_REWARD_MULTIPLIER;
+    farm_setup.b_mock.check_nft_balance(
+        &farm_setup.user_address,
+        FARM_TOKEN_ID,
+        3,
+        &rust_biguint!(farm_in_amount),
+        Some(&expected_attributes),
+    );
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.total_extra_reward_weight().get(), managed_biguint!(0));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_early_unbond_penalty_destination() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_early_unbond_penalty_destination(
+                    EarlyUnbondPenaltyDestination::FeesCollector,
+                );
+            },
+        )
+        .assert_user_error("Fees collector address not set");
+
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_fees_collector_address(managed_address!(&farm_setup.user_address));
+            },
+        )
+        .assert_user_error("Invalid fees collector address");
+}
+
+#[test]
+fn test_unbond_early() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    let current_block = 10;
+    let current_epoch = 5;
+    farm_setup.set_block_epoch(current_epoch);
+    farm_setup.set_block_nonce(current_block);
+
+    let expected_rewards = 40;
+    let expected_ride_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount + expected_rewards;
+    farm_setup.unstake_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_rewards,
+        &expected_ride_token_balance,
+        &expected_ride_token_balance,
+        expected_farm_token_nonce + 1,
+        farm_in_amount,
+        &UnbondSftAttributes {
+            unlock_epoch: current_epoch + MIN_UNBOND_EPOCHS,
+        },
+    );
+
+    // early unbond not enabled yet
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce + 1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unbond_early();
+            },
+        )
+        .assert_user_error("Early unbond is not enabled");
+
+    let max_penalty_percent = 1_000; // 10%
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_early_unbond_max_penalty_percent(max_penalty_percent);
+            },
+        )
+        .assert_ok();
+
+    // 3 out of 5 epochs remaining => 6% penalty
+    farm_setup.set_block_epoch(current_epoch + 2);
+    let expected_penalty = farm_in_amount * 600 / MAX_PERCENT;
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce + 1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let farming_tokens = sc.unbond_early();
+                assert_eq!(
+                    farming_tokens.amount,
+                    managed_biguint!(farm_in_amount - expected_penalty)
+                );
+            },
+        )
+        .assert_ok();
+
+    farm_setup.b_mock.check_esdt_balance(
+        &farm_setup.user_address,
+        FARMING_TOKEN_ID,
+        &(rust_biguint!(USER_TOTAL_RIDE_TOKENS) + expected_rewards - expected_penalty),
+    );
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.reward_capacity().get(),
+                managed_biguint!(TOTAL_REWARDS_AMOUNT + expected_penalty)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_cancel_unbond() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.stake_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0);
+
+    let current_block = 10;
+    let current_epoch = 5;
+    farm_setup.set_block_epoch(current_epoch);
+    farm_setup.set_block_nonce(current_block);
+
+    let expected_rewards = 40;
+    let expected_ride_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS) - farm_in_amount + expected_rewards;
+    farm_setup.unstake_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_rewards,
+        &expected_ride_token_balance,
+        &expected_ride_token_balance,
+        expected_farm_token_nonce + 1,
+        farm_in_amount,
+        &UnbondSftAttributes {
+            unlock_epoch: current_epoch + MIN_UNBOND_EPOCHS,
+        },
+    );
+    farm_setup.check_farm_token_supply(0);
+
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce + 1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, _) = sc.cancel_unbond().into_tuple();
+                assert_eq!(new_farm_token.token_nonce, expected_farm_token_nonce + 2);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    farm_setup.check_farm_token_supply(farm_in_amount);
+    farm_setup
+        .b_mock
+        .check_nft_balance::<StakingFarmTokenAttributes<DebugApi>>(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce + 1,
+            &rust_biguint!(0),
+            None,
+        );
+    farm_setup.b_mock.check_esdt_balance(
+        &farm_setup.user_address,
+        FARMING_TOKEN_ID,
+        &expected_ride_token_balance,
+    );
+}
+
+#[test]
+fn test_apr_curve() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let target_staked_supply = 100_000_000;
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_apr_curve(
+                    managed_biguint!(target_staked_supply),
+                    MultiValueEncoded::new(),
+                );
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut points = MultiValueEncoded::new();
+                points.push((5_000u64, 5_000u64).into());
+                points.push((10_000u64, 2_500u64).into());
+                points.push((20_000u64, 1_000u64).into());
+                sc.set_apr_curve(managed_biguint!(target_staked_supply), points);
+            },
+        )
+        .assert_ok();
+
+    // nothing staked yet, cap is the first point's APR
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.get_effective_max_apr(), managed_biguint!(5_000));
+        })
+        .assert_ok();
+
+    farm_setup.stake_farm(75_000_000, &[], 1, 0, 0);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.get_effective_max_apr(), managed_biguint!(3_750));
+        })
+        .assert_ok();
+
+    farm_setup.stake_farm(225_000_000, &[], 2, 0, 0);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.get_effective_max_apr(), managed_biguint!(1_000));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_reward_runway() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.stake_farm(farm_in_amount, &[], 1, 0, 0);
+
+    // ~= 4 per block, APR bounded
+    let expected_rewards_per_block = farm_in_amount * MAX_APR / MAX_PERCENT / BLOCKS_IN_YEAR;
+    assert_eq!(expected_rewards_per_block, 4);
+
+    farm_setup.set_block_nonce(10);
+    let expected_remaining_blocks =
+        (TOTAL_REWARDS_AMOUNT - 10 * expected_rewards_per_block) / expected_rewards_per_block;
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let (rewards_per_block, remaining_blocks, remaining_epochs) =
+                sc.get_reward_runway().into_tuple();
+            assert_eq!(
+                rewards_per_block,
+                managed_biguint!(expected_rewards_per_block)
+            );
+            assert_eq!(
+                remaining_blocks,
+                managed_biguint!(expected_remaining_blocks)
+            );
+            assert_eq!(
+                remaining_epochs,
+                managed_biguint!(expected_remaining_blocks / BLOCKS_IN_EPOCH)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_low_reward_capacity_event() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let threshold = TOTAL_REWARDS_AMOUNT - 20;
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_low_reward_capacity_threshold(managed_biguint!(threshold));
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.stake_farm(farm_in_amount, &[], 1, 0, 0);
+
+    // 10 blocks * 4 rewards per block take the remaining capacity below the threshold
+    farm_setup.set_block_nonce(10);
+    let tx_result = farm_setup.b_mock.execute_esdt_transfer(
+        &farm_setup.user_address,
+        &farm_setup.farm_wrapper,
+        FARM_TOKEN_ID,
+        1,
+        &rust_biguint!(farm_in_amount),
+        |sc| {
+            let _ = sc.claim_rewards(OptionalValue::None);
+        },
+    );
+    tx_result.assert_ok();
+    assert!(tx_result
+        .result_logs
+        .iter()
+        .any(|log| log.topics[0] == b"low_reward_capacity".to_vec()));
+
+    // the event is only emitted once, when the threshold is crossed
+    farm_setup.set_block_nonce(20);
+    let tx_result = farm_setup.b_mock.execute_esdt_transfer(
+        &farm_setup.user_address,
+        &farm_setup.farm_wrapper,
+        FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(farm_in_amount),
+        |sc| {
+            let _ = sc.claim_rewards(OptionalValue::None);
+        },
+    );
+    tx_result.assert_ok();
+    assert!(!tx_result
+        .result_logs
+        .iter()
+        .any(|log| log.topics[0] == b"low_reward_capacity".to_vec()));
+}
+
+#[test]
+fn test_stake_farm_for() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let beneficiary = farm_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let farm_in_amount = 100_000_000;
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, _) = sc
+                    .stake_farm_for(managed_address!(&beneficiary))
+                    .into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 1);
+                assert_eq!(new_farm_token.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    let expected_attributes = StakingFarmTokenAttributes::<DebugApi> {
+        reward_per_share: managed_biguint!(0),
+        compounded_reward: managed_biguint!(0),
+        current_farm_amount: managed_biguint!(farm_in_amount),
+        original_owner: managed_address!(&beneficiary),
+        unlock_epoch: 0,
+        reward_multiplier: DEFAULT_REWARD_MULTIPLIER,
+    };
+    farm_setup.b_mock.check_nft_balance(
+        &beneficiary,
+        FARM_TOKEN_ID,
+        1,
+        &rust_biguint!(farm_in_amount),
+        Some(&expected_attributes),
+    );
+    farm_setup
+        .b_mock
+        .check_nft_balance::<StakingFarmTokenAttributes<DebugApi>>(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(0),
+            None,
+        );
+    farm_setup.check_farm_token_supply(farm_in_amount);
+}
+
+#[test]
+fn test_stake_farm_for_keeps_beneficiary_progress() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let beneficiary = farm_setup.user_address.clone();
+    let staker = farm_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let farm_in_amount = 1_000;
+    farm_setup
+        .b_mock
+        .set_esdt_balance(&staker, FARMING_TOKEN_ID, &rust_biguint!(farm_in_amount));
+
+    // the beneficiary's claim progress is registered in the first week
+    farm_setup.set_user_energy(&beneficiary, 1_000, 0, 1);
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.update_energy_and_progress(&managed_address!(&beneficiary));
+            },
+        )
+        .assert_ok();
+
+    // staking for the beneficiary does not reset their progress for the past week
+    farm_setup.set_block_epoch(10);
+    farm_setup.set_user_energy(&beneficiary, 1_000, 10, 1);
+    farm_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &staker,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_for(managed_address!(&beneficiary));
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let claim_progress = sc
+                .current_claim_progress(&managed_address!(&beneficiary))
+                .get();
+            assert_eq!(claim_progress.week, 1);
+        })
+        .assert_ok();
+}
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           64
+// Endpoints:                           88
 // Async Callback:                       1
-// Total number of exported functions:  66
+// Total number of exported functions:  90
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -19,16 +19,23 @@ multiversx_sc_wasm_adapter::endpoints! {
     farm_staking
     (
         mergeFarmTokens
+        splitFarmToken
         calculateRewardsForGivenPosition
         topUpRewards
         endProduceRewards
         setPerBlockRewardAmount
         setMaxApr
+        setAprCurve
         setMinUnbondEpochs
+        getEffectiveMaxApr
+        getMaxAprForLockEpochs
+        setLowRewardCapacityThreshold
+        getRewardRunway
         startProduceRewards
         getAccumulatedRewards
         getRewardCapacity
         getAnnualPercentageRewards
+        getLowRewardCapacityThreshold
         getMinUnbondEpochs
         getRewardPerShare
         getRewardReserve
@@ -55,12 +62,23 @@ multiversx_sc_wasm_adapter::endpoints! {
         setBurnRoleForAddress
         stakeFarmThroughProxy
         stakeFarm
+        stakeFarmFor
+        stakeFarmLocked
+        cancelUnbond
         claimRewards
         claimRewardsWithNewValue
         compoundRewards
         unstakeFarm
         unstakeFarmThroughProxy
         unbondFarm
+        unbondEarly
+        setEarlyUnbondMaxPenaltyPercent
+        setEarlyUnbondPenaltyDestination
+        setFeesCollectorAddress
+        getEarlyUnbondPenalty
+        getEarlyUnbondMaxPenaltyPercent
+        getEarlyUnbondPenaltyDestination
+        getFeesCollectorAddress
         setBoostedYieldsRewardsPercentage
         collectUndistributedBoostedRewards
         getBoostedYieldsRewardsPercentage
@@ -79,9 +97,15 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTotalEnergyForWeek
         getTotalLockedTokensForWeek
         updateEnergyForUser
+        updateEnergyAfterDelegation
         getCurrentClaimProgress
         setEnergyFactoryAddress
         getEnergyFactoryAddress
+        setStakingTiers
+        getStakingTiers
+        getTotalExtraRewardWeight
+        getTargetStakedSupply
+        getAprCurvePoints
         callBack
     )
 }
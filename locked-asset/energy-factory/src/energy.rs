@@ -142,6 +142,37 @@ impl<M: ManagedTypeApi> Energy<M> {
         self.add_after_token_lock(token_amount, new_unlock_epoch, current_epoch);
     }
 
+    /// Tokens in perpetual lock mode keep a constant energy.
+    /// They are not part of the total locked tokens, which drive the energy decay.
+    pub fn add_after_perpetual_lock(&mut self, lock_amount: &BigUint<M>, lock_epochs: Epoch) {
+        let energy_added = lock_amount * lock_epochs;
+        self.amount += BigInt::from(energy_added);
+    }
+
+    pub fn remove_after_perpetual_unlock(
+        &mut self,
+        unlock_amount: &BigUint<M>,
+        lock_epochs: Epoch,
+    ) {
+        let energy_removed = unlock_amount * lock_epochs;
+        self.amount -= BigInt::from(energy_removed);
+    }
+
+    /// Adds another entry to this one. Both entries are expected to be depleted up to the same epoch.
+    pub fn add_entry(&mut self, other: &Self) {
+        self.amount += &other.amount;
+        self.total_locked_tokens += &other.total_locked_tokens;
+    }
+
+    /// Applies the change of a single entry to an entry aggregating multiple ones.
+    /// All entries are expected to be depleted up to the same epoch.
+    pub fn apply_entry_change(&mut self, old_entry: &Self, new_entry: &Self) {
+        self.amount += &new_entry.amount;
+        self.amount -= &old_entry.amount;
+        self.total_locked_tokens += &new_entry.total_locked_tokens;
+        self.total_locked_tokens -= &old_entry.total_locked_tokens;
+    }
+
     #[inline]
     pub fn get_last_update_epoch(&self) -> Epoch {
         self.last_update_epoch
@@ -167,7 +198,9 @@ impl<M: ManagedTypeApi> Energy<M> {
 }
 
 #[multiversx_sc::module]
-pub trait EnergyModule: crate::events::EventsModule {
+pub trait EnergyModule:
+    crate::energy_history::EnergyHistoryModule + crate::events::EventsModule
+{
     fn update_energy<T, F: FnOnce(&mut Energy<Self::Api>) -> T>(
         &self,
         user: &ManagedAddress,
@@ -181,11 +214,90 @@ pub trait EnergyModule: crate::events::EventsModule {
     }
 
     fn set_energy_entry(&self, user: &ManagedAddress, new_energy: Energy<Self::Api>) {
+        self.init_effective_energy_checkpoints(user);
+
         let prev_energy = self.get_updated_energy_entry_for_user(user);
         self.user_energy(user).set(&new_energy);
+        self.checkpoint_energy(user, &prev_energy, &new_energy);
+
+        let delegate_mapper = self.energy_delegate(user);
+        if delegate_mapper.is_empty() {
+            self.checkpoint_effective_energy(user);
+        } else {
+            let delegate = delegate_mapper.get();
+            self.update_delegated_energy(&delegate, &prev_energy, &new_energy);
+            self.checkpoint_effective_energy(&delegate);
+        }
+
         self.emit_energy_updated_event(user, prev_energy, new_energy);
     }
 
+    /// Applies the change of a delegator's entry to the energy delegated to the given address
+    fn update_delegated_energy(
+        &self,
+        delegate: &ManagedAddress,
+        prev_delegator_energy: &Energy<Self::Api>,
+        new_delegator_energy: &Energy<Self::Api>,
+    ) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut prev_entry = prev_delegator_energy.clone();
+        prev_entry.deplete(current_epoch);
+        let mut new_entry = new_delegator_energy.clone();
+        new_entry.deplete(current_epoch);
+
+        let mut delegated_energy = self.get_updated_delegated_energy(delegate);
+        delegated_energy.apply_entry_change(&prev_entry, &new_entry);
+        self.delegated_energy(delegate).set(&delegated_energy);
+    }
+
+    fn checkpoint_effective_energy(&self, user: &ManagedAddress) {
+        let effective_energy = self.get_effective_energy_entry_for_user(user);
+        self.add_energy_checkpoint(self.effective_energy_checkpoints(user), &effective_energy);
+    }
+
+    /// Users with no energy change since the effective energy history was introduced have no checkpoints.
+    /// Their stored entry is saved as the first checkpoint before it changes,
+    /// so lookups at earlier epochs still find it.
+    fn init_effective_energy_checkpoints(&self, user: &ManagedAddress) {
+        let checkpoints = self.effective_energy_checkpoints(user);
+        let energy_mapper = self.user_energy(user);
+        if !checkpoints.is_empty() || energy_mapper.is_empty() {
+            return;
+        }
+
+        self.add_energy_checkpoint(checkpoints, &energy_mapper.get());
+    }
+
+    fn get_updated_delegated_energy(&self, delegate: &ManagedAddress) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let delegated_energy_mapper = self.delegated_energy(delegate);
+        if !delegated_energy_mapper.is_empty() {
+            let mut delegated_energy = delegated_energy_mapper.get();
+            delegated_energy.deplete(current_epoch);
+
+            delegated_energy
+        } else {
+            Energy::new_zero_energy(current_epoch)
+        }
+    }
+
+    /// The user's own energy, unless delegated to another address,
+    /// plus the energy delegated to the user
+    #[view(getEffectiveEnergyEntryForUser)]
+    fn get_effective_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
+        let mut effective_energy = if self.energy_delegate(user).is_empty() {
+            self.get_updated_energy_entry_for_user(user)
+        } else {
+            let current_epoch = self.blockchain().get_block_epoch();
+            Energy::new_zero_energy(current_epoch)
+        };
+
+        let delegated_energy = self.get_updated_delegated_energy(user);
+        effective_energy.add_entry(&delegated_energy);
+
+        effective_energy
+    }
+
     #[view(getEnergyEntryForUser)]
     fn get_updated_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
@@ -209,4 +321,14 @@ pub trait EnergyModule: crate::events::EventsModule {
 
     #[storage_mapper("userEnergy")]
     fn user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[view(getEnergyDelegate)]
+    #[storage_mapper("energyDelegate")]
+    fn energy_delegate(&self, user: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("delegatedEnergy")]
+    fn delegated_energy(&self, delegate: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("effectiveEnergyCheckpoints")]
+    fn effective_energy_checkpoints(&self, user: &ManagedAddress) -> VecMapper<Energy<Self::Api>>;
 }
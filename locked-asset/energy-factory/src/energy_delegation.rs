@@ -0,0 +1,162 @@
+multiversx_sc::imports!();
+
+use common_structs::Epoch;
+
+use crate::energy::Energy;
+
+static UPDATE_ENERGY_AFTER_DELEGATION_ENDPOINT_NAME: &[u8] = b"updateEnergyAfterDelegation";
+
+/// Every delegation change notifies each consumer for up to three users,
+/// so their number is kept low
+pub const MAX_ENERGY_CONSUMERS: usize = 5;
+
+#[multiversx_sc::module]
+pub trait EnergyDelegationModule:
+    utils::UtilsModule
+    + crate::energy::EnergyModule
+    + crate::energy_history::EnergyHistoryModule
+    + crate::events::EventsModule
+    + multiversx_sc_modules::pause::PauseModule
+{
+    /// Delegates all of the caller's energy to the given address, replacing any previous delegation.
+    /// The locked tokens stay with the caller, and any future energy changes follow the delegation.
+    ///
+    /// Delegation is not transitive: energy delegated to the caller stays with the caller.
+    ///
+    /// The energy consumers (i.e. fees collector, farms) are notified of the change,
+    /// so the delegated energy is not counted twice in their weekly totals.
+    #[endpoint(delegateEnergy)]
+    fn delegate_energy(&self, to: ManagedAddress) {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        require!(!to.is_zero() && to != caller, "Invalid delegate address");
+
+        self.init_effective_energy_checkpoints(&caller);
+        self.init_effective_energy_checkpoints(&to);
+
+        let mut affected_users = ManagedVec::from_single_item(caller.clone());
+        let delegate_mapper = self.energy_delegate(&caller);
+        if !delegate_mapper.is_empty() {
+            affected_users.push(delegate_mapper.get());
+            self.remove_energy_delegation(&caller);
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let energy = self.get_updated_energy_entry_for_user(&caller);
+        self.energy_delegate(&caller).set(&to);
+        self.update_delegated_energy(&to, &Energy::new_zero_energy(current_epoch), &energy);
+
+        self.checkpoint_effective_energy(&caller);
+        self.checkpoint_effective_energy(&to);
+
+        affected_users.push(to);
+        self.notify_energy_consumers(&affected_users);
+    }
+
+    #[endpoint(undelegateEnergy)]
+    fn undelegate_energy(&self) {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.energy_delegate(&caller).is_empty(),
+            "Energy is not delegated"
+        );
+
+        let delegate = self.energy_delegate(&caller).get();
+        self.remove_energy_delegation(&caller);
+        self.checkpoint_effective_energy(&caller);
+
+        let mut affected_users = ManagedVec::from_single_item(caller);
+        affected_users.push(delegate);
+        self.notify_energy_consumers(&affected_users);
+    }
+
+    /// Registers the contracts tracking user energy through weekly rewards (i.e. fees collector, farms).
+    /// They must expose the `updateEnergyAfterDelegation` endpoint.
+    #[only_owner]
+    #[endpoint(addEnergyConsumers)]
+    fn add_energy_consumers(&self, sc_addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut mapper = self.energy_consumers();
+        for sc_addr in sc_addresses {
+            self.require_sc_address(&sc_addr);
+            let _ = mapper.insert(sc_addr);
+        }
+
+        require!(
+            mapper.len() <= MAX_ENERGY_CONSUMERS,
+            "Too many energy consumers"
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(removeEnergyConsumers)]
+    fn remove_energy_consumers(&self, sc_addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut mapper = self.energy_consumers();
+        for sc_addr in sc_addresses {
+            let _ = mapper.swap_remove(&sc_addr);
+        }
+    }
+
+    fn notify_energy_consumers(&self, users: &ManagedVec<ManagedAddress>) {
+        for consumer in self.energy_consumers().iter() {
+            for user in users {
+                let mut contract_call = self.send().contract_call::<()>(
+                    consumer.clone(),
+                    ManagedBuffer::new_from_bytes(UPDATE_ENERGY_AFTER_DELEGATION_ENDPOINT_NAME),
+                );
+                contract_call.proxy_arg(&user);
+                contract_call.execute_on_dest_context_ignore_result();
+            }
+        }
+    }
+
+    fn remove_energy_delegation(&self, delegator: &ManagedAddress) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let delegate = self.energy_delegate(delegator).take();
+        let energy = self.get_updated_energy_entry_for_user(delegator);
+        self.update_delegated_energy(&delegate, &energy, &Energy::new_zero_energy(current_epoch));
+
+        self.checkpoint_effective_energy(&delegate);
+    }
+
+    #[view(getEffectiveEnergyAmountForUser)]
+    fn get_effective_energy_amount_for_user(&self, user: ManagedAddress) -> BigUint {
+        let energy = self.get_effective_energy_entry_for_user(&user);
+
+        energy.get_energy_amount()
+    }
+
+    /// Checkpoints are only final for past epochs, so governance snapshots
+    /// should be taken at an epoch before the vote starts.
+    ///
+    /// Users without checkpoints kept the same entry since before the history was introduced.
+    #[view(getEffectiveEnergyAtEpoch)]
+    fn get_effective_energy_at_epoch(&self, user: ManagedAddress, epoch: Epoch) -> BigUint {
+        let checkpoints = self.effective_energy_checkpoints(&user);
+        if !checkpoints.is_empty() {
+            return self.get_energy_at_epoch(&checkpoints, epoch);
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(epoch <= current_epoch, "Epoch is in the future");
+
+        let energy_mapper = self.user_energy(&user);
+        if energy_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let mut energy = energy_mapper.get();
+        if energy.get_last_update_epoch() > epoch {
+            return BigUint::zero();
+        }
+
+        energy.deplete(epoch);
+        energy.get_energy_amount()
+    }
+
+    #[view(getEnergyConsumers)]
+    #[storage_mapper("energyConsumers")]
+    fn energy_consumers(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
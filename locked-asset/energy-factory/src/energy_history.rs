@@ -0,0 +1,139 @@
+multiversx_sc::imports!();
+
+use common_structs::Epoch;
+
+use crate::energy::Energy;
+
+#[multiversx_sc::module]
+pub trait EnergyHistoryModule {
+    /// Saves the new energy entry as the user's checkpoint for the current epoch,
+    /// and updates the total energy accordingly.
+    ///
+    /// Users are included in the total energy starting with their first energy update
+    /// after the history tracking was introduced, or once added through `addUsersToTotalEnergy`.
+    fn checkpoint_energy(
+        &self,
+        user: &ManagedAddress,
+        prev_energy: &Energy<Self::Api>,
+        new_energy: &Energy<Self::Api>,
+    ) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut new_entry = new_energy.clone();
+        new_entry.deplete(current_epoch);
+
+        let user_checkpoints = self.user_energy_checkpoints(user);
+        let counted_prev_entry = if user_checkpoints.is_empty() {
+            Energy::new_zero_energy(current_epoch)
+        } else {
+            prev_energy.clone()
+        };
+
+        let mut total_energy = self.get_updated_total_energy();
+        total_energy.apply_entry_change(&counted_prev_entry, &new_entry);
+        self.total_energy().set(&total_energy);
+
+        self.add_energy_checkpoint(user_checkpoints, &new_entry);
+        self.add_energy_checkpoint(self.total_energy_checkpoints(), &total_energy);
+    }
+
+    fn add_energy_checkpoint(
+        &self,
+        mut checkpoints: VecMapper<Energy<Self::Api>>,
+        energy: &Energy<Self::Api>,
+    ) {
+        let last_index = checkpoints.len();
+        if last_index > 0
+            && checkpoints.get(last_index).get_last_update_epoch() == energy.get_last_update_epoch()
+        {
+            checkpoints.set(last_index, energy);
+        } else {
+            checkpoints.push(energy);
+        }
+    }
+
+    /// The sum of all the tracked users' energy entries. The total decays lazily,
+    /// by the total locked tokens amount for every epoch passed since the last update.
+    ///
+    /// Tokens that reached their unlock epoch keep decaying until unlocked,
+    /// the same way they do for the user's own energy entry.
+    #[view(getTotalEnergyEntry)]
+    fn get_updated_total_energy(&self) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let total_energy_mapper = self.total_energy();
+        if !total_energy_mapper.is_empty() {
+            let mut total_energy = total_energy_mapper.get();
+            total_energy.deplete(current_epoch);
+
+            total_energy
+        } else {
+            Energy::new_zero_energy(current_epoch)
+        }
+    }
+
+    #[view(getTotalEnergy)]
+    fn get_total_energy_amount(&self) -> BigUint {
+        let total_energy = self.get_updated_total_energy();
+
+        total_energy.get_energy_amount()
+    }
+
+    #[view(getTotalLockedTokens)]
+    fn get_total_locked_tokens(&self) -> BigUint {
+        let total_energy = self.get_updated_total_energy();
+
+        total_energy.get_total_locked_tokens().clone()
+    }
+
+    #[view(getUserEnergyAtEpoch)]
+    fn get_user_energy_at_epoch(&self, user: ManagedAddress, epoch: Epoch) -> BigUint {
+        self.get_energy_at_epoch(&self.user_energy_checkpoints(&user), epoch)
+    }
+
+    #[view(getTotalEnergyAtEpoch)]
+    fn get_total_energy_at_epoch(&self, epoch: Epoch) -> BigUint {
+        self.get_energy_at_epoch(&self.total_energy_checkpoints(), epoch)
+    }
+
+    /// Finds the last checkpoint saved at or before the given epoch through binary search,
+    /// and depletes it up to that epoch
+    fn get_energy_at_epoch(
+        &self,
+        checkpoints: &VecMapper<Energy<Self::Api>>,
+        epoch: Epoch,
+    ) -> BigUint {
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(epoch <= current_epoch, "Epoch is in the future");
+
+        let mut low = 1;
+        let mut high = checkpoints.len();
+        let mut opt_found_index = None;
+        while low <= high {
+            let mid = (low + high) / 2;
+            if checkpoints.get(mid).get_last_update_epoch() <= epoch {
+                opt_found_index = Some(mid);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        match opt_found_index {
+            Some(index) => {
+                let mut energy = checkpoints.get(index);
+                energy.deplete(epoch);
+
+                energy.get_energy_amount()
+            }
+            None => BigUint::zero(),
+        }
+    }
+
+    #[storage_mapper("userEnergyCheckpoints")]
+    fn user_energy_checkpoints(&self, user: &ManagedAddress) -> VecMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("totalEnergyCheckpoints")]
+    fn total_energy_checkpoints(&self) -> VecMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("totalEnergy")]
+    fn total_energy(&self) -> SingleValueMapper<Energy<Self::Api>>;
+}
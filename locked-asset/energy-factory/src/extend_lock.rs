@@ -1,9 +1,9 @@
 multiversx_sc::imports!();
 
 use common_structs::Epoch;
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{apply_energy_multiplier, LockedTokenAttributes};
 
-use crate::energy::Energy;
+use crate::{energy::Energy, perpetual_lock::PERPETUAL_LOCK_ENABLED_ERR_MSG};
 
 static INVALID_EXTEND_PERIOD_ARG_ERR_MSG: &[u8] =
     b"New lock period must be longer than the current one";
@@ -28,18 +28,31 @@ pub trait ExtendLockModule:
         dest_address: &ManagedAddress,
         payment: EsdtTokenPayment,
         unlock_epoch: Epoch,
+        energy_multiplier: u64,
         current_epoch: Epoch,
     ) -> EsdtTokenPayment {
         let output_payment = self.update_energy(dest_address, |energy: &mut Energy<Self::Api>| {
             let payment_clone = payment.clone();
             if self.is_base_asset_token(&payment.token_identifier) {
-                self.lock_base_asset(payment_clone, unlock_epoch, current_epoch, energy)
+                self.lock_base_asset(
+                    payment_clone,
+                    unlock_epoch,
+                    energy_multiplier,
+                    current_epoch,
+                    energy,
+                )
             } else {
                 self.require_address_is_caller(dest_address);
                 self.locked_token()
                     .require_same_token(&payment.token_identifier);
 
-                self.extend_new_token_period(payment_clone, unlock_epoch, current_epoch, energy)
+                self.extend_new_token_period(
+                    payment_clone,
+                    unlock_epoch,
+                    energy_multiplier,
+                    current_epoch,
+                    energy,
+                )
             }
         });
 
@@ -56,11 +69,20 @@ pub trait ExtendLockModule:
         &self,
         payment: EsdtTokenPayment,
         unlock_epoch: Epoch,
+        energy_multiplier: u64,
         current_epoch: Epoch,
         energy: &mut Energy<Self::Api>,
     ) -> EsdtTokenPayment {
-        let output_tokens = self.lock_tokens(payment.into(), unlock_epoch);
-        energy.add_after_token_lock(&output_tokens.amount, unlock_epoch, current_epoch);
+        let output_tokens = self.lock_tokens_with_energy_multiplier(
+            payment.into(),
+            unlock_epoch,
+            energy_multiplier,
+        );
+        energy.add_after_token_lock(
+            &apply_energy_multiplier(&output_tokens.amount, energy_multiplier),
+            unlock_epoch,
+            current_epoch,
+        );
 
         self.to_esdt_payment(output_tokens)
     }
@@ -69,6 +91,7 @@ pub trait ExtendLockModule:
         &self,
         payment: EsdtTokenPayment,
         new_unlock_epoch: Epoch,
+        energy_multiplier: u64,
         current_epoch: Epoch,
         energy: &mut Energy<Self::Api>,
     ) -> EsdtTokenPayment {
@@ -76,20 +99,29 @@ pub trait ExtendLockModule:
             .locked_token()
             .get_token_attributes(payment.token_nonce);
 
+        require!(!attributes.is_perpetual(), PERPETUAL_LOCK_ENABLED_ERR_MSG);
         require!(
             new_unlock_epoch > attributes.unlock_epoch,
             INVALID_EXTEND_PERIOD_ARG_ERR_MSG
         );
 
-        energy.update_after_unlock_epoch_change(
-            &payment.amount,
+        energy.update_after_unlock_any(
+            &attributes.get_energy_weighted_amount(&payment.amount),
             attributes.unlock_epoch,
-            new_unlock_epoch,
             current_epoch,
         );
 
         let unlocked_tokens = self.unlock_tokens_unchecked(payment, &attributes);
-        let output_tokens = self.lock_tokens(unlocked_tokens, new_unlock_epoch);
+        let output_tokens = self.lock_tokens_with_energy_multiplier(
+            unlocked_tokens,
+            new_unlock_epoch,
+            energy_multiplier,
+        );
+        energy.add_after_token_lock(
+            &apply_energy_multiplier(&output_tokens.amount, energy_multiplier),
+            new_unlock_epoch,
+            current_epoch,
+        );
 
         self.to_esdt_payment(output_tokens)
     }
@@ -3,18 +3,23 @@
 multiversx_sc::imports!();
 
 pub mod energy;
+pub mod energy_delegation;
+pub mod energy_history;
 pub mod events;
 pub mod extend_lock;
 pub mod local_roles;
+pub mod lock_batch;
 pub mod lock_options;
 pub mod lock_options_endpoints;
 pub mod locked_token_transfer;
 pub mod migration;
 pub mod penalty;
+pub mod perpetual_lock;
 pub mod token_merging;
 pub mod token_whitelist;
 pub mod unlock_with_penalty;
 pub mod unstake;
+pub mod vesting;
 pub mod virtual_lock;
 
 use common_structs::{Epoch, Percent};
@@ -32,12 +37,16 @@ pub trait SimpleLockEnergy:
     + simple_lock::token_attributes::TokenAttributesModule
     + token_whitelist::TokenWhitelistModule
     + energy::EnergyModule
+    + energy_history::EnergyHistoryModule
+    + energy_delegation::EnergyDelegationModule
     + lock_options::LockOptionsModule
     + lock_options_endpoints::LockOptionsEndpointsModule
     + unlock_with_penalty::UnlockWithPenaltyModule
     + unstake::UnstakeModule
     + extend_lock::ExtendLockModule
+    + perpetual_lock::PerpetualLockModule
     + migration::SimpleLockMigrationModule
+    + vesting::VestingModule
     + events::EventsModule
     + multiversx_sc_modules::pause::PauseModule
     + local_roles::LocalRolesModule
@@ -45,6 +54,8 @@ pub trait SimpleLockEnergy:
     + penalty::LocalPenaltyModule
     + utils::UtilsModule
     + virtual_lock::VirtualLockModule
+    + lock_batch::LockBatchModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
     + sc_whitelist_module::SCWhitelistModule
     + locked_token_transfer::LockedTokenTransferModule
     + legacy_token_decode_module::LegacyTokenDecodeModule
@@ -124,8 +135,14 @@ pub trait SimpleLockEnergy:
             "Unlock epoch must be greater than the current epoch"
         );
 
-        let output_tokens =
-            self.lock_by_token_type(&dest_address, payment, unlock_epoch, current_epoch);
+        let energy_multiplier = self.get_energy_multiplier(lock_epochs);
+        let output_tokens = self.lock_by_token_type(
+            &dest_address,
+            payment,
+            unlock_epoch,
+            energy_multiplier,
+            current_epoch,
+        );
 
         self.send().direct_esdt(
             &dest_address,
@@ -164,7 +181,7 @@ pub trait SimpleLockEnergy:
 
                 let unlocked_tokens = self.unlock_tokens(payment);
                 energy.refund_after_token_unlock(
-                    &unlocked_tokens.amount,
+                    &attributes.get_energy_weighted_amount(&unlocked_tokens.amount),
                     attributes.unlock_epoch,
                     current_epoch,
                 );
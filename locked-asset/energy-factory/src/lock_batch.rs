@@ -0,0 +1,249 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::energy::Energy;
+use common_structs::Epoch;
+use multiversx_sc_modules::ongoing_operation::{CONTINUE_OP, STOP_OP};
+
+pub const MIN_GAS_TO_SAVE_PROGRESS: u64 = 30_000_000;
+const FIRST_INDEX: usize = 0;
+
+#[derive(
+    TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq,
+)]
+pub struct LockBatchEntry<M: ManagedTypeApi> {
+    pub destination: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+}
+
+/// A batch lock interrupted before running out of gas, saved for the caller that started it
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct OngoingLockBatch<M: ManagedTypeApi> {
+    pub unlock_epoch: Epoch,
+    pub energy_multiplier: u64,
+    pub entries: ManagedVec<M, LockBatchEntry<M>>,
+    pub entry_index: usize,
+}
+
+#[multiversx_sc::module]
+pub trait LockBatchModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::events::EventsModule
+    + crate::migration::SimpleLockMigrationModule
+    + multiversx_sc_modules::pause::PauseModule
+    + utils::UtilsModule
+    + crate::extend_lock::ExtendLockModule
+    + sc_whitelist_module::SCWhitelistModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+{
+    /// Locks the base asset payment for multiple recipients at once,
+    /// each receiving the given amount of locked tokens and the corresponding energy.
+    /// Only whitelisted SCs may call this endpoint.
+    ///
+    /// Expected payment: base asset tokens, the sum of all the amounts
+    ///
+    /// Arguments:
+    /// - lock_epochs - The lock option used for all the recipients
+    /// - batch - pairs of (destination, amount)
+    ///
+    /// If the operation runs out of gas, it is saved for the caller, and can be continued
+    /// through the `continueLockTokensBatch` endpoint,
+    /// or cancelled through the `cancelLockTokensBatch` endpoint.
+    /// Each caller may have a single batch in progress.
+    #[payable("*")]
+    #[endpoint(lockTokensBatch)]
+    fn lock_tokens_batch(
+        &self,
+        lock_epochs: Epoch,
+        batch: MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>>,
+    ) -> OperationCompletionStatus {
+        self.require_not_paused();
+        self.require_is_listed_lock_option(lock_epochs);
+
+        let caller = self.blockchain().get_caller();
+        self.require_sc_address_whitelisted(&caller);
+
+        require!(
+            self.ongoing_batch(&caller).is_empty(),
+            "Caller already has a batch lock in progress"
+        );
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            self.is_base_asset_token(&payment.token_identifier),
+            "May only lock the base asset token"
+        );
+
+        let mut entries = ManagedVec::new();
+        let mut total_amount = BigUint::zero();
+        for pair in batch {
+            let (destination, amount) = pair.into_tuple();
+            require!(amount > 0, "Amount cannot be 0");
+
+            total_amount += &amount;
+            entries.push(LockBatchEntry {
+                destination,
+                amount,
+            });
+        }
+        require!(
+            total_amount == payment.amount,
+            "Payment amount does not match the batch total"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlock_epoch = self.unlock_epoch_to_start_of_month(current_epoch + lock_epochs);
+        require!(
+            unlock_epoch > current_epoch,
+            "Unlock epoch must be greater than the current epoch"
+        );
+
+        let energy_multiplier = self.get_energy_multiplier(lock_epochs);
+
+        self.lock_batch_entries(
+            caller,
+            unlock_epoch,
+            energy_multiplier,
+            entries,
+            FIRST_INDEX,
+        )
+    }
+
+    /// Continues the caller's batch lock
+    #[endpoint(continueLockTokensBatch)]
+    fn continue_lock_tokens_batch(&self) -> OperationCompletionStatus {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let ongoing_batch_mapper = self.ongoing_batch(&caller);
+        require!(
+            !ongoing_batch_mapper.is_empty(),
+            "No batch lock in progress"
+        );
+
+        let ongoing_batch = ongoing_batch_mapper.get();
+        self.lock_batch_entries(
+            caller,
+            ongoing_batch.unlock_epoch,
+            ongoing_batch.energy_multiplier,
+            ongoing_batch.entries,
+            ongoing_batch.entry_index,
+        )
+    }
+
+    /// Cancels a batch lock, refunding the base asset for the remaining entries
+    /// to the batch creator.
+    ///
+    /// Arguments:
+    /// - opt_batch_creator - OPTIONAL: the caller that started the batch. Default is caller.
+    ///     Only the owner may cancel other callers' batches.
+    #[endpoint(cancelLockTokensBatch)]
+    fn cancel_lock_tokens_batch(
+        &self,
+        opt_batch_creator: OptionalValue<ManagedAddress>,
+    ) -> BigUint {
+        let caller = self.blockchain().get_caller();
+        let batch_creator = match opt_batch_creator {
+            OptionalValue::Some(batch_creator) => batch_creator,
+            OptionalValue::None => caller.clone(),
+        };
+        require!(
+            batch_creator == caller || caller == self.blockchain().get_owner_address(),
+            "Only the batch creator or the owner may cancel it"
+        );
+
+        let ongoing_batch_mapper = self.ongoing_batch(&batch_creator);
+        require!(
+            !ongoing_batch_mapper.is_empty(),
+            "No batch lock in progress"
+        );
+
+        let ongoing_batch = ongoing_batch_mapper.get();
+        ongoing_batch_mapper.clear();
+
+        let mut refund_amount = BigUint::zero();
+        for i in ongoing_batch.entry_index..ongoing_batch.entries.len() {
+            refund_amount += ongoing_batch.entries.get(i).amount;
+        }
+        if refund_amount > 0 {
+            let base_asset_token_id = self.base_asset_token_id().get();
+            self.send()
+                .direct_esdt(&batch_creator, &base_asset_token_id, 0, &refund_amount);
+        }
+
+        refund_amount
+    }
+
+    fn lock_batch_entries(
+        &self,
+        caller: ManagedAddress,
+        unlock_epoch: Epoch,
+        energy_multiplier: u64,
+        entries: ManagedVec<LockBatchEntry<Self::Api>>,
+        mut entry_index: usize,
+    ) -> OperationCompletionStatus {
+        let base_asset_token_id = self.base_asset_token_id().get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let entries_len = entries.len();
+
+        let run_result = self.run_while_it_has_gas(MIN_GAS_TO_SAVE_PROGRESS, || {
+            if entry_index >= entries_len {
+                return STOP_OP;
+            }
+
+            let entry = entries.get(entry_index);
+            let payment = EsdtTokenPayment::new(base_asset_token_id.clone(), 0, entry.amount);
+
+            // a batch continued after its unlock epoch pays out the base asset directly
+            let output_tokens = if unlock_epoch > current_epoch {
+                self.update_energy(&entry.destination, |energy: &mut Energy<Self::Api>| {
+                    self.lock_base_asset(
+                        payment,
+                        unlock_epoch,
+                        energy_multiplier,
+                        current_epoch,
+                        energy,
+                    )
+                })
+            } else {
+                payment
+            };
+            self.send().direct_esdt(
+                &entry.destination,
+                &output_tokens.token_identifier,
+                output_tokens.token_nonce,
+                &output_tokens.amount,
+            );
+
+            entry_index += 1;
+
+            CONTINUE_OP
+        });
+        if run_result == OperationCompletionStatus::InterruptedBeforeOutOfGas {
+            self.ongoing_batch(&caller).set(&OngoingLockBatch {
+                unlock_epoch,
+                energy_multiplier,
+                entries,
+                entry_index,
+            });
+        } else {
+            self.ongoing_batch(&caller).clear();
+        }
+
+        run_result
+    }
+
+    #[view(getOngoingLockBatch)]
+    #[storage_mapper("ongoingBatch")]
+    fn ongoing_batch(
+        &self,
+        caller: &ManagedAddress,
+    ) -> SingleValueMapper<OngoingLockBatch<Self::Api>>;
+}
@@ -2,6 +2,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use common_structs::Epoch;
+use simple_lock::locked_token::DEFAULT_ENERGY_MULTIPLIER;
 use unwrappable::Unwrappable;
 
 pub const EPOCHS_PER_MONTH: Epoch = 30;
@@ -37,6 +38,15 @@ pub trait LockOptionsModule {
         sc_panic!("Invalid lock choice");
     }
 
+    fn get_energy_multiplier(&self, lock_epochs: Epoch) -> u64 {
+        let mapper = self.energy_multiplier_for_lock_option(lock_epochs);
+        if mapper.is_empty() {
+            DEFAULT_ENERGY_MULTIPLIER
+        } else {
+            mapper.get()
+        }
+    }
+
     fn unlock_epoch_to_start_of_month(&self, unlock_epoch: Epoch) -> Epoch {
         let extra_days = unlock_epoch % EPOCHS_PER_MONTH;
         unlock_epoch - extra_days
@@ -62,4 +72,7 @@ pub trait LockOptionsModule {
 
     #[storage_mapper("lockOptions")]
     fn lock_options(&self) -> SingleValueMapper<AllLockOptions>;
+
+    #[storage_mapper("energyMultiplierForLockOption")]
+    fn energy_multiplier_for_lock_option(&self, lock_epochs: Epoch) -> SingleValueMapper<u64>;
 }
@@ -1,6 +1,7 @@
 multiversx_sc::imports!();
 
 use common_structs::{Epoch, Percent};
+use simple_lock::locked_token::DEFAULT_ENERGY_MULTIPLIER;
 
 use crate::lock_options::{
     AllLockOptions, LockOption, EPOCHS_PER_YEAR, MAX_LOCK_OPTIONS, MAX_PENALTY_PERCENTAGE,
@@ -55,6 +56,29 @@ pub trait LockOptionsEndpointsModule: crate::lock_options::LockOptionsModule {
     fn get_lock_options_view(&self) -> AllLockOptions {
         self.lock_options().get()
     }
+
+    /// Sets the energy multiplier for tokens locked with the given option,
+    /// where 10_000 is 1x, i.e. no bonus. For example, 15_000 gives 50% extra energy.
+    ///
+    /// The multiplier is saved in the tokens' attributes at lock time,
+    /// so changing it does not affect already locked tokens and their energy.
+    #[only_owner]
+    #[endpoint(setLockOptionEnergyMultiplier)]
+    fn set_lock_option_energy_multiplier(&self, lock_epochs: Epoch, energy_multiplier: u64) {
+        self.require_is_listed_lock_option(lock_epochs);
+        require!(
+            energy_multiplier >= DEFAULT_ENERGY_MULTIPLIER,
+            "Invalid energy multiplier"
+        );
+
+        self.energy_multiplier_for_lock_option(lock_epochs)
+            .set(energy_multiplier);
+    }
+
+    #[view(getLockOptionEnergyMultiplier)]
+    fn get_lock_option_energy_multiplier(&self, lock_epochs: Epoch) -> u64 {
+        self.get_energy_multiplier(lock_epochs)
+    }
 }
 
 fn sort_lock_options(lock_options: &mut AllLockOptions) {
@@ -42,6 +42,24 @@ pub trait SimpleLockMigrationModule:
         }
     }
 
+    /// Adds the current energy of the given users to the total energy.
+    /// Meant for users that had no energy update since the total energy tracking was introduced.
+    /// Users that are already tracked are skipped.
+    #[only_owner]
+    #[endpoint(addUsersToTotalEnergy)]
+    fn add_users_to_total_energy(&self, users: MultiValueEncoded<ManagedAddress>) {
+        for user in users {
+            if !self.user_energy_checkpoints(&user).is_empty() || self.user_energy(&user).is_empty()
+            {
+                continue;
+            }
+
+            let energy = self.get_updated_energy_entry_for_user(&user);
+            self.checkpoint_energy(&user, &energy, &energy);
+            self.checkpoint_effective_energy(&user);
+        }
+    }
+
     #[endpoint(updateEnergyAfterOldTokenUnlock)]
     fn update_energy_after_old_token_unlock(
         &self,
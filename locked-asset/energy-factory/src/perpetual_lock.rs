@@ -0,0 +1,134 @@
+multiversx_sc::imports!();
+
+use common_structs::Epoch;
+use simple_lock::locked_token::{apply_energy_multiplier, LockedTokenAttributes};
+
+use crate::energy::Energy;
+
+pub static PERPETUAL_LOCK_ENABLED_ERR_MSG: &[u8] = b"Perpetual lock must be disabled first";
+
+#[multiversx_sc::module]
+pub trait PerpetualLockModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::events::EventsModule
+    + multiversx_sc_modules::pause::PauseModule
+    + utils::UtilsModule
+{
+    /// Switches locked tokens to perpetual lock mode. While enabled, the tokens' unlock epoch
+    /// stays pinned at current epoch + `lock_epochs`, so their energy does not decay.
+    ///
+    /// Arguments:
+    /// - lock_epochs - One of the available lock options.
+    ///     May not be shorter than the tokens' remaining lock period.
+    ///
+    /// Expected payment: LOCKED tokens
+    ///
+    /// Output payment: LOCKED tokens in perpetual lock mode
+    #[payable("*")]
+    #[endpoint(enablePerpetualLock)]
+    fn enable_perpetual_lock(&self, lock_epochs: Epoch) -> EsdtTokenPayment {
+        self.require_not_paused();
+        self.require_is_listed_lock_option(lock_epochs);
+
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let locked_token_mapper = self.locked_token();
+        locked_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: LockedTokenAttributes<Self::Api> =
+            locked_token_mapper.get_token_attributes(payment.token_nonce);
+        require!(!attributes.is_perpetual(), "Perpetual lock already enabled");
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch + lock_epochs >= attributes.unlock_epoch,
+            "Lock period may not be shorter than the remaining one"
+        );
+
+        let energy_multiplier = self.get_energy_multiplier(lock_epochs);
+        self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            energy.update_after_unlock_any(
+                &attributes.get_energy_weighted_amount(&payment.amount),
+                attributes.unlock_epoch,
+                current_epoch,
+            );
+            energy.add_after_perpetual_lock(
+                &apply_energy_multiplier(&payment.amount, energy_multiplier),
+                lock_epochs,
+            );
+        });
+
+        locked_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let new_attributes = LockedTokenAttributes::new_perpetual(
+            attributes.original_token_id.clone(),
+            attributes.original_token_nonce,
+            lock_epochs,
+            energy_multiplier,
+        );
+        let new_token_nonce = self.get_or_create_nonce_for_attributes(
+            &locked_token_mapper,
+            &attributes.original_token_id.into_name(),
+            &new_attributes,
+        );
+
+        locked_token_mapper.nft_add_quantity_and_send(&caller, new_token_nonce, payment.amount)
+    }
+
+    /// Disables perpetual lock mode. The tokens are locked until the start of the month
+    /// of current epoch + the pinned lock period, and their energy starts decaying normally.
+    ///
+    /// Expected payment: LOCKED tokens in perpetual lock mode
+    ///
+    /// Output payment: LOCKED tokens
+    #[payable("*")]
+    #[endpoint(disablePerpetualLock)]
+    fn disable_perpetual_lock(&self) -> EsdtTokenPayment {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let locked_token_mapper = self.locked_token();
+        locked_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: LockedTokenAttributes<Self::Api> =
+            locked_token_mapper.get_token_attributes(payment.token_nonce);
+        require!(attributes.is_perpetual(), "Perpetual lock not enabled");
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let new_unlock_epoch =
+            self.unlock_epoch_to_start_of_month(current_epoch + attributes.perpetual_lock_epochs);
+
+        let energy_weighted_amount = attributes.get_energy_weighted_amount(&payment.amount);
+        self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            energy.remove_after_perpetual_unlock(
+                &energy_weighted_amount,
+                attributes.perpetual_lock_epochs,
+            );
+            energy.add_after_token_lock(&energy_weighted_amount, new_unlock_epoch, current_epoch);
+        });
+
+        locked_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let energy_multiplier = attributes.energy_multiplier;
+        let unlocked_tokens = self.unlock_tokens_unchecked(payment, &attributes);
+        let output_tokens = self.lock_tokens_with_energy_multiplier(
+            unlocked_tokens,
+            new_unlock_epoch,
+            energy_multiplier,
+        );
+        self.send().direct(
+            &caller,
+            &output_tokens.token_identifier,
+            output_tokens.token_nonce,
+            &output_tokens.amount,
+        );
+
+        self.to_esdt_payment(output_tokens)
+    }
+}
@@ -1,13 +1,17 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::PaymentsVec;
+use common_structs::{Epoch, PaymentsVec};
 use math::weighted_average_round_up;
 use mergeable::Mergeable;
-use simple_lock::locked_token::LockedTokenAttributes;
+use multiversx_sc::contract_base::BlockchainWrapper;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 use unwrappable::Unwrappable;
 
-use crate::{energy::Energy, unlock_with_penalty::TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG};
+use crate::{
+    energy::Energy, perpetual_lock::PERPETUAL_LOCK_ENABLED_ERR_MSG,
+    unlock_with_penalty::TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG,
+};
 
 #[derive(TopEncode, TopDecode, Clone, PartialEq, Debug)]
 pub struct LockedAmountWeightAttributesPair<M: ManagedTypeApi> {
@@ -22,6 +26,30 @@ impl<M: ManagedTypeApi> LockedAmountWeightAttributesPair<M> {
             attributes,
         }
     }
+
+    /// The energy of the tokens, scaled by DEFAULT_ENERGY_MULTIPLIER
+    fn get_scaled_energy(&self, current_epoch: Epoch) -> BigUint<M> {
+        self.attributes
+            .get_energy_weighted_amount(&self.token_amount)
+            * (self.attributes.unlock_epoch - current_epoch)
+            * DEFAULT_ENERGY_MULTIPLIER
+    }
+
+    /// Sets the new unlock epoch, lowering the energy multiplier
+    /// so the energy does not go above the given scaled energy
+    fn set_unlock_epoch_keeping_energy(
+        &mut self,
+        unlock_epoch: Epoch,
+        scaled_energy: &BigUint<M>,
+        current_epoch: Epoch,
+    ) where
+        M: BlockchainApi,
+    {
+        let divisor = &self.token_amount * (unlock_epoch - current_epoch);
+        self.attributes.energy_multiplier =
+            (scaled_energy / &divisor).to_u64().unwrap_or_panic::<M>();
+        self.attributes.unlock_epoch = unlock_epoch;
+    }
 }
 
 impl<M: ManagedTypeApi + BlockchainApi> Mergeable<M> for LockedAmountWeightAttributesPair<M> {
@@ -33,6 +61,8 @@ impl<M: ManagedTypeApi + BlockchainApi> Mergeable<M> for LockedAmountWeightAttri
         same_token_id && same_token_nonce
     }
 
+    /// Tokens with different energy multipliers get a new multiplier,
+    /// chosen so that the merged energy is not higher than the sum of the tokens' energy.
     fn merge_with(&mut self, other: Self) {
         self.error_if_not_mergeable(&other);
 
@@ -45,8 +75,18 @@ impl<M: ManagedTypeApi + BlockchainApi> Mergeable<M> for LockedAmountWeightAttri
         .to_u64()
         .unwrap_or_panic::<M>();
 
-        self.attributes.unlock_epoch = new_unlock_epoch;
+        if self.attributes.energy_multiplier == other.attributes.energy_multiplier {
+            self.attributes.unlock_epoch = new_unlock_epoch;
+            self.token_amount += other.token_amount;
+
+            return;
+        }
+
+        let current_epoch = BlockchainWrapper::<M>::new().get_block_epoch();
+        let total_scaled_energy =
+            self.get_scaled_energy(current_epoch) + other.get_scaled_energy(current_epoch);
         self.token_amount += other.token_amount;
+        self.set_unlock_epoch_keeping_energy(new_unlock_epoch, &total_scaled_energy, current_epoch);
     }
 }
 
@@ -84,10 +124,16 @@ pub trait TokenMergingModule:
             output_amount_attributes.attributes.original_token_nonce,
             output_amount_attributes.token_amount,
         );
-        let output_tokens = self.lock_and_send(
-            &caller,
+        let output_tokens = self.lock_tokens_with_energy_multiplier(
             simulated_lock_payment,
             output_amount_attributes.attributes.unlock_epoch,
+            output_amount_attributes.attributes.energy_multiplier,
+        );
+        self.send().direct(
+            &caller,
+            &output_tokens.token_identifier,
+            output_tokens.token_nonce,
+            &output_tokens.amount,
         );
 
         self.to_esdt_payment(output_tokens)
@@ -107,33 +153,45 @@ pub trait TokenMergingModule:
         let current_epoch = self.blockchain().get_block_epoch();
         let first_token_attributes: LockedTokenAttributes<Self::Api> =
             locked_token_mapper.get_token_attributes(first_payment.token_nonce);
+        require!(
+            !first_token_attributes.is_perpetual(),
+            PERPETUAL_LOCK_ENABLED_ERR_MSG
+        );
         require!(
             first_token_attributes.unlock_epoch > current_epoch,
             TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG
         );
 
         energy.update_after_unlock_any(
-            &first_payment.amount,
+            &first_token_attributes.get_energy_weighted_amount(&first_payment.amount),
             first_token_attributes.unlock_epoch,
             current_epoch,
         );
 
         locked_token_mapper.nft_burn(first_payment.token_nonce, &first_payment.amount);
 
+        let first_energy_multiplier = first_token_attributes.energy_multiplier;
+        let mut same_energy_multipliers = true;
         let mut output_pair =
             LockedAmountWeightAttributesPair::new(first_payment.amount, first_token_attributes);
         for payment in &payments {
             let attributes: LockedTokenAttributes<Self::Api> =
                 locked_token_mapper.get_token_attributes(payment.token_nonce);
+            require!(!attributes.is_perpetual(), PERPETUAL_LOCK_ENABLED_ERR_MSG);
             require!(
                 attributes.unlock_epoch > current_epoch,
                 TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG
             );
 
-            energy.update_after_unlock_any(&payment.amount, attributes.unlock_epoch, current_epoch);
+            energy.update_after_unlock_any(
+                &attributes.get_energy_weighted_amount(&payment.amount),
+                attributes.unlock_epoch,
+                current_epoch,
+            );
 
             locked_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
 
+            same_energy_multipliers &= attributes.energy_multiplier == first_energy_multiplier;
             let amount_attr_pair =
                 LockedAmountWeightAttributesPair::new(payment.amount, attributes);
             output_pair.merge_with(amount_attr_pair);
@@ -141,10 +199,21 @@ pub trait TokenMergingModule:
 
         let normalized_unlock_epoch =
             self.unlock_epoch_to_start_of_month_upper_estimate(output_pair.attributes.unlock_epoch);
-        output_pair.attributes.unlock_epoch = normalized_unlock_epoch;
+        if same_energy_multipliers {
+            output_pair.attributes.unlock_epoch = normalized_unlock_epoch;
+        } else {
+            let scaled_energy = output_pair.get_scaled_energy(current_epoch);
+            output_pair.set_unlock_epoch_keeping_energy(
+                normalized_unlock_epoch,
+                &scaled_energy,
+                current_epoch,
+            );
+        }
 
         energy.add_after_token_lock(
-            &output_pair.token_amount,
+            &output_pair
+                .attributes
+                .get_energy_weighted_amount(&output_pair.token_amount),
             output_pair.attributes.unlock_epoch,
             current_epoch,
         );
@@ -3,9 +3,12 @@ multiversx_sc::derive_imports!();
 
 use common_structs::Epoch;
 
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{apply_energy_multiplier, LockedTokenAttributes};
 
-use crate::{energy::Energy, lock_options::MAX_PENALTY_PERCENTAGE};
+use crate::{
+    energy::Energy, lock_options::MAX_PENALTY_PERCENTAGE,
+    perpetual_lock::PERPETUAL_LOCK_ENABLED_ERR_MSG,
+};
 
 pub static TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG: &[u8] = b"Token can be unlocked already";
 
@@ -15,6 +18,16 @@ pub struct LockReduceResult<M: ManagedTypeApi> {
     pub energy: Energy<M>,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Debug)]
+pub struct LockReductionPreview<M: ManagedTypeApi> {
+    pub new_lock_epochs: Epoch,
+    pub penalty_amount: BigUint<M>,
+    pub received_amount: BigUint<M>,
+    pub energy_delta: BigInt<M>,
+    pub burn_amount: BigUint<M>,
+    pub fees_collector_amount: BigUint<M>,
+}
+
 #[multiversx_sc::module]
 pub trait UnlockWithPenaltyModule:
     simple_lock::basic_lock_unlock::BasicLockUnlock
@@ -40,6 +53,45 @@ pub trait UnlockWithPenaltyModule:
     fn unlock_early(&self) {
         let caller = self.blockchain().get_caller();
         let payment = self.call_value().single_esdt();
+        self.unlock_early_common(caller, payment);
+    }
+
+    /// Unlock only `amount` of the given locked tokens instantly, with the same penalty as `unlockEarly`.
+    /// The remaining locked tokens are sent back to the caller, keeping their unlock epoch.
+    ///
+    /// Output payment: the remaining LOCKED tokens
+    #[payable("*")]
+    #[endpoint(unlockEarlyPartial)]
+    fn unlock_early_partial(&self, amount: BigUint) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        require!(
+            amount > 0 && amount <= payment.amount,
+            "Invalid unlock amount"
+        );
+
+        let remaining_tokens = EsdtTokenPayment::new(
+            payment.token_identifier.clone(),
+            payment.token_nonce,
+            &payment.amount - &amount,
+        );
+        let tokens_to_unlock =
+            EsdtTokenPayment::new(payment.token_identifier, payment.token_nonce, amount);
+        self.unlock_early_common(caller.clone(), tokens_to_unlock);
+
+        if remaining_tokens.amount > 0 {
+            self.send().direct_esdt(
+                &caller,
+                &remaining_tokens.token_identifier,
+                remaining_tokens.token_nonce,
+                &remaining_tokens.amount,
+            );
+        }
+
+        remaining_tokens
+    }
+
+    fn unlock_early_common(&self, caller: ManagedAddress, payment: EsdtTokenPayment) {
         let reduce_result = self.reduce_lock_period_common(&caller, payment.clone(), None);
 
         let unlocked_tokens = self.to_esdt_payment(reduce_result.unlocked_tokens);
@@ -71,7 +123,12 @@ pub trait UnlockWithPenaltyModule:
 
         let unlocked_tokens = reduce_result.unlocked_tokens;
         let penalty_amount = &payment.amount - &unlocked_tokens.amount;
-        let new_locked_tokens = self.lock_tokens(unlocked_tokens, new_unlock_epoch);
+        let energy_multiplier = self.get_energy_multiplier(new_lock_period);
+        let new_locked_tokens = self.lock_tokens_with_energy_multiplier(
+            unlocked_tokens,
+            new_unlock_epoch,
+            energy_multiplier,
+        );
 
         let amount_to_burn = &payment.amount - &penalty_amount;
         self.send().esdt_local_burn(
@@ -89,7 +146,11 @@ pub trait UnlockWithPenaltyModule:
         }
 
         let mut energy = reduce_result.energy;
-        energy.add_after_token_lock(&new_locked_tokens.amount, new_unlock_epoch, current_epoch);
+        energy.add_after_token_lock(
+            &apply_energy_multiplier(&new_locked_tokens.amount, energy_multiplier),
+            new_unlock_epoch,
+            current_epoch,
+        );
         self.set_energy_entry(&caller, energy);
 
         self.send().direct(
@@ -116,13 +177,66 @@ pub trait UnlockWithPenaltyModule:
         let attributes: LockedTokenAttributes<Self::Api> =
             locked_token_mapper.get_token_attributes(payment.token_nonce);
 
+        let mut energy = self.get_updated_energy_entry_for_user(caller);
+        let (new_lock_epochs, penalty_amount) = self.apply_lock_reduction(
+            &payment.amount,
+            &attributes,
+            opt_new_lock_period,
+            &mut energy,
+        );
+        let mut unlocked_tokens = self.unlock_tokens_unchecked(payment, &attributes);
+        require!(
+            unlocked_tokens.amount > penalty_amount,
+            "No tokens remaining after penalty is applied"
+        );
+        unlocked_tokens.amount -= penalty_amount;
+
+        LockReduceResult {
+            new_lock_epochs,
+            energy,
+            unlocked_tokens,
+        }
+    }
+
+    /// Validates the lock period reduction, and applies the early unlock on the given energy entry.
+    /// Returns the new lock epochs and the penalty amount.
+    fn apply_lock_reduction(
+        &self,
+        token_amount: &BigUint,
+        attributes: &LockedTokenAttributes<Self::Api>,
+        opt_new_lock_period: Option<Epoch>,
+        energy: &mut Energy<Self::Api>,
+    ) -> (Epoch, BigUint) {
+        require!(!attributes.is_perpetual(), PERPETUAL_LOCK_ENABLED_ERR_MSG);
+
         let current_epoch = self.blockchain().get_block_epoch();
         require!(
             attributes.unlock_epoch > current_epoch,
             TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG
         );
 
-        let new_lock_epochs = match opt_new_lock_period {
+        let new_lock_epochs = self.get_reduced_lock_epochs(current_epoch, opt_new_lock_period);
+        let prev_lock_epochs = attributes.unlock_epoch - current_epoch;
+        require!(new_lock_epochs < prev_lock_epochs, "Invalid reduce choice");
+
+        energy.deplete_after_early_unlock(
+            &attributes.get_energy_weighted_amount(token_amount),
+            attributes.unlock_epoch,
+            current_epoch,
+        );
+
+        let penalty_amount =
+            self.calculate_penalty_amount(token_amount, prev_lock_epochs, new_lock_epochs);
+
+        (new_lock_epochs, penalty_amount)
+    }
+
+    fn get_reduced_lock_epochs(
+        &self,
+        current_epoch: Epoch,
+        opt_new_lock_period: Option<Epoch>,
+    ) -> Epoch {
+        match opt_new_lock_period {
             Some(lock_epochs) => {
                 let tentative_new_unlock_epoch = current_epoch + lock_epochs;
                 let start_of_month_epoch =
@@ -133,27 +247,95 @@ pub trait UnlockWithPenaltyModule:
                 lock_epochs - epochs_diff_from_month_start
             }
             None => 0,
-        };
+        }
+    }
+
+    /// Previews all the ways of reducing the lock period of `token_amount` tokens of the given nonce.
+    /// The first entry is the full early unlock (`new_lock_epochs` = 0),
+    /// followed by the reduction to each of the shorter lock options.
+    /// `energy_delta` is the resulting change of the owner's energy.
+    ///
+    /// The penalty is deposited to the token unstake SC,
+    /// which burns `burn_amount` and sends `fees_collector_amount` to the fees collector.
+    ///
+    /// The result is empty for perpetual tokens, as these cannot be unlocked early,
+    /// or if the token unstake SC is not set yet.
+    #[view(getLockReductionPreview)]
+    fn get_lock_reduction_preview(
+        &self,
+        token_nonce: u64,
+        token_amount: BigUint,
+    ) -> MultiValueEncoded<LockReductionPreview<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let attributes: LockedTokenAttributes<Self::Api> =
+            self.locked_token().get_token_attributes(token_nonce);
+        if attributes.is_perpetual() || self.token_unstake_sc_address().is_empty() {
+            return result;
+        }
 
+        let fees_burn_percentage = self.get_unstake_sc_fees_burn_percentage();
+        let full_unlock_preview =
+            self.preview_lock_reduction(&token_amount, &attributes, None, fees_burn_percentage);
+        result.push(full_unlock_preview);
+
+        let current_epoch = self.blockchain().get_block_epoch();
         let prev_lock_epochs = attributes.unlock_epoch - current_epoch;
-        require!(new_lock_epochs < prev_lock_epochs, "Invalid reduce choice");
+        let lock_options = self.get_lock_options();
+        for option in &lock_options {
+            let new_lock_epochs =
+                self.get_reduced_lock_epochs(current_epoch, Some(option.lock_epochs));
+            if new_lock_epochs >= prev_lock_epochs {
+                continue;
+            }
 
-        let mut energy = self.get_updated_energy_entry_for_user(caller);
-        energy.deplete_after_early_unlock(&payment.amount, attributes.unlock_epoch, current_epoch);
+            let preview = self.preview_lock_reduction(
+                &token_amount,
+                &attributes,
+                Some(option.lock_epochs),
+                fees_burn_percentage,
+            );
+            result.push(preview);
+        }
 
-        let penalty_amount =
-            self.calculate_penalty_amount(&payment.amount, prev_lock_epochs, new_lock_epochs);
-        let mut unlocked_tokens = self.unlock_tokens_unchecked(payment, &attributes);
-        require!(
-            unlocked_tokens.amount > penalty_amount,
-            "No tokens remaining after penalty is applied"
+        result
+    }
+
+    fn preview_lock_reduction(
+        &self,
+        token_amount: &BigUint,
+        attributes: &LockedTokenAttributes<Self::Api>,
+        opt_new_lock_period: Option<Epoch>,
+        fees_burn_percentage: u64,
+    ) -> LockReductionPreview<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut energy = Energy::new(
+            BigInt::zero(),
+            current_epoch,
+            attributes.get_energy_weighted_amount(token_amount),
         );
-        unlocked_tokens.amount -= penalty_amount;
+        let (new_lock_epochs, penalty_amount) =
+            self.apply_lock_reduction(token_amount, attributes, opt_new_lock_period, &mut energy);
 
-        LockReduceResult {
+        let received_amount = token_amount - &penalty_amount;
+        if let Some(new_lock_period) = opt_new_lock_period {
+            let energy_multiplier = self.get_energy_multiplier(new_lock_period);
+            energy.add_after_token_lock(
+                &apply_energy_multiplier(&received_amount, energy_multiplier),
+                current_epoch + new_lock_epochs,
+                current_epoch,
+            );
+        }
+
+        let burn_amount = &penalty_amount * fees_burn_percentage / MAX_PENALTY_PERCENTAGE;
+        let fees_collector_amount = &penalty_amount - &burn_amount;
+
+        LockReductionPreview {
             new_lock_epochs,
-            energy,
-            unlocked_tokens,
+            penalty_amount,
+            received_amount,
+            energy_delta: energy.get_energy_amount_raw().clone(),
+            burn_amount,
+            fees_collector_amount,
         }
     }
 
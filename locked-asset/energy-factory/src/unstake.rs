@@ -14,6 +14,9 @@ mod token_unstake_proxy {
         #[payable("*")]
         #[endpoint(depositFees)]
         fn deposit_fees(&self);
+
+        #[view(getFeesBurnPercentage)]
+        fn get_fees_burn_percentage(&self) -> u64;
     }
 }
 
@@ -77,6 +80,13 @@ pub trait UnstakeModule:
             .execute_on_dest_context();
     }
 
+    fn get_unstake_sc_fees_burn_percentage(&self) -> u64 {
+        let sc_address = self.token_unstake_sc_address().get();
+        self.token_unstake_sc_proxy_obj(sc_address)
+            .get_fees_burn_percentage()
+            .execute_on_dest_context()
+    }
+
     fn require_caller_unstake_sc(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.token_unstake_sc_address().get();
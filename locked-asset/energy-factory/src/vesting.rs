@@ -0,0 +1,379 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Epoch, UnlockEpochAmountPairs};
+use simple_lock::error_messages::INVALID_PAYMENTS_ERR_MSG;
+use unwrappable::Unwrappable;
+
+use crate::energy::Energy;
+
+static INVALID_VESTING_SCHEDULE_ERR_MSG: &[u8] = b"Invalid vesting schedule";
+static REACHED_MILESTONES_ERR_MSG: &[u8] = b"Reached milestones must be unlocked first";
+
+/// Tokens vest linearly between `start_epoch` and `end_epoch`,
+/// but nothing can be unlocked before `cliff_epoch`.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct VestingTokenAttributes {
+    pub start_epoch: Epoch,
+    pub cliff_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+impl VestingTokenAttributes {
+    pub fn new(start_epoch: Epoch, cliff_epoch: Epoch, end_epoch: Epoch) -> Self {
+        VestingTokenAttributes {
+            start_epoch,
+            cliff_epoch,
+            end_epoch,
+        }
+    }
+
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.start_epoch <= self.cliff_epoch && self.cliff_epoch <= self.end_epoch
+    }
+
+    pub fn get_unlockable_amount<M: ManagedTypeApi>(
+        &self,
+        token_amount: &BigUint<M>,
+        current_epoch: Epoch,
+    ) -> BigUint<M> {
+        if current_epoch >= self.end_epoch {
+            return token_amount.clone();
+        }
+        if current_epoch < self.cliff_epoch {
+            return BigUint::zero();
+        }
+
+        let vested_epochs = current_epoch - self.start_epoch;
+        let total_vesting_epochs = self.end_epoch - self.start_epoch;
+
+        token_amount * vested_epochs / total_vesting_epochs
+    }
+
+    /// The tokens left after a partial unlock keep vesting at the same rate until `end_epoch`
+    pub fn get_remaining_schedule(&self, current_epoch: Epoch) -> Self {
+        VestingTokenAttributes::new(current_epoch, current_epoch, self.end_epoch)
+    }
+
+    /// The average epoch at which the tokens become unlockable, rounded up.
+    /// Tokens vesting before the cliff are only unlockable at `cliff_epoch`.
+    ///
+    /// As tokens keep decaying until actually unlocked, energy computed as if all tokens
+    /// were locked until this epoch is the same as the sum over the whole schedule.
+    pub fn get_average_unlock_epoch(&self) -> Epoch {
+        if self.end_epoch == self.start_epoch {
+            return self.end_epoch;
+        }
+
+        let epochs_before_cliff = self.cliff_epoch - self.start_epoch;
+        let epochs_after_cliff = self.end_epoch - self.cliff_epoch;
+        let weighted_epochs_sum = 2 * epochs_before_cliff * self.cliff_epoch
+            + epochs_after_cliff * (self.cliff_epoch + self.end_epoch);
+
+        let total_weight = 2 * (self.end_epoch - self.start_epoch);
+        (weighted_epochs_sum + total_weight - 1) / total_weight
+    }
+}
+
+#[multiversx_sc::module]
+pub trait VestingModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::migration::SimpleLockMigrationModule
+    + crate::events::EventsModule
+    + multiversx_sc_modules::pause::PauseModule
+    + utils::UtilsModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+{
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueVestingToken)]
+    fn issue_vesting_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        let payment_amount = self.call_value().egld_value();
+
+        self.vesting_token().issue_and_set_all_roles(
+            EsdtTokenType::Meta,
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    /// Sets the transfer role for the given address. Defaults to own address.
+    #[only_owner]
+    #[endpoint(setTransferRoleVestingToken)]
+    fn set_transfer_role_vesting_token(&self, opt_address: OptionalValue<ManagedAddress>) {
+        let address = match opt_address {
+            OptionalValue::Some(addr) => addr,
+            OptionalValue::None => self.blockchain().get_sc_address(),
+        };
+
+        self.vesting_token().set_local_roles_for_address(
+            &address,
+            &[EsdtLocalRole::Transfer],
+            None,
+        );
+    }
+
+    /// Locks base asset tokens on a vesting schedule, and receive VESTING tokens on a 1:1 ratio.
+    /// The tokens vest linearly between the start and end epochs,
+    /// and may be unlocked pro-rata after the cliff epoch, through `unlockVestedTokens`.
+    ///
+    /// Arguments:
+    /// - start_epoch, cliff_epoch, end_epoch - The vesting schedule.
+    ///     May not start in the past, and may not end later than the longest lock option.
+    /// - opt_destination - OPTIONAL: destination address for the VESTING tokens. Default is caller.
+    ///
+    /// Output payment: VESTING tokens
+    #[payable("*")]
+    #[endpoint(lockTokensVesting)]
+    fn lock_tokens_vesting(
+        &self,
+        start_epoch: Epoch,
+        cliff_epoch: Epoch,
+        end_epoch: Epoch,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        self.require_not_paused();
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            self.is_base_asset_token(&payment.token_identifier) && payment.token_nonce == 0,
+            INVALID_PAYMENTS_ERR_MSG
+        );
+
+        let attributes = VestingTokenAttributes::new(start_epoch, cliff_epoch, end_epoch);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let lock_options = self.get_lock_options();
+        let max_lock_option = lock_options.last().unwrap_or_panic::<Self::Api>();
+        require!(
+            attributes.is_valid()
+                && start_epoch >= current_epoch
+                && end_epoch > current_epoch
+                && end_epoch <= current_epoch + max_lock_option.lock_epochs,
+            INVALID_VESTING_SCHEDULE_ERR_MSG
+        );
+
+        let dest_address = self.dest_from_optional(opt_destination);
+        self.update_energy(&dest_address, |energy: &mut Energy<Self::Api>| {
+            energy.add_after_token_lock(
+                &payment.amount,
+                attributes.get_average_unlock_epoch(),
+                current_epoch,
+            );
+        });
+
+        self.send().esdt_local_burn(
+            &payment.token_identifier,
+            payment.token_nonce,
+            &payment.amount,
+        );
+
+        self.mint_and_send_vesting_tokens(&dest_address, &attributes, payment.amount)
+    }
+
+    /// Unlocks the vested part of the given VESTING tokens.
+    /// The tokens that did not vest yet are sent back, keeping the same vesting rate.
+    ///
+    /// Expected payment: VESTING tokens
+    ///
+    /// Output payments: the unlocked base asset tokens
+    #[payable("*")]
+    #[endpoint(unlockVestedTokens)]
+    fn unlock_vested_tokens(&self) -> EsdtTokenPayment {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let vesting_token_mapper = self.vesting_token();
+        vesting_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: VestingTokenAttributes =
+            vesting_token_mapper.get_token_attributes(payment.token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlocked_amount = attributes.get_unlockable_amount(&payment.amount, current_epoch);
+        require!(unlocked_amount > 0, "Cannot unlock yet");
+
+        let remaining_amount = &payment.amount - &unlocked_amount;
+        let remaining_attributes = attributes.get_remaining_schedule(current_epoch);
+        self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            energy.update_after_unlock_any(
+                &payment.amount,
+                attributes.get_average_unlock_epoch(),
+                current_epoch,
+            );
+            if remaining_amount > 0 {
+                energy.add_after_token_lock(
+                    &remaining_amount,
+                    remaining_attributes.get_average_unlock_epoch(),
+                    current_epoch,
+                );
+            }
+        });
+
+        vesting_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+        if remaining_amount > 0 {
+            let _ =
+                self.mint_and_send_vesting_tokens(&caller, &remaining_attributes, remaining_amount);
+        }
+
+        let base_asset = self.base_asset_token_id().get();
+        self.send()
+            .esdt_local_mint(&base_asset, 0, &unlocked_amount);
+        self.send()
+            .direct_esdt(&caller, &base_asset, 0, &unlocked_amount);
+
+        EsdtTokenPayment::new(base_asset, 0, unlocked_amount)
+    }
+
+    /// Migrates old factory tokens to VESTING tokens. The unlock milestones are replaced by
+    /// a linear schedule from the current epoch to the last milestone,
+    /// with the cliff at the first milestone that was not reached yet.
+    ///
+    /// Tokens with milestones that were already reached are rejected,
+    /// these amounts have to be unlocked through the old factory first.
+    ///
+    /// Expected payments: Any number of old factory tokens
+    ///
+    /// Output payments: VESTING tokens, one for each old token
+    #[payable("*")]
+    #[endpoint(migrateOldTokensToVesting)]
+    fn migrate_old_tokens_to_vesting(&self) -> MultiValueEncoded<EsdtTokenPayment> {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let is_smart_contract_address = self.blockchain().is_smart_contract(&caller);
+        if !is_smart_contract_address {
+            self.require_old_tokens_energy_was_updated(&caller);
+        }
+
+        let payments = self.get_non_empty_payments();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let legacy_token_id = self.legacy_locked_token_id().get();
+
+        let mut output_payments = MultiValueEncoded::new();
+        self.update_energy(&caller, |energy| {
+            for payment in &payments {
+                require!(
+                    payment.token_identifier == legacy_token_id,
+                    INVALID_PAYMENTS_ERR_MSG
+                );
+
+                let new_token = self.migrate_single_old_token_to_vesting(
+                    &caller,
+                    payment,
+                    is_smart_contract_address,
+                    current_epoch,
+                    energy,
+                );
+                output_payments.push(new_token);
+            }
+        });
+
+        output_payments
+    }
+
+    fn migrate_single_old_token_to_vesting(
+        &self,
+        caller: &ManagedAddress,
+        payment: EsdtTokenPayment,
+        is_smart_contract_address: bool,
+        current_epoch: Epoch,
+        energy: &mut Energy<Self::Api>,
+    ) -> EsdtTokenPayment {
+        let attributes = self.decode_legacy_token(&payment.token_identifier, payment.token_nonce);
+        self.send().esdt_local_burn(
+            &payment.token_identifier,
+            payment.token_nonce,
+            &payment.amount,
+        );
+
+        let unlock_epoch_amount_pairs = attributes.get_unlock_amounts_per_epoch(&payment.amount);
+        let vesting_attributes =
+            self.get_vesting_schedule_for_old_token(&unlock_epoch_amount_pairs, current_epoch);
+        if !is_smart_contract_address {
+            for epoch_amount_pair in &unlock_epoch_amount_pairs.pairs {
+                energy.update_after_unlock_any(
+                    &epoch_amount_pair.amount,
+                    epoch_amount_pair.epoch,
+                    current_epoch,
+                );
+            }
+        }
+        energy.add_after_token_lock(
+            &payment.amount,
+            vesting_attributes.get_average_unlock_epoch(),
+            current_epoch,
+        );
+
+        self.mint_and_send_vesting_tokens(caller, &vesting_attributes, payment.amount)
+    }
+
+    fn get_vesting_schedule_for_old_token(
+        &self,
+        unlock_epoch_amount_pairs: &UnlockEpochAmountPairs<Self::Api>,
+        current_epoch: Epoch,
+    ) -> VestingTokenAttributes {
+        let mut cliff_epoch = Epoch::MAX;
+        let mut end_epoch = 0;
+        for epoch_amount_pair in &unlock_epoch_amount_pairs.pairs {
+            if epoch_amount_pair.amount == 0 {
+                continue;
+            }
+
+            require!(
+                epoch_amount_pair.epoch > current_epoch,
+                REACHED_MILESTONES_ERR_MSG
+            );
+
+            cliff_epoch = core::cmp::min(cliff_epoch, epoch_amount_pair.epoch);
+            end_epoch = core::cmp::max(end_epoch, epoch_amount_pair.epoch);
+        }
+
+        require!(end_epoch > current_epoch, "Tokens can be unlocked already");
+
+        VestingTokenAttributes::new(current_epoch, cliff_epoch, end_epoch)
+    }
+
+    fn mint_and_send_vesting_tokens(
+        &self,
+        to: &ManagedAddress,
+        attributes: &VestingTokenAttributes,
+        amount: BigUint,
+    ) -> EsdtTokenPayment {
+        let vesting_token_mapper = self.vesting_token();
+        let token_nonce = self.get_or_create_nonce_for_attributes(
+            &vesting_token_mapper,
+            vesting_token_mapper.get_token_id().as_managed_buffer(),
+            attributes,
+        );
+
+        vesting_token_mapper.nft_add_quantity_and_send(to, token_nonce, amount)
+    }
+
+    #[view(getVestingUnlockableAmount)]
+    fn get_vesting_unlockable_amount(&self, token_nonce: u64, token_amount: BigUint) -> BigUint {
+        let attributes: VestingTokenAttributes =
+            self.vesting_token().get_token_attributes(token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        attributes.get_unlockable_amount(&token_amount, current_epoch)
+    }
+
+    #[view(getVestingTokenId)]
+    #[storage_mapper("vestingToken")]
+    fn vesting_token(&self) -> NonFungibleTokenMapper;
+}
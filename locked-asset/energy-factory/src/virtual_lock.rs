@@ -47,11 +47,13 @@ pub trait VirtualLockModule:
             "Unlock epoch must be greater than the current epoch"
         );
 
+        let energy_multiplier = self.get_energy_multiplier(lock_epochs);
         let locked_tokens =
             self.update_energy(&energy_address, |energy: &mut Energy<Self::Api>| {
                 self.lock_base_asset(
                     EsdtTokenPayment::new(token_id, 0, amount),
                     unlock_epoch,
+                    energy_multiplier,
                     current_epoch,
                     energy,
                 )
@@ -3,7 +3,9 @@
 pub mod unbond_sc_mock;
 
 use energy_factory::{
-    energy::EnergyModule, unlock_with_penalty::UnlockWithPenaltyModule, unstake::UnstakeModule,
+    energy::EnergyModule, energy_delegation::EnergyDelegationModule,
+    lock_options_endpoints::LockOptionsEndpointsModule, perpetual_lock::PerpetualLockModule,
+    unlock_with_penalty::UnlockWithPenaltyModule, unstake::UnstakeModule, vesting::VestingModule,
     SimpleLockEnergy,
 };
 use multiversx_sc::{
@@ -30,6 +32,7 @@ pub const FIRST_UPDATED_BLOCK_NONCE: u64 = 2_286_815; // first nonce for updated
 pub static BASE_ASSET_TOKEN_ID: &[u8] = b"MEX-123456";
 pub static LOCKED_TOKEN_ID: &[u8] = b"LOCKED-123456";
 pub static LEGACY_LOCKED_TOKEN_ID: &[u8] = b"LEGACY-123456";
+pub static VESTING_TOKEN_ID: &[u8] = b"VESTING-123456";
 
 pub static LOCK_OPTIONS: &[u64] = &[EPOCHS_IN_YEAR, 2 * EPOCHS_IN_YEAR, 4 * EPOCHS_IN_YEAR]; // 1, 2 or 4 years
 pub static PENALTY_PERCENTAGES: &[u64] = &[4_000, 6_000, 8_000];
@@ -81,6 +84,8 @@ where
 
                 sc.locked_token()
                     .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+                sc.vesting_token()
+                    .set_token_id(managed_token_id!(VESTING_TOKEN_ID));
                 sc.set_paused(false);
                 sc.set_token_unstake_address(managed_address!(token_unstake_wrapper.address_ref()));
             })
@@ -102,6 +107,16 @@ where
                 EsdtLocalRole::Transfer,
             ],
         );
+        b_mock.set_esdt_local_roles(
+            sc_wrapper.address_ref(),
+            VESTING_TOKEN_ID,
+            &[
+                EsdtLocalRole::NftCreate,
+                EsdtLocalRole::NftAddQuantity,
+                EsdtLocalRole::NftBurn,
+                EsdtLocalRole::Transfer,
+            ],
+        );
         b_mock.set_esdt_local_roles(
             sc_wrapper.address_ref(),
             LEGACY_LOCKED_TOKEN_ID,
@@ -185,6 +200,43 @@ where
         )
     }
 
+    pub fn enable_perpetual_lock(
+        &mut self,
+        caller: &Address,
+        token_nonce: u64,
+        amount: u64,
+        lock_epochs: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.enable_perpetual_lock(lock_epochs);
+            },
+        )
+    }
+
+    pub fn disable_perpetual_lock(
+        &mut self,
+        caller: &Address,
+        token_nonce: u64,
+        amount: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.disable_perpetual_lock();
+            },
+        )
+    }
+
     pub fn unlock(&mut self, caller: &Address, token_nonce: u64, amount: u64) -> TxResult {
         self.b_mock.execute_esdt_transfer(
             caller,
@@ -211,6 +263,25 @@ where
         )
     }
 
+    pub fn unlock_early_partial(
+        &mut self,
+        caller: &Address,
+        token_nonce: u64,
+        payment_amount: u64,
+        unlock_amount: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                sc.unlock_early_partial(managed_biguint!(unlock_amount));
+            },
+        )
+    }
+
     pub fn reduce_lock_period(
         &mut self,
         caller: &Address,
@@ -230,6 +301,64 @@ where
         )
     }
 
+    pub fn set_energy_multiplier(&mut self, lock_epochs: u64, energy_multiplier: u64) -> TxResult {
+        self.b_mock
+            .execute_tx(&self.owner, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                sc.set_lock_option_energy_multiplier(lock_epochs, energy_multiplier);
+            })
+    }
+
+    pub fn lock_vesting(
+        &mut self,
+        caller: &Address,
+        amount: u64,
+        start_epoch: u64,
+        cliff_epoch: u64,
+        end_epoch: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.lock_tokens_vesting(start_epoch, cliff_epoch, end_epoch, OptionalValue::None);
+            },
+        )
+    }
+
+    pub fn unlock_vested(&mut self, caller: &Address, token_nonce: u64, amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            VESTING_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.unlock_vested_tokens();
+            },
+        )
+    }
+
+    pub fn migrate_to_vesting(
+        &mut self,
+        caller: &Address,
+        token_nonce: u64,
+        amount: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LEGACY_LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                let _ = sc.migrate_old_tokens_to_vesting();
+            },
+        )
+    }
+
     pub fn get_penalty_amount(
         &mut self,
         token_amount: u64,
@@ -262,6 +391,19 @@ where
 
         result
     }
+
+    pub fn get_effective_energy(&mut self, user: &Address) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result =
+                    sc.get_effective_energy_amount_for_user(managed_address!(user));
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
 }
 
 pub fn to_rust_biguint(
@@ -1,4 +1,4 @@
-use multiversx_sc::io::load_endpoint_args;
+use multiversx_sc::io::{finish_multi, load_endpoint_args};
 use multiversx_sc::{
     contract_base::{CallableContract, ContractBase},
     types::ManagedAddress,
@@ -7,6 +7,9 @@ use multiversx_sc_scenario::DebugApi;
 
 static DEPOSIT_USER_TOKENS_FN_NAME: &str = "depositUserTokens";
 static DEPOSIT_FEES_FN_NAME: &str = "depositFees";
+static GET_FEES_BURN_PERCENTAGE_FN_NAME: &str = "getFeesBurnPercentage";
+
+pub const FEES_BURN_PERCENTAGE: u64 = 2_000;
 
 #[derive(Clone)]
 pub struct UnbondScMock {}
@@ -20,6 +23,9 @@ impl CallableContract for UnbondScMock {
         if fn_name == DEPOSIT_USER_TOKENS_FN_NAME {
             self.send_to_user();
             true
+        } else if fn_name == GET_FEES_BURN_PERCENTAGE_FN_NAME {
+            finish_multi::<DebugApi, _>(&FEES_BURN_PERCENTAGE);
+            true
         } else {
             fn_name == DEPOSIT_FEES_FN_NAME
         }
@@ -0,0 +1,254 @@
+mod energy_factory_setup;
+
+use energy_factory::lock_batch::{LockBatchEntry, LockBatchModule, OngoingLockBatch};
+use energy_factory_setup::*;
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{ManagedVec, MultiValueEncoded, OperationCompletionStatus},
+};
+use sc_whitelist_module::SCWhitelistModule;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
+
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id_wrapped, rust_biguint, DebugApi,
+};
+
+#[test]
+fn lock_batch_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let owner = setup.owner.clone();
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    // not whitelisted
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &setup.sc_wrapper,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            &rust_biguint!(3_000),
+            |sc| {
+                let mut batch = MultiValueEncoded::new();
+                batch.push((managed_address!(&owner), managed_biguint!(1_000)).into());
+                batch.push((managed_address!(&second_user), managed_biguint!(2_000)).into());
+
+                let _ = sc.lock_tokens_batch(LOCK_OPTIONS[0], batch);
+            },
+        )
+        .assert_user_error("Item not whitelisted");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.sc_whitelist_addresses()
+                .add(&managed_address!(&first_user));
+        })
+        .assert_ok();
+
+    // payment does not match the batch
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &setup.sc_wrapper,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            &rust_biguint!(2_500),
+            |sc| {
+                let mut batch = MultiValueEncoded::new();
+                batch.push((managed_address!(&owner), managed_biguint!(1_000)).into());
+                batch.push((managed_address!(&second_user), managed_biguint!(2_000)).into());
+
+                let _ = sc.lock_tokens_batch(LOCK_OPTIONS[0], batch);
+            },
+        )
+        .assert_user_error("Payment amount does not match the batch total");
+
+    // nothing to continue
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.continue_lock_tokens_batch();
+        })
+        .assert_user_error("No batch lock in progress");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &setup.sc_wrapper,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            &rust_biguint!(3_000),
+            |sc| {
+                let mut batch = MultiValueEncoded::new();
+                batch.push((managed_address!(&owner), managed_biguint!(1_000)).into());
+                batch.push((managed_address!(&second_user), managed_biguint!(2_000)).into());
+
+                let run_result = sc.lock_tokens_batch(LOCK_OPTIONS[0], batch);
+                assert_eq!(run_result, OperationCompletionStatus::Completed);
+            },
+        )
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    let expected_attributes = LockedTokenAttributes::<DebugApi> {
+        original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+        original_token_nonce: 0,
+        unlock_epoch,
+        perpetual_lock_epochs: 0,
+        energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+    };
+    setup.b_mock.check_nft_balance(
+        &owner,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(1_000),
+        Some(&expected_attributes),
+    );
+    setup.b_mock.check_nft_balance(
+        &second_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(2_000),
+        Some(&expected_attributes),
+    );
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 3_000),
+    );
+
+    assert_eq!(
+        setup.get_user_energy(&owner),
+        rust_biguint!(1_000) * (unlock_epoch - current_epoch)
+    );
+    assert_eq!(
+        setup.get_user_energy(&second_user),
+        rust_biguint!(2_000) * (unlock_epoch - current_epoch)
+    );
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+}
+
+#[test]
+fn cancel_lock_batch_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let owner = setup.owner.clone();
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    // nothing to cancel
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.cancel_lock_tokens_batch(OptionalValue::None);
+        })
+        .assert_user_error("No batch lock in progress");
+
+    // simulate a batch interrupted after its first entry
+    setup.b_mock.set_esdt_balance(
+        setup.sc_wrapper.address_ref(),
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(2_000),
+    );
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut entries = ManagedVec::new();
+            entries.push(LockBatchEntry {
+                destination: managed_address!(&owner),
+                amount: managed_biguint!(1_000),
+            });
+            entries.push(LockBatchEntry {
+                destination: managed_address!(&second_user),
+                amount: managed_biguint!(2_000),
+            });
+
+            sc.ongoing_batch(&managed_address!(&first_user))
+                .set(&OngoingLockBatch {
+                    unlock_epoch: to_start_of_month(current_epoch + LOCK_OPTIONS[0]),
+                    energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+                    entries,
+                    entry_index: 1,
+                });
+        })
+        .assert_ok();
+
+    // the ongoing batch does not block other callers
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.sc_whitelist_addresses()
+                .add(&managed_address!(&second_user));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &second_user,
+            &setup.sc_wrapper,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let mut batch = MultiValueEncoded::new();
+                batch.push((managed_address!(&owner), managed_biguint!(1_000)).into());
+
+                let run_result = sc.lock_tokens_batch(LOCK_OPTIONS[0], batch);
+                assert_eq!(run_result, OperationCompletionStatus::Completed);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&second_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.continue_lock_tokens_batch();
+        })
+        .assert_user_error("No batch lock in progress");
+
+    setup
+        .b_mock
+        .execute_tx(&second_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.cancel_lock_tokens_batch(OptionalValue::Some(managed_address!(&first_user)));
+        })
+        .assert_user_error("Only the batch creator or the owner may cancel it");
+
+    // the owner may cancel it, the remaining entries are refunded to the creator
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let refund_amount =
+                sc.cancel_lock_tokens_batch(OptionalValue::Some(managed_address!(&first_user)));
+            assert_eq!(refund_amount, managed_biguint!(2_000));
+        })
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE + 2_000),
+    );
+    setup.b_mock.check_esdt_balance(
+        setup.sc_wrapper.address_ref(),
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+    assert_eq!(setup.get_user_energy(&second_user), rust_biguint!(0));
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.continue_lock_tokens_batch();
+        })
+        .assert_user_error("No batch lock in progress");
+}
@@ -11,7 +11,7 @@ use energy_factory::{
 use energy_factory_setup::*;
 use multiversx_sc::types::{BigInt, ManagedVec, MultiValueEncoded};
 use multiversx_sc_modules::pause::PauseModule;
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id_wrapped, rust_biguint, DebugApi,
@@ -119,6 +119,8 @@ fn extend_lock_period_old_token_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -227,6 +229,8 @@ fn min_period_migrated_token_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -345,6 +349,8 @@ fn min_period_migrated_token_test2() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -462,6 +468,8 @@ fn check_initial_old_unlock_schedule_decode_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
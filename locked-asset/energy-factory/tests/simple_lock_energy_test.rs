@@ -1,8 +1,16 @@
 mod energy_factory_setup;
 
-use energy_factory::energy::EnergyModule;
+use energy_factory::energy::{Energy, EnergyModule};
+use energy_factory::energy_delegation::{EnergyDelegationModule, MAX_ENERGY_CONSUMERS};
+use energy_factory::energy_history::EnergyHistoryModule;
+use energy_factory::migration::SimpleLockMigrationModule;
+use energy_factory::unlock_with_penalty::{LockReductionPreview, UnlockWithPenaltyModule};
+use energy_factory::unstake::UnstakeModule;
 use energy_factory_setup::*;
-use simple_lock::locked_token::LockedTokenAttributes;
+use multiversx_sc::types::{BigInt, MultiValueEncoded};
+use simple_lock::locked_token::{
+    LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER, PERPETUAL_LOCK_UNLOCK_EPOCH,
+};
 
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id_wrapped, rust_biguint, DebugApi,
@@ -66,6 +74,8 @@ fn lock_ok() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: first_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -107,6 +117,8 @@ fn lock_ok() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: second_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -167,6 +179,171 @@ fn unlock_early_test() {
     assert_eq!(actual_energy, expected_energy);
 }
 
+#[test]
+fn unlock_early_partial_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+    let quarter_balance = half_balance / 2;
+
+    let current_epoch = 0;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    setup
+        .unlock_early_partial(&first_user, 1, half_balance, half_balance + 1)
+        .assert_user_error("Invalid unlock amount");
+
+    let penalty_amount = setup.get_penalty_amount(quarter_balance, LOCK_OPTIONS[0], 0);
+    setup
+        .unlock_early_partial(&first_user, 1, half_balance, quarter_balance)
+        .assert_ok();
+
+    let received_token_amount = rust_biguint!(quarter_balance) - penalty_amount;
+    let expected_balance = received_token_amount + half_balance;
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, BASE_ASSET_TOKEN_ID, &expected_balance);
+
+    // remaining tokens keep their unlock epoch
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(half_balance - quarter_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+        }),
+    );
+
+    let expected_energy = rust_biguint!(half_balance - quarter_balance) * unlock_epoch;
+    let actual_energy = setup.get_user_energy(&first_user);
+    assert_eq!(actual_energy, expected_energy);
+}
+
+#[test]
+fn lock_reduction_preview_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let current_epoch = 0;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[2],
+        )
+        .assert_ok();
+
+    let mut expected_energy_decrease = rust_biguint!(0);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let previews: Vec<LockReductionPreview<DebugApi>> = sc
+                .get_lock_reduction_preview(1, managed_biguint!(half_balance))
+                .into_iter()
+                .collect();
+            assert_eq!(previews.len(), 3);
+
+            // full unlock
+            let full_unlock_preview = &previews[0];
+            let full_unlock_penalty =
+                sc.calculate_penalty_amount(&managed_biguint!(half_balance), LOCK_OPTIONS[2], 0);
+            let mut expected_energy_delta = BigInt::<DebugApi>::zero();
+            expected_energy_delta -= BigInt::from(managed_biguint!(half_balance) * LOCK_OPTIONS[2]);
+            assert_eq!(full_unlock_preview.new_lock_epochs, 0);
+            assert_eq!(full_unlock_preview.penalty_amount, full_unlock_penalty);
+            assert_eq!(
+                full_unlock_preview.received_amount,
+                managed_biguint!(half_balance) - full_unlock_penalty.clone()
+            );
+            assert_eq!(full_unlock_preview.energy_delta, expected_energy_delta);
+            let expected_burn_amount =
+                full_unlock_penalty.clone() * unbond_sc_mock::FEES_BURN_PERCENTAGE / 10_000u64;
+            assert_eq!(full_unlock_preview.burn_amount, expected_burn_amount);
+            assert_eq!(
+                full_unlock_preview.fees_collector_amount,
+                full_unlock_penalty - expected_burn_amount
+            );
+
+            // reduce to the shortest lock option
+            let reduce_preview = &previews[1];
+            let reduce_penalty = sc.calculate_penalty_amount(
+                &managed_biguint!(half_balance),
+                LOCK_OPTIONS[2],
+                LOCK_OPTIONS[0],
+            );
+            let received_amount = managed_biguint!(half_balance) - reduce_penalty.clone();
+            expected_energy_delta += BigInt::from(received_amount.clone() * LOCK_OPTIONS[0]);
+            assert_eq!(reduce_preview.new_lock_epochs, LOCK_OPTIONS[0]);
+            assert_eq!(reduce_preview.penalty_amount, reduce_penalty);
+            assert_eq!(reduce_preview.received_amount, received_amount);
+            assert_eq!(reduce_preview.energy_delta, expected_energy_delta);
+
+            assert_eq!(previews[2].new_lock_epochs, LOCK_OPTIONS[1]);
+
+            expected_energy_decrease = to_rust_biguint(reduce_preview.energy_delta.magnitude());
+        })
+        .assert_ok();
+
+    // preview matches the actual execution
+    let energy_before = setup.get_user_energy(&first_user);
+    setup
+        .reduce_lock_period(&first_user, 1, half_balance, LOCK_OPTIONS[0])
+        .assert_ok();
+    let energy_after = setup.get_user_energy(&first_user);
+
+    assert_eq!(energy_before - energy_after, expected_energy_decrease);
+
+    // no preview for perpetual tokens
+    let remaining_amount = setup
+        .b_mock
+        .get_esdt_balance(&first_user, LOCKED_TOKEN_ID, 2);
+    let remaining_amount = u64::try_from(remaining_amount).unwrap();
+    setup
+        .enable_perpetual_lock(&first_user, 2, remaining_amount / 2, LOCK_OPTIONS[1])
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let previews = sc.get_lock_reduction_preview(3, managed_biguint!(remaining_amount / 2));
+            assert!(previews.is_empty());
+        })
+        .assert_ok();
+
+    // no preview without the token unstake SC
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.token_unstake_sc_address().clear();
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let previews = sc.get_lock_reduction_preview(2, managed_biguint!(remaining_amount / 2));
+            assert!(previews.is_empty());
+        })
+        .assert_ok();
+}
+
 #[test]
 fn multiple_early_unlocks_same_week_test() {
     let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
@@ -208,6 +385,8 @@ fn multiple_early_unlocks_same_week_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 360,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -285,6 +464,8 @@ fn reduce_lock_period_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: expected_new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -298,6 +479,8 @@ fn reduce_lock_period_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 720,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -358,6 +541,8 @@ fn extend_locking_period_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: new_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -417,6 +602,8 @@ fn test_same_token_nonce() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 360,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
@@ -453,3 +640,502 @@ fn energy_deplete_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn energy_history_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let first_unlock_epoch = LOCK_OPTIONS[0];
+
+    let second_lock_epoch = 10;
+    setup.b_mock.set_block_epoch(second_lock_epoch);
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let second_unlock_epoch = to_start_of_month(second_lock_epoch + LOCK_OPTIONS[0]);
+
+    setup.b_mock.set_block_epoch(20);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_user_energy_at_epoch(managed_address!(&first_user), 0),
+                managed_biguint!(half_balance) * first_unlock_epoch
+            );
+            assert_eq!(
+                sc.get_user_energy_at_epoch(managed_address!(&first_user), 5),
+                managed_biguint!(half_balance) * (first_unlock_epoch - 5)
+            );
+            assert_eq!(
+                sc.get_user_energy_at_epoch(managed_address!(&second_user), 5),
+                managed_biguint!(0)
+            );
+
+            assert_eq!(
+                sc.get_total_energy_at_epoch(5),
+                managed_biguint!(half_balance) * (first_unlock_epoch - 5)
+            );
+            assert_eq!(
+                sc.get_total_energy_at_epoch(15),
+                managed_biguint!(half_balance) * (first_unlock_epoch - 15)
+                    + managed_biguint!(half_balance) * (second_unlock_epoch - 15)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let _ = sc.get_total_energy_at_epoch(21);
+        })
+        .assert_user_error("Epoch is in the future");
+}
+
+#[test]
+fn total_energy_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    // simulate energy set before the total energy tracking was introduced
+    let second_user_locked_tokens = 1_000u64;
+    let second_user_energy = 100_000u64;
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.user_energy(&managed_address!(&second_user))
+                .set(Energy::new(
+                    BigInt::from(managed_biguint!(second_user_energy)),
+                    0,
+                    managed_biguint!(second_user_locked_tokens),
+                ));
+        })
+        .assert_ok();
+
+    let current_epoch = 10;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_total_energy_amount(),
+                managed_biguint!(half_balance) * (LOCK_OPTIONS[0] - current_epoch)
+            );
+            assert_eq!(sc.get_total_locked_tokens(), managed_biguint!(half_balance));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&first_user));
+            users.push(managed_address!(&second_user));
+            sc.add_users_to_total_energy(users);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_total_energy_amount(),
+                managed_biguint!(half_balance) * (LOCK_OPTIONS[0] - current_epoch)
+                    + managed_biguint!(
+                        second_user_energy - second_user_locked_tokens * current_epoch
+                    )
+            );
+            assert_eq!(
+                sc.get_total_locked_tokens(),
+                managed_biguint!(half_balance + second_user_locked_tokens)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn energy_delegation_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let quarter_balance = USER_BALANCE / 4;
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            quarter_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            quarter_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let single_lock_energy = rust_biguint!(quarter_balance) * LOCK_OPTIONS[0];
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.delegate_energy(managed_address!(&first_user));
+        })
+        .assert_user_error("Invalid delegate address");
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.delegate_energy(managed_address!(&second_user));
+        })
+        .assert_ok();
+
+    assert_eq!(setup.get_effective_energy(&first_user), rust_biguint!(0));
+    assert_eq!(
+        setup.get_effective_energy(&second_user),
+        &single_lock_energy * 2u32
+    );
+
+    // own energy is unchanged, and further changes follow the delegation
+    assert_eq!(setup.get_user_energy(&first_user), single_lock_energy);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            quarter_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    assert_eq!(setup.get_effective_energy(&first_user), rust_biguint!(0));
+    assert_eq!(
+        setup.get_effective_energy(&second_user),
+        &single_lock_energy * 3u32
+    );
+
+    setup.b_mock.set_block_epoch(10);
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.undelegate_energy();
+        })
+        .assert_ok();
+
+    let epoch_10_single_lock_energy = rust_biguint!(quarter_balance) * (LOCK_OPTIONS[0] - 10);
+    assert_eq!(
+        setup.get_effective_energy(&first_user),
+        &epoch_10_single_lock_energy * 2u32
+    );
+    assert_eq!(
+        setup.get_effective_energy(&second_user),
+        epoch_10_single_lock_energy
+    );
+
+    // the snapshot of the delegation period is unchanged
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_effective_energy_at_epoch(managed_address!(&first_user), 5),
+                managed_biguint!(0)
+            );
+            assert_eq!(
+                sc.get_effective_energy_at_epoch(managed_address!(&second_user), 5),
+                managed_biguint!(quarter_balance) * (LOCK_OPTIONS[0] - 5) * 3u32
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.undelegate_energy();
+        })
+        .assert_user_error("Energy is not delegated");
+}
+
+#[test]
+fn effective_energy_at_epoch_untracked_user_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+
+    // simulate energy set before the effective energy history was introduced
+    let legacy_locked_tokens = 1_000u64;
+    let legacy_energy = 100_000u64;
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.user_energy(&managed_address!(&first_user))
+                .set(Energy::new(
+                    BigInt::from(managed_biguint!(legacy_energy)),
+                    0,
+                    managed_biguint!(legacy_locked_tokens),
+                ));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(10);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_effective_energy_at_epoch(managed_address!(&first_user), 5),
+                managed_biguint!(legacy_energy - legacy_locked_tokens * 5)
+            );
+        })
+        .assert_ok();
+
+    // locking after the snapshot does not change the energy at the snapshot epoch
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            USER_BALANCE / 2,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_effective_energy_at_epoch(managed_address!(&first_user), 5),
+                managed_biguint!(legacy_energy - legacy_locked_tokens * 5)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn energy_consumers_limit_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+
+    let mut consumer_addresses = Vec::new();
+    for _ in 0..=MAX_ENERGY_CONSUMERS {
+        let consumer = setup.b_mock.create_sc_account(
+            &rust_biguint!(0),
+            Some(&owner),
+            energy_factory::contract_obj,
+            "energy consumer",
+        );
+        consumer_addresses.push(consumer.address_ref().clone());
+    }
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut consumers = MultiValueEncoded::new();
+            for address in &consumer_addresses {
+                consumers.push(managed_address!(address));
+            }
+            sc.add_energy_consumers(consumers);
+        })
+        .assert_user_error("Too many energy consumers");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut consumers = MultiValueEncoded::new();
+            for address in &consumer_addresses[..MAX_ENERGY_CONSUMERS] {
+                consumers.push(managed_address!(address));
+            }
+            sc.add_energy_consumers(consumers);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn perpetual_lock_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let mut current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+
+    // lock period may not be reduced
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance, LOCK_OPTIONS[0])
+        .assert_user_error("Lock period may not be shorter than the remaining one");
+
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance, LOCK_OPTIONS[1])
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        2,
+        &rust_biguint!(half_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: PERPETUAL_LOCK_UNLOCK_EPOCH,
+            perpetual_lock_epochs: LOCK_OPTIONS[1],
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+        }),
+    );
+
+    let perpetual_energy = rust_biguint!(half_balance) * LOCK_OPTIONS[1];
+    assert_eq!(setup.get_user_energy(&first_user), perpetual_energy);
+
+    // energy does not decay while in perpetual lock mode
+    current_epoch += EPOCHS_IN_YEAR;
+    setup.b_mock.set_block_epoch(current_epoch);
+    assert_eq!(setup.get_user_energy(&first_user), perpetual_energy);
+
+    setup
+        .unlock_early(&first_user, 2, half_balance)
+        .assert_user_error("Perpetual lock must be disabled first");
+    setup
+        .unlock(&first_user, 2, half_balance)
+        .assert_user_error("Cannot unlock yet");
+
+    // disable perpetual lock - normal decay starts
+    setup
+        .disable_perpetual_lock(&first_user, 2, half_balance)
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[1]);
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        3,
+        &rust_biguint!(half_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+        }),
+    );
+
+    let mut expected_energy = rust_biguint!(half_balance) * (unlock_epoch - current_epoch);
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    current_epoch += 10;
+    setup.b_mock.set_block_epoch(current_epoch);
+    expected_energy -= rust_biguint!(half_balance) * 10u64;
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    setup
+        .disable_perpetual_lock(&first_user, 3, half_balance)
+        .assert_user_error("Perpetual lock not enabled");
+}
+
+#[test]
+fn energy_multiplier_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+    let energy_multiplier = 15_000; // 1.5x
+
+    let mut current_epoch = 0;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    setup
+        .set_energy_multiplier(LOCK_OPTIONS[2], DEFAULT_ENERGY_MULTIPLIER - 1)
+        .assert_user_error("Invalid energy multiplier");
+    setup
+        .set_energy_multiplier(LOCK_OPTIONS[2] + 1, energy_multiplier)
+        .assert_user_error("Invalid lock choice");
+    setup
+        .set_energy_multiplier(LOCK_OPTIONS[2], energy_multiplier)
+        .assert_ok();
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[2],
+        )
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[2]);
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(half_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier,
+        }),
+    );
+
+    let weighted_amount =
+        rust_biguint!(half_balance) * energy_multiplier / DEFAULT_ENERGY_MULTIPLIER;
+    let mut expected_energy = &weighted_amount * unlock_epoch;
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    // energy decays with the weighted amount
+    current_epoch += 10;
+    setup.b_mock.set_block_epoch(current_epoch);
+    expected_energy -= &weighted_amount * 10u64;
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    // other lock options are not affected
+    let second_user = setup.second_user.clone();
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let other_unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    assert_eq!(
+        setup.get_user_energy(&second_user),
+        rust_biguint!(half_balance) * (other_unlock_epoch - current_epoch)
+    );
+
+    // unlocking removes the weighted energy
+    current_epoch = unlock_epoch;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup.unlock(&first_user, 1, half_balance).assert_ok();
+
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+}
@@ -21,7 +21,7 @@ use multiversx_sc_scenario::{
 };
 use simple_lock::{
     basic_lock_unlock::BasicLockUnlock,
-    locked_token::{LockedTokenAttributes, LockedTokenModule},
+    locked_token::{LockedTokenAttributes, LockedTokenModule, DEFAULT_ENERGY_MULTIPLIER},
 };
 
 use multiversx_sc_scenario::{managed_token_id_wrapped, rust_biguint, DebugApi};
@@ -88,6 +88,8 @@ fn token_merging_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: expected_merged_token_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -96,6 +98,81 @@ fn token_merging_test() {
     assert_eq!(expected_energy, actual_energy);
 }
 
+#[test]
+fn token_merging_different_multipliers_test() {
+    let _ = DebugApi::dummy();
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+
+    setup
+        .set_energy_multiplier(LOCK_OPTIONS[0], 15_000)
+        .assert_ok();
+
+    let first_token_amount = 400_000;
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            first_token_amount,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let second_token_amount = 100_000;
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            second_token_amount,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+
+    // 400_000 * 1.5 * 360 + 100_000 * 720
+    let energy_before = setup.get_user_energy(&first_user);
+    assert_eq!(energy_before, rust_biguint!(288_000_000));
+
+    let payments = [
+        TxTokenTransfer {
+            token_identifier: LOCKED_TOKEN_ID.to_vec(),
+            nonce: 1,
+            value: rust_biguint!(first_token_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: LOCKED_TOKEN_ID.to_vec(),
+            nonce: 2,
+            value: rust_biguint!(second_token_amount),
+        },
+    ];
+    setup
+        .b_mock
+        .execute_esdt_multi_transfer(&first_user, &setup.sc_wrapper, &payments[..], |sc| {
+            let _ = sc.merge_tokens_endpoint(OptionalValue::None);
+        })
+        .assert_ok();
+
+    // averaging the multipliers would give 1.4x at epoch 450, i.e. 315_000_000 energy
+    // 288_000_000 / (500_000 * 450) = 1.28x, rounded down
+    let expected_energy_multiplier = 12_799;
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        3,
+        &rust_biguint!(first_token_amount + second_token_amount),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: 450,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: expected_energy_multiplier,
+        }),
+    );
+
+    let energy_after = setup.get_user_energy(&first_user);
+    assert_eq!(energy_after, rust_biguint!(639_950) * 450u64);
+    assert!(energy_after <= energy_before);
+}
+
 #[test]
 fn token_merging_different_years_test() {
     let _ = DebugApi::dummy();
@@ -158,6 +235,8 @@ fn token_merging_different_years_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: expected_merged_token_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -228,6 +307,8 @@ fn token_merging_different_years2_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: expected_merged_token_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -362,6 +443,8 @@ fn test_specific_tokens_merge() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 4_110,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
@@ -0,0 +1,204 @@
+mod energy_factory_setup;
+
+use common_structs::{LockedAssetTokenAttributesEx, UnlockMilestoneEx, UnlockScheduleEx};
+use energy_factory::{
+    migration::SimpleLockMigrationModule,
+    vesting::{VestingModule, VestingTokenAttributes},
+};
+use energy_factory_setup::*;
+use multiversx_sc::types::{BigInt, ManagedVec, MultiValueEncoded};
+use multiversx_sc_modules::pause::PauseModule;
+
+use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint, DebugApi};
+
+#[test]
+fn vesting_lock_and_unlock_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let vesting_amount = 1_000_000u64;
+
+    let mut current_epoch = 5;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    // invalid schedules
+    setup
+        .lock_vesting(&first_user, vesting_amount, 4, 100, 370)
+        .assert_user_error("Invalid vesting schedule");
+    setup
+        .lock_vesting(&first_user, vesting_amount, 10, 5, 370)
+        .assert_user_error("Invalid vesting schedule");
+    setup
+        .lock_vesting(
+            &first_user,
+            vesting_amount,
+            10,
+            100,
+            current_epoch + LOCK_OPTIONS[2] + 1,
+        )
+        .assert_user_error("Invalid vesting schedule");
+
+    setup
+        .lock_vesting(&first_user, vesting_amount, 10, 100, 370)
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        VESTING_TOKEN_ID,
+        1,
+        &rust_biguint!(vesting_amount),
+        Some(&VestingTokenAttributes::new(10, 100, 370)),
+    );
+
+    // 90 epochs until cliff, then 270 epochs with an average of 235
+    // (90 * 100 + 270 * 235) / 360 = 201.25, rounded up
+    let average_unlock_epoch = 202;
+    assert_eq!(
+        setup.get_user_energy(&first_user),
+        rust_biguint!(vesting_amount) * (average_unlock_epoch - current_epoch)
+    );
+
+    // nothing can be unlocked before the cliff
+    current_epoch = 50;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .unlock_vested(&first_user, 1, vesting_amount)
+        .assert_user_error("Cannot unlock yet");
+
+    // half vested
+    current_epoch = 190;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let unlockable_amount =
+                sc.get_vesting_unlockable_amount(1, managed_biguint!(vesting_amount));
+            assert_eq!(unlockable_amount, managed_biguint!(vesting_amount / 2));
+        })
+        .assert_ok();
+
+    setup
+        .unlock_vested(&first_user, 1, vesting_amount)
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - vesting_amount / 2),
+    );
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        VESTING_TOKEN_ID,
+        2,
+        &rust_biguint!(vesting_amount / 2),
+        Some(&VestingTokenAttributes::new(
+            current_epoch,
+            current_epoch,
+            370,
+        )),
+    );
+
+    // remaining tokens vest linearly from now on, with an average of 280
+    let remaining_average_unlock_epoch = 280;
+    assert_eq!(
+        setup.get_user_energy(&first_user),
+        rust_biguint!(vesting_amount / 2) * (remaining_average_unlock_epoch - current_epoch)
+    );
+
+    // fully vested
+    current_epoch = 400;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .unlock_vested(&first_user, 2, vesting_amount / 2)
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        BASE_ASSET_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE),
+    );
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+}
+
+#[test]
+fn migrate_old_tokens_to_vesting_test() {
+    let _ = DebugApi::dummy();
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+
+    let mut current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    let first_unlock_epoch = 91;
+    let second_unlock_epoch = 121;
+    let mut unlock_milestones = ManagedVec::<DebugApi, UnlockMilestoneEx>::new();
+    unlock_milestones.push(UnlockMilestoneEx {
+        unlock_percent: 40_000,
+        unlock_epoch: first_unlock_epoch,
+    });
+    unlock_milestones.push(UnlockMilestoneEx {
+        unlock_percent: 60_000,
+        unlock_epoch: second_unlock_epoch,
+    });
+    let old_token_attributes = LockedAssetTokenAttributesEx {
+        is_merged: false,
+        unlock_schedule: UnlockScheduleEx { unlock_milestones },
+    };
+    setup.b_mock.set_nft_balance(
+        &first_user,
+        LEGACY_LOCKED_TOKEN_ID,
+        FIRST_UPDATED_BLOCK_NONCE,
+        &rust_biguint!(USER_BALANCE),
+        &old_token_attributes,
+    );
+
+    // 40% * 90 + 60% * 120 = 108
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_paused(true);
+            let mut users_energy = MultiValueEncoded::new();
+            users_energy.push(
+                (
+                    managed_address!(&first_user),
+                    managed_biguint!(USER_BALANCE),
+                    BigInt::from(managed_biguint!(USER_BALANCE) * 108u64),
+                )
+                    .into(),
+            );
+            sc.set_energy_for_old_tokens(users_energy);
+            sc.set_paused(false);
+        })
+        .assert_ok();
+
+    current_epoch = 50;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .migrate_to_vesting(&first_user, FIRST_UPDATED_BLOCK_NONCE, USER_BALANCE / 2)
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        VESTING_TOKEN_ID,
+        1,
+        &rust_biguint!(USER_BALANCE / 2),
+        Some(&VestingTokenAttributes::new(
+            current_epoch,
+            first_unlock_epoch,
+            second_unlock_epoch,
+        )),
+    );
+
+    // old tokens left: 40% * 41 + 60% * 71 = 59
+    // migrated tokens: (2 * 41 * 91 + 30 * 212) / 142 = 97.34, rounded up to 98
+    assert_eq!(
+        setup.get_user_energy(&first_user),
+        rust_biguint!(USER_BALANCE / 2) * (59u64 + 98 - current_epoch)
+    );
+
+    // the first milestone was reached, so those tokens must be unlocked first
+    current_epoch = 100;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .migrate_to_vesting(&first_user, FIRST_UPDATED_BLOCK_NONCE, USER_BALANCE / 2)
+        .assert_user_error("Reached milestones must be unlocked first");
+}
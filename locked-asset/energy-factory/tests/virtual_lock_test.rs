@@ -3,7 +3,7 @@ mod energy_factory_setup;
 use energy_factory::virtual_lock::VirtualLockModule;
 use energy_factory_setup::*;
 use sc_whitelist_module::SCWhitelistModule;
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
@@ -70,6 +70,8 @@ fn virtual_lock_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 360,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
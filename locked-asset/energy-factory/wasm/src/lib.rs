@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           32
+// Endpoints:                           64
 // Async Callback:                       1
-// Total number of exported functions:  34
+// Total number of exported functions:  66
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -24,19 +24,47 @@ multiversx_sc_wasm_adapter::endpoints! {
         getLockedTokenId
         getBaseAssetTokenId
         getLegacyLockedTokenId
+        getEffectiveEnergyEntryForUser
         getEnergyEntryForUser
         getEnergyAmountForUser
+        getEnergyDelegate
+        getTotalEnergyEntry
+        getTotalEnergy
+        getTotalLockedTokens
+        getUserEnergyAtEpoch
+        getTotalEnergyAtEpoch
+        delegateEnergy
+        undelegateEnergy
+        addEnergyConsumers
+        removeEnergyConsumers
+        getEffectiveEnergyAmountForUser
+        getEffectiveEnergyAtEpoch
+        getEnergyConsumers
         addLockOptions
         getLockOptions
+        setLockOptionEnergyMultiplier
+        getLockOptionEnergyMultiplier
         unlockEarly
+        unlockEarlyPartial
         reduceLockPeriod
+        getLockReductionPreview
         getPenaltyAmount
         setTokenUnstakeAddress
         revertUnstake
         getTokenUnstakeScAddress
+        enablePerpetualLock
+        disablePerpetualLock
         setEnergyForOldTokens
+        addUsersToTotalEnergy
         updateEnergyAfterOldTokenUnlock
         migrateOldTokens
+        issueVestingToken
+        setTransferRoleVestingToken
+        lockTokensVesting
+        unlockVestedTokens
+        migrateOldTokensToVesting
+        getVestingUnlockableAmount
+        getVestingTokenId
         pause
         unpause
         isPaused
@@ -44,6 +72,10 @@ multiversx_sc_wasm_adapter::endpoints! {
         setBurnRoleLockedToken
         mergeTokens
         lockVirtual
+        lockTokensBatch
+        continueLockTokensBatch
+        cancelLockTokensBatch
+        getOngoingLockBatch
         addSCAddressToWhitelist
         removeSCAddressFromWhitelist
         isSCAddressWhitelisted
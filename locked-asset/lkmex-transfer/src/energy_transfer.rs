@@ -17,18 +17,27 @@ pub trait EnergyTransferModule:
         tokens: &PaymentsVec<Self::Api>,
     ) {
         let current_epoch = self.blockchain().get_block_epoch();
-        let mut energy = self.get_energy_entry(&from_user);
+        let mut energy = self.get_own_energy_entry(&from_user);
         for token in tokens {
             let attributes: LockedTokenAttributes<Self::Api> = self
                 .blockchain()
                 .get_token_attributes(&token.token_identifier, token.token_nonce);
+            let energy_weighted_amount = attributes.get_energy_weighted_amount(&token.amount);
+            if attributes.is_perpetual() {
+                energy.remove_after_perpetual_unlock(
+                    &energy_weighted_amount,
+                    attributes.perpetual_lock_epochs,
+                );
+                continue;
+            }
+
             require!(
                 attributes.unlock_epoch > current_epoch,
                 "Cannot transfer tokens that are unlockable"
             );
 
             energy.deplete_after_early_unlock(
-                &token.amount,
+                &energy_weighted_amount,
                 attributes.unlock_epoch,
                 current_epoch,
             );
@@ -39,19 +48,29 @@ pub trait EnergyTransferModule:
 
     fn add_energy_to_destination(&self, to_user: ManagedAddress, tokens: &PaymentsVec<Self::Api>) {
         let current_epoch = self.blockchain().get_block_epoch();
-        let mut energy = self.get_energy_entry(&to_user);
+        let mut energy = self.get_own_energy_entry(&to_user);
         for token in tokens {
             let attributes: LockedTokenAttributes<Self::Api> = self
                 .blockchain()
                 .get_token_attributes(&token.token_identifier, token.token_nonce);
-            if attributes.unlock_epoch > current_epoch {
-                energy.add_after_token_lock(&token.amount, attributes.unlock_epoch, current_epoch);
+            let energy_weighted_amount = attributes.get_energy_weighted_amount(&token.amount);
+            if attributes.is_perpetual() {
+                energy.add_after_perpetual_lock(
+                    &energy_weighted_amount,
+                    attributes.perpetual_lock_epochs,
+                );
+            } else if attributes.unlock_epoch > current_epoch {
+                energy.add_after_token_lock(
+                    &energy_weighted_amount,
+                    attributes.unlock_epoch,
+                    current_epoch,
+                );
             } else {
                 // we have to simulate depletion of energy for the new user
                 // otherwise, at unlock time, they would receive free energy
                 // due to the negative energy refund mechanism
                 let epoch_diff = current_epoch - attributes.unlock_epoch;
-                let simulated_deplete_amount = &token.amount * epoch_diff;
+                let simulated_deplete_amount = &energy_weighted_amount * epoch_diff;
                 energy.remove_energy_raw(BigUint::zero(), simulated_deplete_amount);
             }
         }
@@ -2,11 +2,13 @@
 
 multiversx_sc::imports!();
 
+pub mod wrapped_rewards;
 pub mod wrapped_token;
 
 #[multiversx_sc::contract]
 pub trait LockedTokenWrapper:
     wrapped_token::WrappedTokenModule
+    + wrapped_rewards::WrappedRewardsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + simple_lock::token_attributes::TokenAttributesModule
     + lkmex_transfer::energy_transfer::EnergyTransferModule
@@ -20,6 +22,8 @@ pub trait LockedTokenWrapper:
         self.energy_factory_address().set(&energy_factory_address);
     }
 
+    /// Wraps locked tokens. Their energy is moved to the wrapper, which claims the rewards
+    /// from the fees collector and distributes them to the wrapped token holders.
     #[payable("*")]
     #[endpoint(wrapLockedToken)]
     fn wrap_locked_token_endpoint(&self) -> EsdtTokenPayment {
@@ -30,11 +34,15 @@ pub trait LockedTokenWrapper:
             payment.token_identifier == self.get_locked_token_id(),
             "Bad payment tokens"
         );
-        self.deduct_energy_from_sender(
-            caller.clone(),
-            &ManagedVec::from_single_item(payment.clone()),
-        );
-        self.wrap_locked_token_and_send(&caller, payment)
+
+        self.claim_fees_collector_rewards();
+
+        let locked_tokens = ManagedVec::from_single_item(payment.clone());
+        self.deduct_energy_from_sender(caller.clone(), &locked_tokens);
+        self.add_energy_to_destination(self.blockchain().get_sc_address(), &locked_tokens);
+
+        let reward_info = self.start_earning_rewards(&payment);
+        self.wrap_locked_token_and_send(&caller, payment, reward_info)
     }
 
     #[payable("*")]
@@ -46,14 +54,22 @@ pub trait LockedTokenWrapper:
             "SCs cannot unwrap locked tokens"
         );
 
+        self.claim_fees_collector_rewards();
+
         let payment = self.call_value().single_esdt();
         let locked_token_id = self.get_locked_token_id();
-        let original_locked_tokens = self.unwrap_locked_token(locked_token_id, payment);
+        let (original_locked_tokens, attributes) =
+            self.unwrap_locked_token(locked_token_id, payment);
 
-        self.add_energy_to_destination(
-            caller.clone(),
-            &ManagedVec::from_single_item(original_locked_tokens.clone()),
-        );
+        let locked_tokens = ManagedVec::from_single_item(original_locked_tokens.clone());
+        let mut rewards = ManagedVec::new();
+        if let Some(reward_info) = attributes.opt_reward_info {
+            rewards = self.stop_earning_rewards(&original_locked_tokens, &reward_info);
+            self.remove_energy_from_wrapper(&locked_tokens);
+        }
+
+        self.add_energy_to_destination(caller.clone(), &locked_tokens);
+        let _ = self.send_wrapped_rewards(&caller, rewards);
 
         self.send().direct_esdt(
             &caller,
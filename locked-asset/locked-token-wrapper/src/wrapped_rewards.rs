@@ -0,0 +1,452 @@
+multiversx_sc::imports!();
+
+use common_structs::{Nonce, PaymentsVec};
+use energy_factory::token_merging::ProxyTrait as _;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use crate::wrapped_token::{
+    RewardPerShareSnapshot, TokenRewardPerShare, WrappedRewardInfo, WrappedTokenAttributes,
+};
+
+pub const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000_000_000;
+
+pub mod fees_collector_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait FeesCollectorProxy {
+        #[endpoint(claimRewards)]
+        fn claim_rewards(
+            &self,
+            opt_original_caller: OptionalValue<ManagedAddress>,
+        ) -> ManagedVec<EsdtTokenPayment>;
+
+        #[view(isPaused)]
+        fn is_paused(&self) -> bool;
+    }
+}
+
+#[multiversx_sc::module]
+pub trait WrappedRewardsModule:
+    crate::wrapped_token::WrappedTokenModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + lkmex_transfer::energy_transfer::EnergyTransferModule
+    + energy_query::EnergyQueryModule
+    + utils::UtilsModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+{
+    #[only_owner]
+    #[endpoint(setFeesCollectorAddress)]
+    fn set_fees_collector_address(&self, sc_address: ManagedAddress) {
+        self.require_sc_address(&sc_address);
+        self.fees_collector_address().set(&sc_address);
+    }
+
+    /// Claims the rewards accumulated by the given wrapped tokens,
+    /// after claiming the wrapper's own rewards from the fees collector.
+    /// Tokens wrapped before the rewards distribution start earning rewards from now on.
+    ///
+    /// Rewards are split by the energy the tokens have when the wrapper claims from
+    /// the fees collector, so the tokens' share decays like their energy.
+    /// Tokens that can be unlocked already stop earning rewards.
+    ///
+    /// Expected payments: WRAPPED tokens
+    ///
+    /// Output payments: the new WRAPPED tokens, followed by the rewards
+    #[payable("*")]
+    #[endpoint(claimWrappedRewards)]
+    fn claim_wrapped_rewards(&self) -> PaymentsVec<Self::Api> {
+        self.claim_fees_collector_rewards();
+
+        let caller = self.blockchain().get_caller();
+        let payments = self.get_non_empty_payments();
+        let locked_token_id = self.get_locked_token_id();
+
+        let mut new_wrapped_tokens = PaymentsVec::new();
+        let mut rewards = PaymentsVec::new();
+        let mut new_earning_tokens = PaymentsVec::new();
+        for payment in &payments {
+            let (locked_tokens, attributes) =
+                self.unwrap_locked_token(locked_token_id.clone(), payment);
+            match attributes.opt_reward_info {
+                Some(reward_info) => {
+                    let token_rewards = self.stop_earning_rewards(&locked_tokens, &reward_info);
+                    merge_rewards(&mut rewards, &token_rewards);
+                }
+                None => {
+                    self.require_not_unlockable(&locked_tokens);
+                    new_earning_tokens.push(locked_tokens.clone());
+                }
+            }
+
+            let reward_info = self.start_earning_rewards(&locked_tokens);
+            let new_wrapped_token =
+                self.wrap_locked_token_and_send(&caller, locked_tokens, reward_info);
+            new_wrapped_tokens.push(new_wrapped_token);
+        }
+
+        if !new_earning_tokens.is_empty() {
+            let sc_address = self.blockchain().get_sc_address();
+            self.add_energy_to_destination(sc_address, &new_earning_tokens);
+        }
+
+        let sent_rewards = self.send_wrapped_rewards(&caller, rewards);
+        new_wrapped_tokens.append_vec(sent_rewards);
+
+        new_wrapped_tokens
+    }
+
+    /// Claims the rewards for the wrapped tokens' energy from the fees collector,
+    /// and splits them between the wrapped locked token nonces by their current energy.
+    /// Locked token rewards are merged into a single position, used for paying out rewards.
+    fn claim_fees_collector_rewards(&self) {
+        let fees_collector_mapper = self.fees_collector_address();
+        if fees_collector_mapper.is_empty() {
+            return;
+        }
+
+        let mut total_energy = BigUint::zero();
+        for locked_token_nonce in self.wrapped_locked_nonces().iter() {
+            let energy_per_token = self.get_energy_per_token(locked_token_nonce);
+            total_energy += self.wrapped_supply(locked_token_nonce).get() * energy_per_token;
+        }
+        if total_energy == 0 {
+            return;
+        }
+
+        let fees_collector_address = fees_collector_mapper.get();
+        let is_paused: bool = self
+            .fees_collector_proxy(fees_collector_address.clone())
+            .is_paused()
+            .execute_on_dest_context();
+        if is_paused {
+            return;
+        }
+
+        let rewards: PaymentsVec<Self::Api> = self
+            .fees_collector_proxy(fees_collector_address)
+            .claim_rewards(OptionalValue::<ManagedAddress>::None)
+            .execute_on_dest_context();
+
+        let locked_token_id = self.get_locked_token_id();
+        let reward_tokens_mapper = self.reward_tokens();
+        for reward in &rewards {
+            for locked_token_nonce in self.wrapped_locked_nonces().iter() {
+                let energy_per_token = self.get_energy_per_token(locked_token_nonce);
+                if energy_per_token == 0 {
+                    continue;
+                }
+
+                let reward_per_share_increase =
+                    &reward.amount * energy_per_token * DIVISION_SAFETY_CONSTANT / &total_energy;
+                self.reward_per_share(locked_token_nonce, &reward.token_identifier)
+                    .update(|reward_per_share| *reward_per_share += reward_per_share_increase);
+            }
+            let _ = reward_tokens_mapper.insert(reward.token_identifier.clone());
+
+            if reward.token_identifier == locked_token_id {
+                self.add_to_locked_rewards_pool(reward);
+            }
+        }
+    }
+
+    fn add_to_locked_rewards_pool(&self, locked_rewards: EsdtTokenPayment) {
+        let pool_mapper = self.locked_rewards_pool();
+        if pool_mapper.is_empty() {
+            pool_mapper.set(&locked_rewards);
+            return;
+        }
+
+        let pool = pool_mapper.get();
+        if pool.amount == 0 || pool.token_nonce == locked_rewards.token_nonce {
+            let mut new_pool = locked_rewards;
+            new_pool.amount += pool.amount;
+            pool_mapper.set(&new_pool);
+            return;
+        }
+
+        let mut payments = PaymentsVec::from_single_item(pool);
+        payments.push(locked_rewards);
+
+        let energy_factory_address = self.energy_factory_address().get();
+        let merged_tokens: EsdtTokenPayment = self
+            .energy_factory_proxy(energy_factory_address)
+            .merge_tokens_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        pool_mapper.set(&merged_tokens);
+    }
+
+    /// Sends the rewards to the given address. Locked token rewards are paid from the
+    /// locked rewards pool, and their energy moves from the wrapper to the receiver.
+    fn send_wrapped_rewards(
+        &self,
+        to: &ManagedAddress,
+        rewards: PaymentsVec<Self::Api>,
+    ) -> PaymentsVec<Self::Api> {
+        let locked_token_id = self.get_locked_token_id();
+        let mut output_payments = PaymentsVec::new();
+        let mut locked_rewards = PaymentsVec::new();
+        for reward in &rewards {
+            if reward.token_identifier != locked_token_id {
+                output_payments.push(reward);
+                continue;
+            }
+
+            let mut pool = self.locked_rewards_pool().get();
+            pool.amount -= &reward.amount;
+            self.locked_rewards_pool().set(&pool);
+
+            let locked_reward =
+                EsdtTokenPayment::new(reward.token_identifier, pool.token_nonce, reward.amount);
+            locked_rewards.push(locked_reward.clone());
+            output_payments.push(locked_reward);
+        }
+
+        if !locked_rewards.is_empty() {
+            self.remove_energy_from_wrapper(&locked_rewards);
+            self.add_energy_to_destination(to.clone(), &locked_rewards);
+        }
+
+        if !output_payments.is_empty() {
+            self.send().direct_multi(to, &output_payments);
+        }
+
+        output_payments
+    }
+
+    /// Unlike `deduct_energy_from_sender`, tokens that can be unlocked already are accepted,
+    /// as wrapped tokens may be unwrapped at any time
+    fn remove_energy_from_wrapper(&self, tokens: &PaymentsVec<Self::Api>) {
+        let sc_address = self.blockchain().get_sc_address();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut energy = self.get_own_energy_entry(&sc_address);
+        for token in tokens {
+            let attributes: LockedTokenAttributes<Self::Api> = self
+                .blockchain()
+                .get_token_attributes(&token.token_identifier, token.token_nonce);
+            let energy_weighted_amount = attributes.get_energy_weighted_amount(&token.amount);
+            if attributes.is_perpetual() {
+                energy.remove_after_perpetual_unlock(
+                    &energy_weighted_amount,
+                    attributes.perpetual_lock_epochs,
+                );
+            } else {
+                energy.update_after_unlock_any(
+                    &energy_weighted_amount,
+                    attributes.unlock_epoch,
+                    current_epoch,
+                );
+            }
+        }
+
+        self.set_energy_in_factory(sc_address, energy);
+    }
+
+    fn require_not_unlockable(&self, locked_tokens: &EsdtTokenPayment) {
+        let attributes: LockedTokenAttributes<Self::Api> = self
+            .blockchain()
+            .get_token_attributes(&locked_tokens.token_identifier, locked_tokens.token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            attributes.is_perpetual() || attributes.unlock_epoch > current_epoch,
+            "Tokens can be unlocked already"
+        );
+    }
+
+    /// Adds the tokens to the wrapped supply of their locked token nonce,
+    /// and returns the reward info to be saved in the wrapped token attributes
+    fn start_earning_rewards(
+        &self,
+        locked_tokens: &EsdtTokenPayment,
+    ) -> WrappedRewardInfo<Self::Api> {
+        let _ = self
+            .wrapped_locked_nonces()
+            .insert(locked_tokens.token_nonce);
+        self.wrapped_supply(locked_tokens.token_nonce)
+            .update(|supply| *supply += &locked_tokens.amount);
+
+        WrappedRewardInfo {
+            reward_per_share: self.get_reward_per_share_snapshot(locked_tokens.token_nonce),
+        }
+    }
+
+    /// Removes the tokens from the wrapped supply of their locked token nonce,
+    /// and returns the rewards accumulated since the snapshot
+    fn stop_earning_rewards(
+        &self,
+        locked_tokens: &EsdtTokenPayment,
+        reward_info: &WrappedRewardInfo<Self::Api>,
+    ) -> PaymentsVec<Self::Api> {
+        let rewards = self.calculate_wrapped_rewards(
+            locked_tokens.token_nonce,
+            &locked_tokens.amount,
+            &reward_info.reward_per_share,
+        );
+
+        let supply_mapper = self.wrapped_supply(locked_tokens.token_nonce);
+        let new_supply = supply_mapper.get() - &locked_tokens.amount;
+        if new_supply == 0 {
+            supply_mapper.clear();
+            let _ = self
+                .wrapped_locked_nonces()
+                .swap_remove(&locked_tokens.token_nonce);
+        } else {
+            supply_mapper.set(&new_supply);
+        }
+
+        rewards
+    }
+
+    /// The current energy of a single token, scaled by the default energy multiplier.
+    /// The same as the token's energy in the energy factory, i.e. perpetual tokens do not decay.
+    fn get_energy_per_token(&self, locked_token_nonce: Nonce) -> u64 {
+        let attributes: LockedTokenAttributes<Self::Api> = self
+            .blockchain()
+            .get_token_attributes(&self.get_locked_token_id(), locked_token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let lock_epochs = if attributes.is_perpetual() {
+            attributes.perpetual_lock_epochs
+        } else if attributes.unlock_epoch > current_epoch {
+            attributes.unlock_epoch - current_epoch
+        } else {
+            0
+        };
+
+        lock_epochs * attributes.energy_multiplier
+    }
+
+    fn calculate_wrapped_rewards(
+        &self,
+        locked_token_nonce: Nonce,
+        wrapped_amount: &BigUint,
+        reward_per_share_snapshot: &RewardPerShareSnapshot<Self::Api>,
+    ) -> PaymentsVec<Self::Api> {
+        let mut rewards = PaymentsVec::new();
+        for token_id in self.reward_tokens().iter() {
+            let current_reward_per_share =
+                self.reward_per_share(locked_token_nonce, &token_id).get();
+            let mut initial_reward_per_share = BigUint::zero();
+            for entry in reward_per_share_snapshot {
+                if entry.token_id == token_id {
+                    initial_reward_per_share = entry.reward_per_share;
+                    break;
+                }
+            }
+
+            let reward = wrapped_amount * &(current_reward_per_share - initial_reward_per_share)
+                / DIVISION_SAFETY_CONSTANT;
+            if reward > 0 {
+                rewards.push(EsdtTokenPayment::new(token_id, 0, reward));
+            }
+        }
+
+        rewards
+    }
+
+    fn get_reward_per_share_snapshot(
+        &self,
+        locked_token_nonce: Nonce,
+    ) -> RewardPerShareSnapshot<Self::Api> {
+        let mut snapshot = RewardPerShareSnapshot::new();
+        for token_id in self.reward_tokens().iter() {
+            let reward_per_share = self.reward_per_share(locked_token_nonce, &token_id).get();
+            snapshot.push(TokenRewardPerShare {
+                token_id,
+                reward_per_share,
+            });
+        }
+
+        snapshot
+    }
+
+    /// The rewards that can be claimed for the given wrapped tokens.
+    /// Does not include the rewards not yet claimed from the fees collector.
+    #[view(getPendingWrappedRewards)]
+    fn get_pending_wrapped_rewards(
+        &self,
+        wrapped_token_nonce: u64,
+        wrapped_token_amount: BigUint,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let attributes: WrappedTokenAttributes<Self::Api> = self
+            .wrapped_token()
+            .get_token_attributes(wrapped_token_nonce);
+        let reward_info = match attributes.opt_reward_info {
+            Some(reward_info) => reward_info,
+            None => return MultiValueEncoded::new(),
+        };
+
+        let locked_token_id = self.get_locked_token_id();
+        let mut pending_rewards = MultiValueEncoded::new();
+        for mut reward in &self.calculate_wrapped_rewards(
+            attributes.locked_token_nonce,
+            &wrapped_token_amount,
+            &reward_info.reward_per_share,
+        ) {
+            if reward.token_identifier == locked_token_id {
+                reward.token_nonce = self.locked_rewards_pool().get().token_nonce;
+            }
+
+            pending_rewards.push(reward);
+        }
+
+        pending_rewards
+    }
+
+    #[proxy]
+    fn fees_collector_proxy(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> fees_collector_proxy::Proxy<Self::Api>;
+
+    #[view(getFeesCollectorAddress)]
+    #[storage_mapper("feesCollectorAddress")]
+    fn fees_collector_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// The locked token nonces of the wrapped tokens earning rewards
+    #[view(getWrappedLockedNonces)]
+    #[storage_mapper("wrappedLockedNonces")]
+    fn wrapped_locked_nonces(&self) -> UnorderedSetMapper<Nonce>;
+
+    /// The amount of wrapped tokens earning rewards, for each locked token nonce
+    #[view(getWrappedSupply)]
+    #[storage_mapper("wrappedSupply")]
+    fn wrapped_supply(&self, locked_token_nonce: Nonce) -> SingleValueMapper<BigUint>;
+
+    #[view(getRewardTokens)]
+    #[storage_mapper("rewardTokens")]
+    fn reward_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// The rewards accumulated by a single wrapped token of the given locked token nonce
+    #[view(getRewardPerShare)]
+    #[storage_mapper("rewardPerShare")]
+    fn reward_per_share(
+        &self,
+        locked_token_nonce: Nonce,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getLockedRewardsPool)]
+    #[storage_mapper("lockedRewardsPool")]
+    fn locked_rewards_pool(&self) -> SingleValueMapper<EsdtTokenPayment>;
+}
+
+fn merge_rewards<M: ManagedTypeApi>(rewards: &mut PaymentsVec<M>, new_rewards: &PaymentsVec<M>) {
+    for new_reward in new_rewards {
+        let mut found = false;
+        for i in 0..rewards.len() {
+            let mut reward = rewards.get(i);
+            if reward.token_identifier == new_reward.token_identifier {
+                reward.amount += &new_reward.amount;
+                let _ = rewards.set(i, &reward);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            rewards.push(new_reward);
+        }
+    }
+}
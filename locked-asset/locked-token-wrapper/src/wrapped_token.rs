@@ -5,9 +5,100 @@ use common_structs::Nonce;
 
 pub static WRAPPED_TOKEN_NAME: &[u8] = b"WrappedLKMEX";
 
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
-pub struct WrappedTokenAttributes {
+#[derive(
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    ManagedVecItem,
+    PartialEq,
+    Debug,
+    Clone,
+)]
+pub struct TokenRewardPerShare<M: ManagedTypeApi> {
+    pub token_id: TokenIdentifier<M>,
+    pub reward_per_share: BigUint<M>,
+}
+
+pub type RewardPerShareSnapshot<M> = ManagedVec<M, TokenRewardPerShare<M>>;
+
+/// Only holds the reward per share of the locked token nonce at the last fees collector claim,
+/// so all the tokens wrapped or claimed since then have the same attributes
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct WrappedRewardInfo<M: ManagedTypeApi> {
+    pub reward_per_share: RewardPerShareSnapshot<M>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct WrappedTokenAttributes<M: ManagedTypeApi> {
     pub locked_token_nonce: Nonce,
+    /// None for tokens wrapped before the rewards distribution was introduced,
+    /// which do not earn rewards until claimed once
+    pub opt_reward_info: Option<WrappedRewardInfo<M>>,
+}
+
+/// The reward info is not encoded at all for tokens that do not earn rewards,
+/// so tokens wrapped before the rewards distribution keep the same attributes.
+///
+/// The compact form is only used for the top-level encoding, i.e. the token attributes,
+/// as the input end is known there. The nested encoding always contains the option flag.
+impl<M: ManagedTypeApi> TopEncode for WrappedTokenAttributes<M> {
+    fn top_encode<O>(&self, output: O) -> Result<(), EncodeError>
+    where
+        O: TopEncodeOutput,
+    {
+        let mut buffer = output.start_nested_encode();
+        self.locked_token_nonce.dep_encode(&mut buffer)?;
+        if let Some(reward_info) = &self.opt_reward_info {
+            reward_info.dep_encode(&mut buffer)?;
+        }
+        output.finalize_nested_encode(buffer);
+
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> NestedEncode for WrappedTokenAttributes<M> {
+    fn dep_encode<O: NestedEncodeOutput>(&self, dest: &mut O) -> Result<(), EncodeError> {
+        self.locked_token_nonce.dep_encode(dest)?;
+        self.opt_reward_info.dep_encode(dest)?;
+
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for WrappedTokenAttributes<M> {
+    fn top_decode<I>(input: I) -> Result<Self, DecodeError>
+    where
+        I: TopDecodeInput,
+    {
+        let mut buffer = input.into_nested_buffer();
+        let locked_token_nonce = Nonce::dep_decode(&mut buffer)?;
+        let opt_reward_info = if buffer.is_depleted() {
+            None
+        } else {
+            Some(WrappedRewardInfo::dep_decode(&mut buffer)?)
+        };
+
+        if !buffer.is_depleted() {
+            return Result::Err(DecodeError::INPUT_TOO_LONG);
+        }
+
+        Result::Ok(WrappedTokenAttributes {
+            locked_token_nonce,
+            opt_reward_info,
+        })
+    }
+}
+
+impl<M: ManagedTypeApi> NestedDecode for WrappedTokenAttributes<M> {
+    fn dep_decode<I: NestedDecodeInput>(input: &mut I) -> Result<Self, DecodeError> {
+        Result::Ok(WrappedTokenAttributes {
+            locked_token_nonce: Nonce::dep_decode(input)?,
+            opt_reward_info: Option::<WrappedRewardInfo<M>>::dep_decode(input)?,
+        })
+    }
 }
 
 #[multiversx_sc::module]
@@ -72,10 +163,12 @@ pub trait WrappedTokenModule:
         &self,
         caller: &ManagedAddress,
         token: EsdtTokenPayment,
+        reward_info: WrappedRewardInfo<Self::Api>,
     ) -> EsdtTokenPayment {
         let wrapped_token_mapper = self.wrapped_token();
         let wrapped_token_attributes = WrappedTokenAttributes {
             locked_token_nonce: token.token_nonce,
+            opt_reward_info: Some(reward_info),
         };
         let wrapped_token_nonce = self.get_or_create_nonce_for_attributes(
             &wrapped_token_mapper,
@@ -90,21 +183,23 @@ pub trait WrappedTokenModule:
         &self,
         locked_token_id: TokenIdentifier,
         token: EsdtTokenPayment,
-    ) -> EsdtTokenPayment {
+    ) -> (EsdtTokenPayment, WrappedTokenAttributes<Self::Api>) {
         let wrapped_token_mapper = self.wrapped_token();
         wrapped_token_mapper.require_same_token(&token.token_identifier);
 
-        let wrapped_token_attributes: WrappedTokenAttributes =
+        let wrapped_token_attributes: WrappedTokenAttributes<Self::Api> =
             wrapped_token_mapper.get_token_attributes(token.token_nonce);
 
         self.send()
             .esdt_local_burn(&token.token_identifier, token.token_nonce, &token.amount);
 
-        EsdtTokenPayment::new(
+        let locked_tokens = EsdtTokenPayment::new(
             locked_token_id,
             wrapped_token_attributes.locked_token_nonce,
             token.amount,
-        )
+        );
+
+        (locked_tokens, wrapped_token_attributes)
     }
 
     #[view(getWrappedTokenId)]
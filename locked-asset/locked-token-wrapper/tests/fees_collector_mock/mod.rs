@@ -0,0 +1,53 @@
+use multiversx_sc::{
+    contract_base::{CallableContract, ContractBase},
+    io::finish_multi,
+    types::{EgldOrEsdtTokenIdentifier, EsdtTokenPayment, ManagedVec, TokenIdentifier},
+};
+use multiversx_sc_scenario::DebugApi;
+
+pub static REWARD_TOKEN_ID: &[u8] = b"REWARD-123456";
+
+static CLAIM_REWARDS_FN_NAME: &str = "claimRewards";
+static IS_PAUSED_FN_NAME: &str = "isPaused";
+
+#[derive(Clone)]
+pub struct FeesCollectorMock {}
+
+impl ContractBase for FeesCollectorMock {
+    type Api = DebugApi;
+}
+
+impl CallableContract for FeesCollectorMock {
+    fn call(&self, fn_name: &str) -> bool {
+        if fn_name == CLAIM_REWARDS_FN_NAME {
+            self.claim_rewards();
+            true
+        } else {
+            fn_name == IS_PAUSED_FN_NAME
+        }
+    }
+}
+
+impl FeesCollectorMock {
+    pub fn new() -> Self {
+        FeesCollectorMock {}
+    }
+
+    // The whole reward token balance is sent to the caller
+    pub fn claim_rewards(&self) {
+        let caller = self.blockchain().get_caller();
+        let reward_token_id = TokenIdentifier::<DebugApi>::from_esdt_bytes(REWARD_TOKEN_ID);
+        let balance = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::esdt(reward_token_id.clone()), 0);
+
+        let mut rewards = ManagedVec::<DebugApi, EsdtTokenPayment<DebugApi>>::new();
+        if balance > 0 {
+            self.send()
+                .direct_esdt(&caller, &reward_token_id, 0, &balance);
+            rewards.push(EsdtTokenPayment::new(reward_token_id, 0, balance));
+        }
+
+        finish_multi::<DebugApi, _>(&rewards);
+    }
+}
@@ -1,15 +1,24 @@
 use energy_factory_mock::EnergyFactoryMock;
 use energy_query::Energy;
+mod fees_collector_mock;
+
+use fees_collector_mock::*;
 use locked_token_wrapper::{
-    wrapped_token::{WrappedTokenAttributes, WrappedTokenModule},
+    wrapped_rewards::{WrappedRewardsModule, DIVISION_SAFETY_CONSTANT},
+    wrapped_token::{
+        TokenRewardPerShare, WrappedRewardInfo, WrappedTokenAttributes, WrappedTokenModule,
+    },
     LockedTokenWrapper,
 };
-use multiversx_sc::{storage::mappers::StorageTokenWrapper, types::EsdtLocalRole};
+use multiversx_sc::{
+    storage::mappers::StorageTokenWrapper,
+    types::{EsdtLocalRole, ManagedVec},
+};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
     whitebox::BlockchainStateWrapper, DebugApi,
 };
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 
 static BASE_ASSET_TOKEN_ID: &[u8] = b"FREEEE-123456";
 static LOCKED_TOKEN_ID: &[u8] = b"LOCKED-123456";
@@ -67,6 +76,8 @@ fn token_wrap_unwrap_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 20,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         },
     );
 
@@ -103,8 +114,11 @@ fn token_wrap_unwrap_test() {
         WRAPPED_TOKEN_ID,
         1,
         &rust_biguint!(500),
-        Some(&WrappedTokenAttributes {
+        Some(&WrappedTokenAttributes::<DebugApi> {
             locked_token_nonce: 1,
+            opt_reward_info: Some(WrappedRewardInfo {
+                reward_per_share: ManagedVec::new(),
+            }),
         }),
     );
 
@@ -127,8 +141,11 @@ fn token_wrap_unwrap_test() {
         WRAPPED_TOKEN_ID,
         1,
         &rust_biguint!(500),
-        &WrappedTokenAttributes {
+        &WrappedTokenAttributes::<DebugApi> {
             locked_token_nonce: 1,
+            opt_reward_info: Some(WrappedRewardInfo {
+                reward_per_share: ManagedVec::new(),
+            }),
         },
     );
 
@@ -158,6 +175,8 @@ fn token_wrap_unwrap_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 20,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -227,6 +246,8 @@ fn tokens_wrap_unwrap_test2() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: first_user_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         },
     );
 
@@ -267,8 +288,11 @@ fn tokens_wrap_unwrap_test2() {
         WRAPPED_TOKEN_ID,
         1,
         &rust_biguint!(user_half_balance),
-        Some(&WrappedTokenAttributes {
+        Some(&WrappedTokenAttributes::<DebugApi> {
             locked_token_nonce: 1,
+            opt_reward_info: Some(WrappedRewardInfo {
+                reward_per_share: ManagedVec::new(),
+            }),
         }),
     );
 
@@ -314,6 +338,8 @@ fn tokens_wrap_unwrap_test2() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: first_user_unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -330,3 +356,414 @@ fn tokens_wrap_unwrap_test2() {
         })
         .assert_ok();
 }
+
+#[test]
+fn wrapped_tokens_rewards_test() {
+    let _ = DebugApi::dummy();
+    let rust_zero = rust_biguint!(0);
+
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let first_user = b_mock.create_user_account(&rust_zero);
+    let second_user = b_mock.create_user_account(&rust_zero);
+    let user_balance = 1_000u64;
+    let unlock_epoch = 100u64;
+    let energy_factory = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        energy_factory_mock::contract_obj,
+        "energy factory mock",
+    );
+    let fees_collector = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        FeesCollectorMock::new,
+        "fees collector mock",
+    );
+    let locked_token_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        locked_token_wrapper::contract_obj,
+        "locked token wrapper",
+    );
+
+    // setup wrapping SC
+    b_mock
+        .execute_tx(&owner, &locked_token_wrapper, &rust_zero, |sc| {
+            sc.init(managed_address!(energy_factory.address_ref()));
+            sc.set_fees_collector_address(managed_address!(fees_collector.address_ref()));
+
+            sc.wrapped_token()
+                .set_token_id(managed_token_id!(WRAPPED_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        locked_token_wrapper.address_ref(),
+        WRAPPED_TOKEN_ID,
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ],
+    );
+
+    let locked_token_attributes = LockedTokenAttributes::<DebugApi> {
+        original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+        original_token_nonce: 0,
+        unlock_epoch,
+        perpetual_lock_epochs: 0,
+        energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+    };
+    for user in [&first_user, &second_user] {
+        b_mock.set_nft_balance(
+            user,
+            LOCKED_TOKEN_ID,
+            1,
+            &rust_biguint!(user_balance),
+            &locked_token_attributes,
+        );
+    }
+
+    b_mock
+        .execute_tx(&owner, &energy_factory, &rust_zero, |sc| {
+            sc.locked_token()
+                .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+
+            for user in [&first_user, &second_user] {
+                let energy = Energy::new(
+                    (managed_biguint!(user_balance) * unlock_epoch).into(),
+                    0,
+                    managed_biguint!(user_balance),
+                );
+                sc.user_energy(&managed_address!(user)).set(&energy);
+            }
+        })
+        .assert_ok();
+
+    // both users wrap their tokens
+    for user in [&first_user, &second_user] {
+        b_mock
+            .execute_esdt_transfer(
+                user,
+                &locked_token_wrapper,
+                LOCKED_TOKEN_ID,
+                1,
+                &rust_biguint!(user_balance),
+                |sc| {
+                    let _ = sc.wrap_locked_token_endpoint();
+                },
+            )
+            .assert_ok();
+    }
+
+    // the wrapper holds the energy of all wrapped tokens
+    b_mock
+        .execute_query(&energy_factory, |sc| {
+            let expected_energy = Energy::new(
+                (managed_biguint!(2 * user_balance) * unlock_epoch).into(),
+                0,
+                managed_biguint!(2 * user_balance),
+            );
+            let actual_energy = sc
+                .user_energy(&managed_address!(locked_token_wrapper.address_ref()))
+                .get();
+            assert_eq!(actual_energy, expected_energy);
+        })
+        .assert_ok();
+
+    // fees collector rewards are available for the wrapper
+    let total_rewards = 2_000u64;
+    b_mock.set_esdt_balance(
+        fees_collector.address_ref(),
+        REWARD_TOKEN_ID,
+        &rust_biguint!(total_rewards),
+    );
+
+    // first user claims their share
+    b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &locked_token_wrapper,
+            WRAPPED_TOKEN_ID,
+            1,
+            &rust_biguint!(user_balance),
+            |sc| {
+                let _ = sc.claim_wrapped_rewards();
+            },
+        )
+        .assert_ok();
+
+    b_mock.check_esdt_balance(
+        &first_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(total_rewards / 2),
+    );
+    b_mock.check_nft_balance(
+        &first_user,
+        WRAPPED_TOKEN_ID,
+        2,
+        &rust_biguint!(user_balance),
+        Some(&WrappedTokenAttributes::<DebugApi> {
+            locked_token_nonce: 1,
+            opt_reward_info: Some(WrappedRewardInfo {
+                reward_per_share: ManagedVec::from_single_item(TokenRewardPerShare {
+                    token_id: managed_token_id!(REWARD_TOKEN_ID),
+                    reward_per_share: managed_biguint!(total_rewards) * DIVISION_SAFETY_CONSTANT
+                        / (2 * user_balance),
+                }),
+            }),
+        }),
+    );
+
+    // nothing left to claim for the new wrapped tokens
+    b_mock
+        .execute_query(&locked_token_wrapper, |sc| {
+            let pending_rewards = sc.get_pending_wrapped_rewards(2, managed_biguint!(user_balance));
+            assert!(pending_rewards.is_empty());
+
+            let pending_rewards: Vec<_> = sc
+                .get_pending_wrapped_rewards(1, managed_biguint!(user_balance))
+                .into_iter()
+                .collect();
+            assert_eq!(pending_rewards.len(), 1);
+            assert_eq!(
+                pending_rewards[0].token_identifier,
+                managed_token_id!(REWARD_TOKEN_ID)
+            );
+            assert_eq!(
+                pending_rewards[0].amount,
+                managed_biguint!(total_rewards / 2)
+            );
+        })
+        .assert_ok();
+
+    // second user receives the rewards on unwrap
+    b_mock
+        .execute_esdt_transfer(
+            &second_user,
+            &locked_token_wrapper,
+            WRAPPED_TOKEN_ID,
+            1,
+            &rust_biguint!(user_balance),
+            |sc| {
+                let _ = sc.unwrap_locked_token_endpoint();
+            },
+        )
+        .assert_ok();
+
+    b_mock.check_esdt_balance(
+        &second_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(total_rewards / 2),
+    );
+    b_mock.check_nft_balance(
+        &second_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(user_balance),
+        Some(&locked_token_attributes),
+    );
+
+    b_mock
+        .execute_query(&locked_token_wrapper, |sc| {
+            assert_eq!(sc.wrapped_supply(1).get(), managed_biguint!(user_balance));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn wrapped_tokens_energy_weighted_rewards_test() {
+    let _ = DebugApi::dummy();
+    let rust_zero = rust_biguint!(0);
+
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let first_user = b_mock.create_user_account(&rust_zero);
+    let second_user = b_mock.create_user_account(&rust_zero);
+    let user_balance = 1_000u64;
+    let first_unlock_epoch = 100u64;
+    let second_unlock_epoch = 300u64;
+    let energy_factory = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        energy_factory_mock::contract_obj,
+        "energy factory mock",
+    );
+    let fees_collector = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        FeesCollectorMock::new,
+        "fees collector mock",
+    );
+    let locked_token_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        locked_token_wrapper::contract_obj,
+        "locked token wrapper",
+    );
+
+    // setup wrapping SC
+    b_mock
+        .execute_tx(&owner, &locked_token_wrapper, &rust_zero, |sc| {
+            sc.init(managed_address!(energy_factory.address_ref()));
+            sc.set_fees_collector_address(managed_address!(fees_collector.address_ref()));
+
+            sc.wrapped_token()
+                .set_token_id(managed_token_id!(WRAPPED_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        locked_token_wrapper.address_ref(),
+        WRAPPED_TOKEN_ID,
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ],
+    );
+
+    // same amount of tokens, with different unlock epochs
+    let users = [
+        (&first_user, 1u64, first_unlock_epoch),
+        (&second_user, 2u64, second_unlock_epoch),
+    ];
+    for (user, locked_token_nonce, unlock_epoch) in users {
+        b_mock.set_nft_balance(
+            user,
+            LOCKED_TOKEN_ID,
+            locked_token_nonce,
+            &rust_biguint!(user_balance),
+            &LockedTokenAttributes::<DebugApi> {
+                original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+                original_token_nonce: 0,
+                unlock_epoch,
+                perpetual_lock_epochs: 0,
+                energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+            },
+        );
+    }
+
+    b_mock
+        .execute_tx(&owner, &energy_factory, &rust_zero, |sc| {
+            sc.locked_token()
+                .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+
+            for (user, _, unlock_epoch) in users {
+                let energy = Energy::new(
+                    (managed_biguint!(user_balance) * unlock_epoch).into(),
+                    0,
+                    managed_biguint!(user_balance),
+                );
+                sc.user_energy(&managed_address!(user)).set(&energy);
+            }
+        })
+        .assert_ok();
+
+    for (user, locked_token_nonce, _) in users {
+        b_mock
+            .execute_esdt_transfer(
+                user,
+                &locked_token_wrapper,
+                LOCKED_TOKEN_ID,
+                locked_token_nonce,
+                &rust_biguint!(user_balance),
+                |sc| {
+                    let _ = sc.wrap_locked_token_endpoint();
+                },
+            )
+            .assert_ok();
+    }
+
+    // 50 epochs left for the first user's tokens, 250 for the second user's
+    let mut current_epoch = 50;
+    b_mock.set_block_epoch(current_epoch);
+
+    let first_rewards = 6_000u64;
+    b_mock.set_esdt_balance(
+        fees_collector.address_ref(),
+        REWARD_TOKEN_ID,
+        &rust_biguint!(first_rewards),
+    );
+
+    b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &locked_token_wrapper,
+            WRAPPED_TOKEN_ID,
+            1,
+            &rust_biguint!(user_balance),
+            |sc| {
+                let _ = sc.claim_wrapped_rewards();
+            },
+        )
+        .assert_ok();
+
+    // rewards are split by the current energy, i.e. 1:5
+    b_mock.check_esdt_balance(
+        &first_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(first_rewards / 6),
+    );
+
+    b_mock
+        .execute_query(&locked_token_wrapper, |sc| {
+            assert_eq!(
+                sc.reward_per_share(2, &managed_token_id!(REWARD_TOKEN_ID))
+                    .get(),
+                managed_biguint!(first_rewards * 5 / 6) * DIVISION_SAFETY_CONSTANT / user_balance
+            );
+        })
+        .assert_ok();
+
+    // the first user's tokens can be unlocked already, so they stop earning rewards
+    current_epoch = first_unlock_epoch;
+    b_mock.set_block_epoch(current_epoch);
+
+    let second_rewards = 3_000u64;
+    b_mock.set_esdt_balance(
+        fees_collector.address_ref(),
+        REWARD_TOKEN_ID,
+        &rust_biguint!(second_rewards),
+    );
+
+    b_mock
+        .execute_esdt_transfer(
+            &second_user,
+            &locked_token_wrapper,
+            WRAPPED_TOKEN_ID,
+            2,
+            &rust_biguint!(user_balance),
+            |sc| {
+                let _ = sc.claim_wrapped_rewards();
+            },
+        )
+        .assert_ok();
+
+    b_mock.check_esdt_balance(
+        &second_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(first_rewards * 5 / 6 + second_rewards),
+    );
+
+    // nothing earned by the expired tokens
+    b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &locked_token_wrapper,
+            WRAPPED_TOKEN_ID,
+            3,
+            &rust_biguint!(user_balance),
+            |sc| {
+                let _ = sc.claim_wrapped_rewards();
+            },
+        )
+        .assert_ok();
+
+    b_mock.check_esdt_balance(
+        &first_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(first_rewards / 6),
+    );
+}
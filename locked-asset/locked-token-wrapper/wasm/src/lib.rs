@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            8
+// Endpoints:                           17
 // Async Callback:                       1
-// Total number of exported functions:  10
+// Total number of exported functions:  19
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -24,6 +24,15 @@ multiversx_sc_wasm_adapter::endpoints! {
         setTransferRoleWrappedToken
         unsetTransferRoleWrappedToken
         getWrappedTokenId
+        setFeesCollectorAddress
+        claimWrappedRewards
+        getPendingWrappedRewards
+        getFeesCollectorAddress
+        getWrappedLockedNonces
+        getWrappedSupply
+        getRewardTokens
+        getRewardPerShare
+        getLockedRewardsPool
         setEnergyFactoryAddress
         getEnergyFactoryAddress
         callBack
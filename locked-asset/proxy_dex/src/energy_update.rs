@@ -36,7 +36,7 @@ pub trait EnergyUpdateModule:
         token_amount: &BigUint,
     ) {
         let current_epoch = self.blockchain().get_block_epoch();
-        let mut energy = self.get_energy_entry(user);
+        let mut energy = self.get_own_energy_entry(user);
 
         let new_locked_token_id = self.get_locked_token_id();
         let old_locked_token_id = self.old_locked_token_id().get();
@@ -44,7 +44,19 @@ pub trait EnergyUpdateModule:
             let attributes: LockedTokenAttributes<Self::Api> = self
                 .blockchain()
                 .get_token_attributes(token_id, token_nonce);
-            energy.update_after_unlock_any(token_amount, attributes.unlock_epoch, current_epoch);
+            let energy_weighted_amount = attributes.get_energy_weighted_amount(token_amount);
+            if attributes.is_perpetual() {
+                energy.remove_after_perpetual_unlock(
+                    &energy_weighted_amount,
+                    attributes.perpetual_lock_epochs,
+                );
+            } else {
+                energy.update_after_unlock_any(
+                    &energy_weighted_amount,
+                    attributes.unlock_epoch,
+                    current_epoch,
+                );
+            }
         } else if token_id == &old_locked_token_id {
             if self.blockchain().is_smart_contract(user) {
                 return;
@@ -47,6 +47,14 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for WrappedFarmTokenAttributes<M> {
             proxy_farming_token: new_proxy_farming_tokens,
         }
     }
+
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self {
+        let part = self.clone().into_part(payment_amount);
+        self.farm_token.amount -= &part.farm_token.amount;
+        self.proxy_farming_token.amount -= &part.proxy_farming_token.amount;
+
+        part
+    }
 }
 
 impl<M: ManagedTypeApi> ExternallyMergeable<M> for WrappedFarmTokenAttributes<M> {
@@ -45,6 +45,26 @@ pub trait WrappedFarmTokenMerge:
         merged_tokens
     }
 
+    #[payable("*")]
+    #[endpoint(splitWrappedFarmToken)]
+    fn split_wrapped_farm_token_endpoint(
+        &self,
+        split_amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let wrapped_farm_token_mapper = self.wrapped_farm_token();
+        let new_wrapped_farm_tokens = self
+            .split_and_create_tokens::<WrappedFarmTokenAttributes<Self::Api>>(
+                &payment,
+                split_amounts,
+                &wrapped_farm_token_mapper,
+            );
+        self.send().direct_multi(&caller, &new_wrapped_farm_tokens);
+
+        new_wrapped_farm_tokens.into()
+    }
+
     fn merge_wrapped_farm_tokens_with_virtual_pos(
         &self,
         caller: &ManagedAddress,
@@ -46,6 +46,14 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for WrappedLpTokenAttributes<M> {
             locked_tokens: new_locked_tokens,
         }
     }
+
+    fn split_part(&mut self, payment_amount: &BigUint<M>) -> Self {
+        let part = self.clone().into_part(payment_amount);
+        self.lp_token_amount -= &part.lp_token_amount;
+        self.locked_tokens.amount -= &part.locked_tokens.amount;
+
+        part
+    }
 }
 
 impl<M: ManagedTypeApi> ExternallyMergeable<M> for WrappedLpTokenAttributes<M> {
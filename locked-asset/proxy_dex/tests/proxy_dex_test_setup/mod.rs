@@ -21,7 +21,9 @@ use pair::{config::ConfigModule as OtherConfigModule, safe_price::SafePriceModul
 use pausable::{PausableModule, State};
 use proxy_dex::{proxy_common::ProxyCommonModule, sc_whitelist::ScWhitelistModule, ProxyDexImpl};
 use sc_whitelist_module::SCWhitelistModule;
-use simple_lock::locked_token::{LockedTokenAttributes, LockedTokenModule};
+use simple_lock::locked_token::{
+    LockedTokenAttributes, LockedTokenModule, DEFAULT_ENERGY_MULTIPLIER,
+};
 
 // General
 pub static MEX_TOKEN_ID: &[u8] = b"MEX-123456";
@@ -183,6 +185,8 @@ where
                 original_token_id: managed_token_id_wrapped!(MEX_TOKEN_ID),
                 original_token_nonce: 0,
                 unlock_epoch: 360,
+                perpetual_lock_epochs: 0,
+                energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
             }),
         );
 
@@ -195,6 +199,8 @@ where
                 original_token_id: managed_token_id_wrapped!(MEX_TOKEN_ID),
                 original_token_nonce: 0,
                 unlock_epoch: 1_800,
+                perpetual_lock_epochs: 0,
+                energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
             }),
         );
 
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           25
+// Endpoints:                           26
 // Async Callback:                       1
-// Total number of exported functions:  27
+// Total number of exported functions:  28
 
 #![no_std]
 #![feature(alloc_error_handler, lang_items)]
@@ -40,6 +40,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         exitFarmProxy
         claimRewardsProxy
         mergeWrappedFarmTokens
+        splitWrappedFarmToken
         mergeWrappedLpTokens
         setEnergyFactoryAddress
         getEnergyFactoryAddress
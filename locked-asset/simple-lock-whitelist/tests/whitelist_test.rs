@@ -90,6 +90,8 @@ fn lock_whitelist_test() {
             original_token_id: managed_token_id_wrapped!(FREE_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 10,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 }
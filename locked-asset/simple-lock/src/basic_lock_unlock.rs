@@ -2,7 +2,7 @@ multiversx_sc::imports!();
 
 use crate::{
     error_messages::{CANNOT_UNLOCK_YET_ERR_MSG, NO_PAYMENT_ERR_MSG},
-    locked_token::LockedTokenAttributes,
+    locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER},
 };
 
 #[multiversx_sc::module]
@@ -15,6 +15,15 @@ pub trait BasicLockUnlock:
         &self,
         payment: EgldOrEsdtTokenPayment<Self::Api>,
         unlock_epoch: u64,
+    ) -> EgldOrEsdtTokenPayment<Self::Api> {
+        self.lock_tokens_with_energy_multiplier(payment, unlock_epoch, DEFAULT_ENERGY_MULTIPLIER)
+    }
+
+    fn lock_tokens_with_energy_multiplier(
+        &self,
+        payment: EgldOrEsdtTokenPayment<Self::Api>,
+        unlock_epoch: u64,
+        energy_multiplier: u64,
     ) -> EgldOrEsdtTokenPayment<Self::Api> {
         require!(payment.amount > 0, NO_PAYMENT_ERR_MSG);
 
@@ -23,11 +32,12 @@ pub trait BasicLockUnlock:
             return payment;
         }
 
-        let attributes = LockedTokenAttributes {
-            original_token_id: payment.token_identifier.clone(),
-            original_token_nonce: payment.token_nonce,
+        let mut attributes = LockedTokenAttributes::new(
+            payment.token_identifier.clone(),
+            payment.token_nonce,
             unlock_epoch,
-        };
+        );
+        attributes.energy_multiplier = energy_multiplier;
         let locked_token_mapper = self.locked_token();
         let sft_nonce = self.get_or_create_nonce_for_attributes(
             &locked_token_mapper,
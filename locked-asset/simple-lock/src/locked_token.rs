@@ -1,11 +1,166 @@
+use multiversx_sc::codec::{
+    NestedDecodeInput, NestedEncodeOutput, TopDecodeInput, TopEncodeOutput,
+};
+
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-#[derive(TypeAbi, TopEncode, TopDecode, NestedDecode, NestedEncode, PartialEq, Debug, Clone)]
+/// Unlock epoch used for tokens in perpetual lock mode,
+/// as these can never be unlocked before the mode is disabled
+pub const PERPETUAL_LOCK_UNLOCK_EPOCH: u64 = u64::MAX;
+
+/// Energy multiplier of tokens locked for an option without a bonus, i.e. 1x
+pub const DEFAULT_ENERGY_MULTIPLIER: u64 = 10_000;
+
+#[derive(TypeAbi, PartialEq, Debug, Clone)]
 pub struct LockedTokenAttributes<M: ManagedTypeApi> {
     pub original_token_id: EgldOrEsdtTokenIdentifier<M>,
     pub original_token_nonce: u64,
     pub unlock_epoch: u64,
+    pub perpetual_lock_epochs: u64,
+    pub energy_multiplier: u64,
+}
+
+impl<M: ManagedTypeApi> LockedTokenAttributes<M> {
+    pub fn new(
+        original_token_id: EgldOrEsdtTokenIdentifier<M>,
+        original_token_nonce: u64,
+        unlock_epoch: u64,
+    ) -> Self {
+        LockedTokenAttributes {
+            original_token_id,
+            original_token_nonce,
+            unlock_epoch,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
+        }
+    }
+
+    pub fn new_perpetual(
+        original_token_id: EgldOrEsdtTokenIdentifier<M>,
+        original_token_nonce: u64,
+        lock_epochs: u64,
+        energy_multiplier: u64,
+    ) -> Self {
+        LockedTokenAttributes {
+            original_token_id,
+            original_token_nonce,
+            unlock_epoch: PERPETUAL_LOCK_UNLOCK_EPOCH,
+            perpetual_lock_epochs: lock_epochs,
+            energy_multiplier,
+        }
+    }
+
+    #[inline]
+    pub fn is_perpetual(&self) -> bool {
+        self.perpetual_lock_epochs > 0
+    }
+
+    #[inline]
+    pub fn has_default_energy_multiplier(&self) -> bool {
+        self.energy_multiplier == DEFAULT_ENERGY_MULTIPLIER
+    }
+
+    #[inline]
+    pub fn get_energy_weighted_amount(&self, token_amount: &BigUint<M>) -> BigUint<M> {
+        apply_energy_multiplier(token_amount, self.energy_multiplier)
+    }
+}
+
+/// The amount used for energy computations, i.e. the token amount scaled by the energy multiplier
+pub fn apply_energy_multiplier<M: ManagedTypeApi>(
+    token_amount: &BigUint<M>,
+    energy_multiplier: u64,
+) -> BigUint<M> {
+    if energy_multiplier == DEFAULT_ENERGY_MULTIPLIER {
+        return token_amount.clone();
+    }
+
+    token_amount * energy_multiplier / DEFAULT_ENERGY_MULTIPLIER
+}
+
+/// The trailing fields are only encoded when they differ from the defaults,
+/// so regular tokens keep the same attributes, and implicitly, the same nonces.
+///
+/// The compact form is only used for the top-level encoding, i.e. the token attributes,
+/// as the input end is known there. The nested encoding always contains all the fields.
+impl<M: ManagedTypeApi> TopEncode for LockedTokenAttributes<M> {
+    fn top_encode<O>(&self, output: O) -> Result<(), EncodeError>
+    where
+        O: TopEncodeOutput,
+    {
+        let mut buffer = output.start_nested_encode();
+        self.original_token_id.dep_encode(&mut buffer)?;
+        self.original_token_nonce.dep_encode(&mut buffer)?;
+        self.unlock_epoch.dep_encode(&mut buffer)?;
+        if self.is_perpetual() || !self.has_default_energy_multiplier() {
+            self.perpetual_lock_epochs.dep_encode(&mut buffer)?;
+        }
+        if !self.has_default_energy_multiplier() {
+            self.energy_multiplier.dep_encode(&mut buffer)?;
+        }
+        output.finalize_nested_encode(buffer);
+
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> NestedEncode for LockedTokenAttributes<M> {
+    fn dep_encode<O: NestedEncodeOutput>(&self, dest: &mut O) -> Result<(), EncodeError> {
+        self.original_token_id.dep_encode(dest)?;
+        self.original_token_nonce.dep_encode(dest)?;
+        self.unlock_epoch.dep_encode(dest)?;
+        self.perpetual_lock_epochs.dep_encode(dest)?;
+        self.energy_multiplier.dep_encode(dest)?;
+
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for LockedTokenAttributes<M> {
+    fn top_decode<I>(input: I) -> Result<Self, DecodeError>
+    where
+        I: TopDecodeInput,
+    {
+        let mut buffer = input.into_nested_buffer();
+        let original_token_id = EgldOrEsdtTokenIdentifier::dep_decode(&mut buffer)?;
+        let original_token_nonce = u64::dep_decode(&mut buffer)?;
+        let unlock_epoch = u64::dep_decode(&mut buffer)?;
+        let perpetual_lock_epochs = if buffer.is_depleted() {
+            0
+        } else {
+            u64::dep_decode(&mut buffer)?
+        };
+        let energy_multiplier = if buffer.is_depleted() {
+            DEFAULT_ENERGY_MULTIPLIER
+        } else {
+            u64::dep_decode(&mut buffer)?
+        };
+
+        if !buffer.is_depleted() {
+            return Result::Err(DecodeError::INPUT_TOO_LONG);
+        }
+
+        Result::Ok(LockedTokenAttributes {
+            original_token_id,
+            original_token_nonce,
+            unlock_epoch,
+            perpetual_lock_epochs,
+            energy_multiplier,
+        })
+    }
+}
+
+impl<M: ManagedTypeApi> NestedDecode for LockedTokenAttributes<M> {
+    fn dep_decode<I: NestedDecodeInput>(input: &mut I) -> Result<Self, DecodeError> {
+        Result::Ok(LockedTokenAttributes {
+            original_token_id: EgldOrEsdtTokenIdentifier::dep_decode(input)?,
+            original_token_nonce: u64::dep_decode(input)?,
+            unlock_epoch: u64::dep_decode(input)?,
+            perpetual_lock_epochs: u64::dep_decode(input)?,
+            energy_multiplier: u64::dep_decode(input)?,
+        })
+    }
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -1,4 +1,5 @@
 use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::codec::{top_encode_to_vec_u8_or_panic, TopDecode};
 use multiversx_sc::types::EsdtLocalRole;
 use multiversx_sc_scenario::{managed_biguint, managed_token_id_wrapped, whitebox::*};
 use multiversx_sc_scenario::{managed_token_id, rust_biguint, DebugApi};
@@ -74,6 +75,8 @@ fn lock_unlock_test() {
             original_token_id: managed_token_id_wrapped!(FREE_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: 10,
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -160,3 +163,35 @@ fn lock_unlock_test() {
         )
         .assert_ok();
 }
+
+#[test]
+fn locked_token_attributes_encoding_test() {
+    let _ = DebugApi::dummy();
+
+    // default attributes keep the compact top-level encoding
+    let regular_attributes =
+        LockedTokenAttributes::<DebugApi>::new(managed_token_id_wrapped!(FREE_TOKEN_ID), 0, 10);
+    let encoded = top_encode_to_vec_u8_or_panic(&regular_attributes);
+    let legacy_encoded =
+        top_encode_to_vec_u8_or_panic(&(managed_token_id_wrapped!(FREE_TOKEN_ID), 0u64, 10u64));
+    assert_eq!(encoded, legacy_encoded);
+    assert_eq!(
+        LockedTokenAttributes::<DebugApi>::top_decode(&encoded[..]).unwrap(),
+        regular_attributes
+    );
+
+    // nested attributes are followed by other values
+    let perpetual_attributes = LockedTokenAttributes::<DebugApi>::new_perpetual(
+        managed_token_id_wrapped!(FREE_TOKEN_ID),
+        0,
+        360,
+        15_000,
+    );
+    for attributes in [regular_attributes, perpetual_attributes] {
+        let encoded = top_encode_to_vec_u8_or_panic(&(attributes.clone(), 5u64));
+        let (decoded_attributes, decoded_value) =
+            <(LockedTokenAttributes<DebugApi>, u64)>::top_decode(&encoded[..]).unwrap();
+        assert_eq!(decoded_attributes, attributes);
+        assert_eq!(decoded_value, 5u64);
+    }
+}
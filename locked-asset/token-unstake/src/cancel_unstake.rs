@@ -18,7 +18,7 @@ pub trait CancelUnstakeModule:
         let current_epoch = self.blockchain().get_block_epoch();
 
         let mut output_payments = ManagedVec::new();
-        let mut energy = self.get_energy_entry(&caller);
+        let mut energy = self.get_own_energy_entry(&caller);
 
         let entries_mapper = self.unlocked_tokens_for_user(&caller);
         let user_entries = entries_mapper.get();
@@ -29,17 +29,19 @@ pub trait CancelUnstakeModule:
             let attributes: LockedTokenAttributes<Self::Api> = self
                 .blockchain()
                 .get_token_attributes(&locked_tokens.token_identifier, locked_tokens.token_nonce);
+            let energy_weighted_amount =
+                attributes.get_energy_weighted_amount(&locked_tokens.amount);
             if attributes.unlock_epoch >= current_epoch {
                 energy.add_after_token_lock(
-                    &locked_tokens.amount,
+                    &energy_weighted_amount,
                     attributes.unlock_epoch,
                     current_epoch,
                 );
             } else {
                 // account for energy refund on unlock
                 let epoch_diff = current_epoch - attributes.unlock_epoch;
-                let energy_to_reduce = &locked_tokens.amount * epoch_diff;
-                energy.add_energy_raw(locked_tokens.amount.clone(), BigInt::zero());
+                let energy_to_reduce = &energy_weighted_amount * epoch_diff;
+                energy.add_energy_raw(energy_weighted_amount, BigInt::zero());
                 energy.remove_energy_raw(BigUint::zero(), energy_to_reduce);
             }
 
@@ -6,7 +6,7 @@ use multiversx_sc_scenario::{
 };
 use num_bigint::ToBigInt;
 use num_traits::cast::ToPrimitive;
-use simple_lock::locked_token::LockedTokenAttributes;
+use simple_lock::locked_token::{LockedTokenAttributes, DEFAULT_ENERGY_MULTIPLIER};
 use token_unstake::tokens_per_user::{TokensPerUserModule, UnstakePair};
 use token_unstake_setup::*;
 
@@ -88,6 +88,8 @@ fn cancel_unbond_test() {
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: LOCK_OPTIONS[0],
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -142,6 +144,8 @@ where
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: LOCK_OPTIONS[1],
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 
@@ -168,6 +172,8 @@ where
             original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
             original_token_nonce: 0,
             unlock_epoch: LOCK_OPTIONS[0],
+            perpetual_lock_epochs: 0,
+            energy_multiplier: DEFAULT_ENERGY_MULTIPLIER,
         }),
     );
 